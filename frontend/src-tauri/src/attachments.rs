@@ -0,0 +1,386 @@
+use crate::backup::ATTACHMENTS_DIR;
+use crate::{AppState, AttachmentState, ProjectState, data_dir, read_state_file, write_export_file};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use tauri_plugin_opener::OpenerExt;
+
+const THUMBNAILS_DIR: &str = "thumbnails";
+const OPEN_DIR: &str = "project-notes-studio-attachments";
+const THUMBNAIL_SIZE: u32 = 256;
+const MAX_ATTACHMENT_BYTES: usize = 64 * 1024 * 1024;
+const ORPHAN_GRACE: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+const SAFE_TO_OPEN: [&str; 9] = [
+  "image/png",
+  "image/jpeg",
+  "image/gif",
+  "image/webp",
+  "image/bmp",
+  "application/pdf",
+  "text/plain",
+  "text/markdown",
+  "text/csv",
+];
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CleanupReport {
+  kept_blobs: usize,
+  removed_blobs: usize,
+  freed_bytes: u64,
+}
+
+fn is_hash(value: &str) -> bool {
+  value.len() == 64 && value.bytes().all(|byte| matches!(byte, b'0'..=b'9' | b'a'..=b'f'))
+}
+
+fn blob_path(root: &Path, hash: &str) -> Result<PathBuf, String> {
+  if !is_hash(hash) {
+    return Err("invalid attachment hash".to_string());
+  }
+  Ok(root.join(ATTACHMENTS_DIR).join(&hash[..2]).join(hash))
+}
+
+fn thumbnail_path(root: &Path, hash: &str) -> PathBuf {
+  root.join(THUMBNAILS_DIR).join(format!("{hash}.png"))
+}
+
+fn guess_mime(name: &str) -> &'static str {
+  let extension = Path::new(name)
+    .extension()
+    .and_then(|value| value.to_str())
+    .unwrap_or_default()
+    .to_lowercase();
+  match extension.as_str() {
+    "png" => "image/png",
+    "jpg" | "jpeg" => "image/jpeg",
+    "gif" => "image/gif",
+    "webp" => "image/webp",
+    "bmp" => "image/bmp",
+    "svg" => "image/svg+xml",
+    "pdf" => "application/pdf",
+    "txt" | "log" => "text/plain",
+    "md" => "text/markdown",
+    "csv" => "text/csv",
+    "json" => "application/json",
+    "zip" => "application/zip",
+    _ => "application/octet-stream",
+  }
+}
+
+fn opens_safely(name: &str) -> bool {
+  SAFE_TO_OPEN.contains(&guess_mime(name))
+}
+
+fn clean_name(name: &str) -> String {
+  let name = Path::new(name.trim())
+    .file_name()
+    .and_then(|value| value.to_str())
+    .unwrap_or_default()
+    .trim()
+    .to_string();
+  if name.is_empty() { "attachment".to_string() } else { name }
+}
+
+fn store_blob(root: &Path, bytes: &[u8]) -> Result<String, String> {
+  let hash = hex::encode(Sha256::digest(bytes));
+  let path = blob_path(root, &hash)?;
+  if !path.exists() {
+    let parent = path.parent().expect("blob path has a shard directory");
+    fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+    let partial = parent.join(format!("{hash}.part"));
+    fs::write(&partial, bytes).map_err(|err| err.to_string())?;
+    fs::rename(&partial, &path).map_err(|err| err.to_string())?;
+  }
+  Ok(hash)
+}
+
+fn render_thumbnail(bytes: &[u8]) -> Option<Vec<u8>> {
+  let image = image::load_from_memory(bytes).ok()?;
+  let mut png = Cursor::new(Vec::new());
+  image
+    .thumbnail(THUMBNAIL_SIZE, THUMBNAIL_SIZE)
+    .write_to(&mut png, image::ImageFormat::Png)
+    .ok()?;
+  Some(png.into_inner())
+}
+
+fn ensure_thumbnail(root: &Path, hash: &str) -> Result<Option<Vec<u8>>, String> {
+  let path = thumbnail_path(root, hash);
+  if path.exists() {
+    return fs::read(path).map(Some).map_err(|err| err.to_string());
+  }
+  let bytes = fs::read(blob_path(root, hash)?).map_err(|_| "attachment file is missing".to_string())?;
+  let Some(thumbnail) = render_thumbnail(&bytes) else {
+    return Ok(None);
+  };
+  fs::create_dir_all(root.join(THUMBNAILS_DIR)).map_err(|err| err.to_string())?;
+  fs::write(path, &thumbnail).map_err(|err| err.to_string())?;
+  Ok(Some(thumbnail))
+}
+
+fn add(root: &Path, name: &str, bytes: &[u8]) -> Result<AttachmentState, String> {
+  if bytes.len() > MAX_ATTACHMENT_BYTES {
+    return Err(format!("attachment is larger than {} MB", MAX_ATTACHMENT_BYTES / (1024 * 1024)));
+  }
+  let name = clean_name(name);
+  let hash = store_blob(root, bytes)?;
+  let mime = guess_mime(&name);
+  if mime.starts_with("image/") {
+    ensure_thumbnail(root, &hash)?;
+  }
+  Ok(AttachmentState { hash, name, mime: mime.to_string(), size: bytes.len() as u64 })
+}
+
+fn reference_counts<'a>(projects: impl IntoIterator<Item = &'a ProjectState>) -> BTreeMap<&'a str, usize> {
+  let mut counts = BTreeMap::new();
+  for project in projects {
+    for note in &project.notes {
+      for attachment in &note.attachments {
+        *counts.entry(attachment.hash.as_str()).or_insert(0) += 1;
+      }
+    }
+  }
+  counts
+}
+
+fn stored_blobs(root: &Path) -> Result<Vec<(String, PathBuf)>, String> {
+  let attachments_root = root.join(ATTACHMENTS_DIR);
+  let mut blobs = Vec::new();
+  if !attachments_root.is_dir() {
+    return Ok(blobs);
+  }
+  for shard in fs::read_dir(&attachments_root).map_err(|err| err.to_string())? {
+    let shard = shard.map_err(|err| err.to_string())?.path();
+    if !shard.is_dir() {
+      continue;
+    }
+    for entry in fs::read_dir(&shard).map_err(|err| err.to_string())? {
+      let path = entry.map_err(|err| err.to_string())?.path();
+      if let Some(name) = path.file_name().and_then(|value| value.to_str())
+        && is_hash(name)
+      {
+        blobs.push((name.to_string(), path));
+      }
+    }
+  }
+  blobs.sort();
+  Ok(blobs)
+}
+
+fn collect_garbage(root: &Path, state: &AppState, grace: Duration) -> Result<CleanupReport, String> {
+  let counts = reference_counts(&state.projects);
+  let now = SystemTime::now();
+  let mut report = CleanupReport::default();
+  for (hash, path) in stored_blobs(root)? {
+    let metadata = fs::metadata(&path).map_err(|err| err.to_string())?;
+    let age = metadata
+      .modified()
+      .ok()
+      .and_then(|modified| now.duration_since(modified).ok())
+      .unwrap_or_default();
+    if counts.contains_key(hash.as_str()) || age < grace {
+      report.kept_blobs += 1;
+      continue;
+    }
+    fs::remove_file(&path).map_err(|err| err.to_string())?;
+    let _ = fs::remove_file(thumbnail_path(root, &hash));
+    if let Some(shard) = path.parent() {
+      let _ = fs::remove_dir(shard);
+    }
+    report.removed_blobs += 1;
+    report.freed_bytes += metadata.len();
+  }
+  Ok(report)
+}
+
+pub(crate) fn collect_on_startup(app: &tauri::AppHandle) -> Result<CleanupReport, String> {
+  let Some(state) = read_state_file(app)? else {
+    return Ok(CleanupReport::default());
+  };
+  collect_garbage(&data_dir(app)?, &state, ORPHAN_GRACE)
+}
+
+pub(crate) fn embed_files(root: &Path, content: &str) -> Result<String, String> {
+  let mut payload: Value = serde_json::from_str(content).map_err(|err| err.to_string())?;
+  let source = payload.get("project").unwrap_or(&payload).clone();
+  let project: ProjectState = serde_json::from_value(source).map_err(|err| err.to_string())?;
+  let mut files = Map::new();
+  for hash in reference_counts([&project]).into_keys() {
+    match fs::read(blob_path(root, hash)?) {
+      Ok(bytes) => {
+        files.insert(hash.to_string(), Value::String(BASE64.encode(bytes)));
+      }
+      Err(err) => log::warn!("attachment {hash} was not exported: {err}"),
+    }
+  }
+  if files.is_empty() {
+    return Ok(content.to_string());
+  }
+  let Value::Object(map) = &mut payload else {
+    return Err("project payload is not an object".to_string());
+  };
+  map.insert("attachmentFiles".to_string(), Value::Object(files));
+  serde_json::to_string_pretty(&payload).map_err(|err| err.to_string())
+}
+
+fn store_files(root: &Path, files: &BTreeMap<String, String>) -> Result<usize, String> {
+  let mut stored = 0;
+  for (hash, data) in files {
+    let bytes = BASE64.decode(data.trim()).map_err(|_| format!("attachment {hash} is damaged"))?;
+    if store_blob(root, &bytes)? != *hash {
+      return Err(format!("attachment {hash} is damaged"));
+    }
+    stored += 1;
+  }
+  Ok(stored)
+}
+
+fn named_copy(root: &Path, hash: &str, name: &str) -> Result<PathBuf, String> {
+  let source = blob_path(root, hash)?;
+  if !source.exists() {
+    return Err("attachment file is missing".to_string());
+  }
+  let dir = std::env::temp_dir().join(OPEN_DIR).join(hash);
+  fs::create_dir_all(&dir).map_err(|err| err.to_string())?;
+  let target = dir.join(clean_name(name));
+  if !target.exists() {
+    fs::copy(&source, &target).map_err(|err| err.to_string())?;
+  }
+  Ok(target)
+}
+
+#[tauri::command]
+pub(crate) fn add_attachment(app: tauri::AppHandle, name: String, data: String) -> Result<AttachmentState, String> {
+  let bytes = BASE64.decode(data.trim()).map_err(|_| "attachment data is not valid base64".to_string())?;
+  add(&data_dir(&app)?, &name, &bytes)
+}
+
+#[tauri::command]
+pub(crate) fn attachment_thumbnail(app: tauri::AppHandle, hash: String) -> Result<Option<String>, String> {
+  let thumbnail = ensure_thumbnail(&data_dir(&app)?, &hash)?;
+  Ok(thumbnail.map(|png| format!("data:image/png;base64,{}", BASE64.encode(png))))
+}
+
+#[tauri::command]
+pub(crate) fn open_attachment(app: tauri::AppHandle, hash: String, name: String) -> Result<Option<String>, String> {
+  if !opens_safely(&name) {
+    let path = export_attachment(app.clone(), hash, name)?;
+    app.opener().reveal_item_in_dir(&path).map_err(|err| err.to_string())?;
+    return Ok(Some(path));
+  }
+  let path = named_copy(&data_dir(&app)?, &hash, &name)?;
+  app
+    .opener()
+    .open_path(path.to_string_lossy(), None::<&str>)
+    .map_err(|err| err.to_string())?;
+  Ok(None)
+}
+
+#[tauri::command]
+pub(crate) fn export_attachment(app: tauri::AppHandle, hash: String, name: String) -> Result<String, String> {
+  let bytes = fs::read(blob_path(&data_dir(&app)?, &hash)?).map_err(|_| "attachment file is missing".to_string())?;
+  write_export_file(&app, &clean_name(&name), "", &bytes)
+}
+
+#[tauri::command]
+pub(crate) fn import_attachment_files(app: tauri::AppHandle, files: BTreeMap<String, String>) -> Result<usize, String> {
+  store_files(&data_dir(&app)?, &files)
+}
+
+#[tauri::command]
+pub(crate) fn clean_attachments(app: tauri::AppHandle, state: AppState) -> Result<CleanupReport, String> {
+  collect_garbage(&data_dir(&app)?, &state, ORPHAN_GRACE)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test_support::TempDir;
+
+  fn encoded(width: u32, height: u32, format: image::ImageFormat) -> Vec<u8> {
+    let mut out = Cursor::new(Vec::new());
+    image::DynamicImage::new_rgb8(width, height).write_to(&mut out, format).unwrap();
+    out.into_inner()
+  }
+
+  fn state_with(attachments: &[&AttachmentState]) -> AppState {
+    serde_json::from_value(serde_json::json!({"projects":[{"id":"p","name":"P","notes":[{"id":"n","title":"N","attachments":attachments}]}]}))
+      .unwrap()
+  }
+
+  #[test]
+  fn deduplicates_blobs_and_renders_thumbnails() {
+    let dir = TempDir::new("attachments", "add");
+    let image = encoded(800, 400, image::ImageFormat::Png);
+    let first = add(&dir.0, "../shot.PNG", &image).unwrap();
+    let second = add(&dir.0, "copy.png", &image).unwrap();
+    assert_eq!(first.hash, second.hash);
+    assert_eq!((first.name.as_str(), first.mime.as_str()), ("shot.PNG", "image/png"));
+    let thumbnail = image::load_from_memory(&fs::read(thumbnail_path(&dir.0, &first.hash)).unwrap()).unwrap();
+    assert_eq!((thumbnail.width(), thumbnail.height()), (256, 128));
+    let photo = add(&dir.0, "photo.jpg", &encoded(300, 600, image::ImageFormat::Jpeg)).unwrap();
+    assert_eq!(photo.mime, "image/jpeg");
+    let thumbnail = image::load_from_memory(&ensure_thumbnail(&dir.0, &photo.hash).unwrap().unwrap()).unwrap();
+    assert_eq!((thumbnail.width(), thumbnail.height()), (128, 256));
+    let log = add(&dir.0, "app.log", b"hello").unwrap();
+    assert!(ensure_thumbnail(&dir.0, &log.hash).unwrap().is_none());
+    assert_eq!(stored_blobs(&dir.0).unwrap().len(), 3);
+    assert!(add(&dir.0, "", b"x").is_ok_and(|attachment| attachment.name == "attachment"));
+    assert!(blob_path(&dir.0, "../x").is_err());
+    assert!(named_copy(&dir.0, &first.hash, "shot.png").unwrap().ends_with("shot.png"));
+  }
+
+  #[test]
+  fn collects_only_old_unreferenced_blobs() {
+    let dir = TempDir::new("attachments", "gc");
+    let kept = add(&dir.0, "a.txt", b"kept").unwrap();
+    let orphan = add(&dir.0, "b.txt", b"orphan").unwrap();
+    let state = state_with(&[&kept, &kept]);
+    assert_eq!(reference_counts(&state.projects).get(kept.hash.as_str()), Some(&2));
+    let fresh = collect_garbage(&dir.0, &state, ORPHAN_GRACE).unwrap();
+    assert_eq!((fresh.kept_blobs, fresh.removed_blobs), (2, 0));
+    let report = collect_garbage(&dir.0, &state, Duration::ZERO).unwrap();
+    assert_eq!((report.kept_blobs, report.removed_blobs, report.freed_bytes), (1, 1, 6));
+    assert!(!blob_path(&dir.0, &orphan.hash).unwrap().parent().unwrap().exists());
+  }
+
+  #[test]
+  fn embeds_and_restores_attachment_files() {
+    let dir = TempDir::new("attachments", "embed");
+    let image = encoded(4, 4, image::ImageFormat::Png);
+    let attachment = add(&dir.0, "a.png", &image).unwrap();
+    let state = state_with(&[&attachment]);
+    let payload = serde_json::json!({"appVersion":"0.3.1","project": state.projects[0]}).to_string();
+    let embedded: Value = serde_json::from_str(&embed_files(&dir.0, &payload).unwrap()).unwrap();
+    let files: BTreeMap<String, String> = serde_json::from_value(embedded["attachmentFiles"].clone()).unwrap();
+    assert_eq!(files.len(), 1);
+
+    fs::remove_dir_all(dir.0.join(ATTACHMENTS_DIR)).unwrap();
+    assert_eq!(store_files(&dir.0, &files).unwrap(), 1);
+    assert_eq!(fs::read(blob_path(&dir.0, &attachment.hash).unwrap()).unwrap(), image);
+
+    let mut damaged = files.clone();
+    damaged.insert("0".repeat(64), BASE64.encode(b"x"));
+    assert!(store_files(&dir.0, &damaged).is_err());
+    assert!(store_files(&dir.0, &BTreeMap::from([(attachment.hash.clone(), "%%%".to_string())])).is_err());
+    assert_eq!(embed_files(&dir.0, r#"{"name":"x"}"#).unwrap(), r#"{"name":"x"}"#);
+  }
+
+  #[test]
+  fn opens_only_viewable_types_directly() {
+    for name in ["photo.JPG", "scan.pdf", "notes.txt", "data.csv", "readme.md"] {
+      assert!(opens_safely(name), "{name}");
+    }
+    for name in ["setup.exe", "script.sh", "page.html", "drawing.svg", "archive.zip", "config.json", "noextension"] {
+      assert!(!opens_safely(name), "{name}");
+    }
+  }
+}
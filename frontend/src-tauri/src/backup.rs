@@ -0,0 +1,360 @@
+use crate::templates::{self, LIBRARY_FILE};
+use crate::time_tracking::{TIMER_FILE, TimeTracker};
+use crate::{AppState, data_dir, revisions, write_export_file, write_state_file};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::io::{Cursor, Read, Write};
+use std::path::{Path, PathBuf};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+const BACKUP_FORMAT: &str = "project-notes-studio-backup";
+const BACKUP_FORMAT_VERSION: u32 = 1;
+const BACKUP_EXTENSION: &str = ".pns-backup";
+const MANIFEST_FILE: &str = "manifest.json";
+const STATE_FILE: &str = "app_state.json";
+pub(crate) const ATTACHMENTS_DIR: &str = "attachments";
+const DATA_PATHS: [&str; 4] = [ATTACHMENTS_DIR, revisions::REVISIONS_DIR, TIMER_FILE, LIBRARY_FILE];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BackupEntry {
+  path: String,
+  size: u64,
+  sha256: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BackupManifest {
+  format: String,
+  format_version: u32,
+  app_version: String,
+  created_at: String,
+  project_count: usize,
+  files: Vec<BackupEntry>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum RestoreMode {
+  Replace,
+  Merge,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RestoreResult {
+  state: AppState,
+  app_version: String,
+  created_at: String,
+  added_projects: usize,
+  skipped_projects: usize,
+  restored_files: usize,
+}
+
+struct BackupContents {
+  manifest: BackupManifest,
+  state: AppState,
+  files: Vec<(PathBuf, Vec<u8>)>,
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+  hex::encode(Sha256::digest(bytes))
+}
+
+fn collect_files(root: &Path, dir: &Path, out: &mut Vec<(String, PathBuf)>) -> Result<(), String> {
+  let mut entries = fs::read_dir(dir)
+    .map_err(|err| err.to_string())?
+    .collect::<Result<Vec<_>, _>>()
+    .map_err(|err| err.to_string())?;
+  entries.sort_by_key(|entry| entry.file_name());
+  for entry in entries {
+    let path = entry.path();
+    if path.is_dir() {
+      collect_files(root, &path, out)?;
+    } else if let Ok(relative) = path.strip_prefix(root) {
+      let name = relative
+        .components()
+        .map(|part| part.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/");
+      out.push((name, path));
+    }
+  }
+  Ok(())
+}
+
+fn build_backup(state: &AppState, data_root: &Path) -> Result<Vec<u8>, String> {
+  let mut files = vec![(
+    STATE_FILE.to_string(),
+    serde_json::to_vec_pretty(state).map_err(|err| err.to_string())?,
+  )];
+
+  let mut paths = Vec::new();
+  for entry in DATA_PATHS {
+    let path = data_root.join(entry);
+    if path.is_dir() {
+      collect_files(data_root, &path, &mut paths)?;
+    } else if path.is_file() {
+      paths.push((entry.to_string(), path));
+    }
+  }
+  for (name, path) in paths {
+    files.push((name, fs::read(path).map_err(|err| err.to_string())?));
+  }
+
+  let manifest = BackupManifest {
+    format: BACKUP_FORMAT.to_string(),
+    format_version: BACKUP_FORMAT_VERSION,
+    app_version: env!("CARGO_PKG_VERSION").to_string(),
+    created_at: chrono::Utc::now().to_rfc3339(),
+    project_count: state.projects.len(),
+    files: files
+      .iter()
+      .map(|(path, bytes)| BackupEntry { path: path.clone(), size: bytes.len() as u64, sha256: sha256_hex(bytes) })
+      .collect(),
+  };
+
+  let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+  let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+  zip.start_file(MANIFEST_FILE, options).map_err(|err| err.to_string())?;
+  zip
+    .write_all(&serde_json::to_vec_pretty(&manifest).map_err(|err| err.to_string())?)
+    .map_err(|err| err.to_string())?;
+  for (path, bytes) in &files {
+    zip.start_file(path.as_str(), options).map_err(|err| err.to_string())?;
+    zip.write_all(bytes).map_err(|err| err.to_string())?;
+  }
+  Ok(zip.finish().map_err(|err| err.to_string())?.into_inner())
+}
+
+fn read_entry(archive: &mut ZipArchive<Cursor<Vec<u8>>>, name: &str) -> Result<Vec<u8>, String> {
+  let mut file = archive.by_name(name).map_err(|_| format!("backup is missing {name}"))?;
+  let mut bytes = Vec::new();
+  file.read_to_end(&mut bytes).map_err(|err| err.to_string())?;
+  Ok(bytes)
+}
+
+fn read_backup(bytes: Vec<u8>) -> Result<BackupContents, String> {
+  let mut archive = ZipArchive::new(Cursor::new(bytes)).map_err(|_| "not a backup archive".to_string())?;
+  let manifest: BackupManifest =
+    serde_json::from_slice(&read_entry(&mut archive, MANIFEST_FILE)?).map_err(|err| err.to_string())?;
+  if manifest.format != BACKUP_FORMAT {
+    return Err("not a backup archive".to_string());
+  }
+  if manifest.format_version > BACKUP_FORMAT_VERSION {
+    return Err(format!("backup was created by a newer app version ({})", manifest.app_version));
+  }
+
+  let mut state = None;
+  let mut files = Vec::new();
+  for entry in &manifest.files {
+    let bytes = read_entry(&mut archive, &entry.path)?;
+    if bytes.len() as u64 != entry.size || sha256_hex(&bytes) != entry.sha256 {
+      return Err(format!("checksum mismatch for {}", entry.path));
+    }
+    if entry.path == STATE_FILE {
+      state = Some(serde_json::from_slice::<AppState>(&bytes).map_err(|err| err.to_string())?);
+      continue;
+    }
+    let path = Path::new(&entry.path);
+    let safe = DATA_PATHS.iter().any(|data_path| path.starts_with(data_path))
+      && path.components().all(|part| matches!(part, std::path::Component::Normal(_)));
+    if !safe {
+      return Err(format!("unexpected file in backup: {}", entry.path));
+    }
+    files.push((path.to_path_buf(), bytes));
+  }
+
+  let state = state.ok_or_else(|| format!("backup is missing {STATE_FILE}"))?;
+  Ok(BackupContents { manifest, state, files })
+}
+
+fn merge_states(mut current: AppState, incoming: AppState) -> (AppState, usize, usize) {
+  let mut added = 0;
+  let mut skipped = 0;
+  for project in incoming.projects {
+    let exists = project.id.is_some() && current.projects.iter().any(|existing| existing.id == project.id);
+    if exists {
+      skipped += 1;
+    } else {
+      current.projects.push(project);
+      added += 1;
+    }
+  }
+  for status in incoming.settings.project_statuses {
+    if !current.settings.project_statuses.contains(&status) {
+      current.settings.project_statuses.push(status);
+    }
+  }
+  (current, added, skipped)
+}
+
+fn restore_files(root: &Path, files: &[(PathBuf, Vec<u8>)], mode: RestoreMode) -> Result<usize, String> {
+  if matches!(mode, RestoreMode::Replace) {
+    for entry in DATA_PATHS {
+      let path = root.join(entry);
+      if path.is_dir() {
+        fs::remove_dir_all(&path).map_err(|err| err.to_string())?;
+      } else if path.exists() {
+        fs::remove_file(&path).map_err(|err| err.to_string())?;
+      }
+    }
+  }
+  let mut restored = 0;
+  for (relative, bytes) in files {
+    let target = root.join(relative);
+    if matches!(mode, RestoreMode::Merge) && target.exists() {
+      if relative == Path::new(LIBRARY_FILE) {
+        templates::merge_library_file(root, &String::from_utf8_lossy(bytes))?;
+        restored += 1;
+      }
+      continue;
+    }
+    if let Some(parent) = target.parent() {
+      fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+    }
+    fs::write(&target, bytes).map_err(|err| err.to_string())?;
+    restored += 1;
+  }
+  Ok(restored)
+}
+
+#[tauri::command]
+pub(crate) fn create_backup(app: tauri::AppHandle, state: AppState) -> Result<String, String> {
+  let bytes = build_backup(&state, &data_dir(&app)?)?;
+  let name = format!("project-notes-studio-{}", chrono::Local::now().format("%Y%m%d-%H%M%S"));
+  write_export_file(&app, &name, BACKUP_EXTENSION, &bytes)
+}
+
+#[tauri::command]
+pub(crate) fn restore_backup(
+  app: tauri::AppHandle,
+  data: String,
+  mode: RestoreMode,
+  current: AppState,
+) -> Result<RestoreResult, String> {
+  let bytes = BASE64.decode(data.trim()).map_err(|_| "backup data is not valid base64".to_string())?;
+  let contents = read_backup(bytes)?;
+  let incoming_count = contents.state.projects.len();
+  let (state, added_projects, skipped_projects) = match mode {
+    RestoreMode::Replace => (contents.state, incoming_count, 0),
+    RestoreMode::Merge => merge_states(current, contents.state),
+  };
+
+  let restored_files = restore_files(&data_dir(&app)?, &contents.files, mode)?;
+  revisions::forget_sessions(&app)?;
+  TimeTracker::reload(&app)?;
+  write_state_file(&app, &state)?;
+  Ok(RestoreResult {
+    state,
+    app_version: contents.manifest.app_version,
+    created_at: contents.manifest.created_at,
+    added_projects,
+    skipped_projects,
+    restored_files,
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test_support::TempDir;
+
+  fn sample() -> AppState {
+    serde_json::from_str(
+      r#"{"projects":[{"id":"p1","name":"Релиз","notes":[{"id":"n1","title":"Заметка","body":"Тело"}]},{"id":2,"name":"Second"}],
+        "settings":{"projectStatuses":["Новый","Готов"]}}"#,
+    )
+    .unwrap()
+  }
+
+  fn rewrite_entry(bytes: Vec<u8>, name: &str, content: &[u8]) -> Vec<u8> {
+    let mut archive = ZipArchive::new(Cursor::new(bytes)).unwrap();
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    for index in 0..archive.len() {
+      let mut file = archive.by_index(index).unwrap();
+      let mut data = Vec::new();
+      file.read_to_end(&mut data).unwrap();
+      zip.start_file(file.name(), SimpleFileOptions::default()).unwrap();
+      zip.write_all(if file.name() == name { content } else { &data }).unwrap();
+    }
+    zip.finish().unwrap().into_inner()
+  }
+
+  #[test]
+  fn round_trips_state_and_data_files() {
+    let source = TempDir::new("backup", "source");
+    source.write("attachments/ab/abcdef", "blob");
+    source.write("revisions/0123.json", "{}");
+    source.write("timer.json", "{}");
+    source.write(
+      "templates.json",
+      r#"{"format":"project-notes-studio-templates","version":1,"templates":[{"id":"t1","name":"Релиз","project":{"name":"{{client}}"}}]}"#,
+    );
+    source.write("unrelated.txt", "skip me");
+    let contents = read_backup(build_backup(&sample(), &source.0).unwrap()).unwrap();
+    assert_eq!(contents.manifest.project_count, 2);
+    assert_eq!(serde_json::to_value(&contents.state).unwrap(), serde_json::to_value(sample()).unwrap());
+    let mut names: Vec<_> = contents.files.iter().map(|(path, _)| path.to_string_lossy().replace('\\', "/")).collect();
+    names.sort();
+    assert_eq!(names, ["attachments/ab/abcdef", "revisions/0123.json", "templates.json", "timer.json"]);
+
+    let target = TempDir::new("backup", "target");
+    target.write("attachments/zz/stale", "old");
+    target.write("revisions/0123.json", "local");
+    target.write("timer.json", "running");
+    target.write(
+      "templates.json",
+      r#"{"format":"project-notes-studio-templates","version":1,"templates":[{"id":"t2","name":"Local","project":{"name":"Mine"}}]}"#,
+    );
+    assert_eq!(restore_files(&target.0, &contents.files, RestoreMode::Merge).unwrap(), 2);
+    let library = target.read("templates.json").unwrap();
+    assert!(library.contains("Local") && library.contains("Релиз"));
+    assert_eq!(target.read("timer.json").as_deref(), Some("running"));
+    assert_eq!(target.read("revisions/0123.json").as_deref(), Some("local"));
+    assert_eq!(target.read("attachments/zz/stale").as_deref(), Some("old"));
+    assert_eq!(restore_files(&target.0, &contents.files, RestoreMode::Replace).unwrap(), 4);
+    assert!(!target.read("templates.json").unwrap().contains("Local"));
+    assert_eq!(target.read("timer.json").as_deref(), Some("{}"));
+    assert_eq!(target.read("revisions/0123.json").as_deref(), Some("{}"));
+    assert_eq!(target.read("attachments/zz/stale"), None);
+    assert_eq!(target.read("attachments/ab/abcdef").as_deref(), Some("blob"));
+  }
+
+  #[test]
+  fn rejects_tampered_and_foreign_archives() {
+    let dir = TempDir::new("backup", "tamper");
+    dir.write("attachments/ab/abcdef", "blob");
+    let bytes = build_backup(&sample(), &dir.0).unwrap();
+    let tampered = rewrite_entry(bytes.clone(), "attachments/ab/abcdef", b"evil");
+    assert!(read_backup(tampered).is_err_and(|err| err.contains("checksum mismatch")));
+
+    let contents = read_backup(bytes.clone()).unwrap();
+    let mut manifest = contents.manifest.clone();
+    manifest.files.push(BackupEntry { path: "../escape".into(), size: 0, sha256: sha256_hex(b"") });
+    let escaped = rewrite_entry(bytes.clone(), MANIFEST_FILE, &serde_json::to_vec(&manifest).unwrap());
+    assert!(read_backup(escaped).is_err());
+
+    let mut newer = contents.manifest;
+    newer.format_version = BACKUP_FORMAT_VERSION + 1;
+    let newer = rewrite_entry(bytes, MANIFEST_FILE, &serde_json::to_vec(&newer).unwrap());
+    assert!(read_backup(newer).is_err_and(|err| err.contains("newer")));
+    assert!(read_backup(b"not a zip".to_vec()).is_err());
+    assert!(read_backup(Vec::new()).is_err());
+  }
+
+  #[test]
+  fn merge_keeps_existing_projects() {
+    let mut incoming = sample();
+    incoming.projects.push(serde_json::from_str(r#"{"id":"p3","name":"Third"}"#).unwrap());
+    incoming.settings.project_statuses.push("Архив".into());
+    let (merged, added, skipped) = merge_states(sample(), incoming);
+    assert_eq!((merged.projects.len(), added, skipped), (3, 1, 2));
+    assert_eq!(merged.settings.project_statuses, ["Новый", "Готов", "Архив"]);
+  }
+}
@@ -0,0 +1,257 @@
+use crate::labels::{DocLabels, doc_labels};
+use crate::markup::{escape, paragraphs};
+use crate::{AppState, EntityId, ProjectState, file_stem, step_tree, write_export_file};
+use std::fmt::Write as _;
+use std::io::{Cursor, Write};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+const CONTENT_TYPES_XML: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types">
+  <Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/>
+  <Default Extension="xml" ContentType="application/xml"/>
+  <Override PartName="/word/document.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.document.main+xml"/>
+  <Override PartName="/word/styles.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.styles+xml"/>
+  <Override PartName="/docProps/core.xml" ContentType="application/vnd.openxmlformats-package.core-properties+xml"/>
+</Types>
+"#;
+
+const PACKAGE_RELS_XML: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+  <Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="word/document.xml"/>
+  <Relationship Id="rId2" Type="http://schemas.openxmlformats.org/package/2006/relationships/metadata/core-properties" Target="docProps/core.xml"/>
+</Relationships>
+"#;
+
+const DOCUMENT_RELS_XML: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+  <Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/styles" Target="styles.xml"/>
+</Relationships>
+"#;
+
+const STYLES_XML: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:styles xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">
+  <w:docDefaults>
+    <w:rPrDefault><w:rPr><w:rFonts w:ascii="Calibri" w:hAnsi="Calibri" w:cs="Calibri" w:eastAsia="Calibri"/><w:sz w:val="22"/><w:lang w:val="ru-RU"/></w:rPr></w:rPrDefault>
+    <w:pPrDefault><w:pPr><w:spacing w:after="120" w:line="276" w:lineRule="auto"/></w:pPr></w:pPrDefault>
+  </w:docDefaults>
+  <w:style w:type="paragraph" w:default="1" w:styleId="Normal"><w:name w:val="Normal"/><w:qFormat/></w:style>
+  <w:style w:type="paragraph" w:styleId="Title"><w:name w:val="Title"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:qFormat/><w:pPr><w:spacing w:after="240"/></w:pPr><w:rPr><w:b/><w:sz w:val="48"/></w:rPr></w:style>
+  <w:style w:type="paragraph" w:styleId="Heading1"><w:name w:val="heading 1"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:qFormat/><w:pPr><w:keepNext/><w:spacing w:before="360" w:after="120"/><w:outlineLvl w:val="0"/></w:pPr><w:rPr><w:b/><w:color w:val="1F3A5F"/><w:sz w:val="36"/></w:rPr></w:style>
+  <w:style w:type="paragraph" w:styleId="Heading2"><w:name w:val="heading 2"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:qFormat/><w:pPr><w:keepNext/><w:spacing w:before="240" w:after="80"/><w:outlineLvl w:val="1"/></w:pPr><w:rPr><w:b/><w:sz w:val="28"/></w:rPr></w:style>
+  <w:style w:type="paragraph" w:styleId="Heading3"><w:name w:val="heading 3"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:qFormat/><w:pPr><w:keepNext/><w:spacing w:before="200" w:after="60"/><w:outlineLvl w:val="2"/></w:pPr><w:rPr><w:b/><w:sz w:val="24"/></w:rPr></w:style>
+  <w:style w:type="paragraph" w:styleId="Meta"><w:name w:val="Meta"/><w:basedOn w:val="Normal"/><w:pPr><w:spacing w:after="40"/></w:pPr><w:rPr><w:color w:val="555555"/><w:sz w:val="20"/></w:rPr></w:style>
+  <w:style w:type="paragraph" w:styleId="Checkbox"><w:name w:val="Checkbox"/><w:basedOn w:val="Normal"/><w:pPr><w:spacing w:after="60"/><w:ind w:left="360" w:hanging="360"/></w:pPr></w:style>
+</w:styles>
+"#;
+
+struct DocumentBody {
+  xml: String,
+}
+
+impl DocumentBody {
+  fn new() -> Self {
+    Self { xml: String::new() }
+  }
+
+  fn run(text: &str, extra: &str) -> String {
+    format!("<w:r>{extra}<w:t xml:space=\"preserve\">{}</w:t></w:r>", escape(text))
+  }
+
+  fn styled(&mut self, style: &str, text: &str) {
+    let _ = writeln!(self.xml, "<w:p><w:pPr><w:pStyle w:val=\"{style}\"/></w:pPr>{}</w:p>", Self::run(text, ""));
+  }
+
+  fn text_block(&mut self, text: &str) {
+    for paragraph in paragraphs(text) {
+      self.xml.push_str("<w:p>");
+      for (index, line) in paragraph.iter().enumerate() {
+        let extra = if index > 0 { "<w:br/>" } else { "" };
+        self.xml.push_str(&Self::run(line, extra));
+      }
+      self.xml.push_str("</w:p>\n");
+    }
+  }
+
+  fn checkbox(&mut self, text: &str, done: bool, depth: usize) {
+    let indent = if depth == 0 { String::new() } else { format!("<w:ind w:left=\"{}\" w:hanging=\"360\"/>", 360 * (depth + 1)) };
+    let mark = if done { "☑" } else { "☐" };
+    let strike = if done { "<w:rPr><w:strike/><w:color w:val=\"666666\"/></w:rPr>" } else { "" };
+    let _ = writeln!(
+      self.xml,
+      "<w:p><w:pPr><w:pStyle w:val=\"Checkbox\"/>{indent}</w:pPr>{}<w:r><w:tab/></w:r>{}</w:p>",
+      Self::run(mark, "<w:rPr><w:rFonts w:ascii=\"Segoe UI Symbol\" w:hAnsi=\"Segoe UI Symbol\"/></w:rPr>"),
+      Self::run(text, strike)
+    );
+  }
+
+  fn page_break(&mut self) {
+    self.xml.push_str("<w:p><w:r><w:br w:type=\"page\"/></w:r></w:p>\n");
+  }
+}
+
+fn write_project(body: &mut DocumentBody, project: &ProjectState, labels: &DocLabels, heading: &str) {
+  body.styled(heading, project.name.trim());
+  let status = if project.status.trim().is_empty() { labels.status_not_set } else { project.status.trim() };
+  body.styled("Meta", &format!("{}: {status}", labels.project_status));
+  if project.pinned {
+    body.styled("Meta", labels.pinned);
+  }
+  let (done, total) = project.step_progress();
+  body.styled("Meta", &format!("{}: {}", labels.progress, labels.progress_caption(done, total)));
+  body.text_block(&project.description);
+
+  let sub_heading = if heading == "Title" { "Heading1" } else { "Heading2" };
+  body.styled(sub_heading, labels.project_steps);
+  if project.steps.is_empty() {
+    body.styled("Meta", labels.no_steps);
+  }
+  for (depth, step) in step_tree::flatten(&project.steps) {
+    body.checkbox(step.text.trim(), step.done, depth);
+  }
+
+  body.styled(sub_heading, labels.project_notes);
+  if project.notes.is_empty() {
+    body.styled("Meta", labels.no_notes);
+  }
+  let note_heading = if heading == "Title" { "Heading2" } else { "Heading3" };
+  for note in &project.notes {
+    body.styled(note_heading, note.title.trim());
+    body.text_block(&note.body);
+  }
+}
+
+fn core_properties(title: &str, language: &str) -> String {
+  let now = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ");
+  format!(
+    "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n<cp:coreProperties xmlns:cp=\"http://schemas.openxmlformats.org/package/2006/metadata/core-properties\" xmlns:dc=\"http://purl.org/dc/elements/1.1/\" xmlns:dcterms=\"http://purl.org/dc/terms/\" xmlns:xsi=\"http://www.w3.org/2001/XMLSchema-instance\">\n<dc:title>{}</dc:title>\n<dc:creator>{}</dc:creator>\n<dc:language>{language}</dc:language>\n<dcterms:created xsi:type=\"dcterms:W3CDTF\">{now}</dcterms:created>\n<dcterms:modified xsi:type=\"dcterms:W3CDTF\">{now}</dcterms:modified>\n</cp:coreProperties>\n",
+    escape(title),
+    escape(doc_labels(language).app_title)
+  )
+}
+
+pub(crate) fn render_docx(projects: &[&ProjectState], title: &str, workspace: bool, language: &str) -> Result<Vec<u8>, String> {
+  let labels = doc_labels(language);
+  let mut body = DocumentBody::new();
+  if workspace {
+    body.styled("Title", title);
+    body.styled("Meta", &format!("{}: {}", labels.generated, chrono::Local::now().format("%Y-%m-%d %H:%M")));
+    for (index, project) in projects.iter().enumerate() {
+      if index > 0 {
+        body.page_break();
+      }
+      write_project(&mut body, project, labels, "Heading1");
+    }
+  } else {
+    for project in projects {
+      write_project(&mut body, project, labels, "Title");
+    }
+  }
+
+  let document = format!(
+    "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n<w:document xmlns:w=\"http://schemas.openxmlformats.org/wordprocessingml/2006/main\">\n<w:body>\n{}<w:sectPr><w:pgSz w:w=\"11906\" w:h=\"16838\"/><w:pgMar w:top=\"1134\" w:right=\"1134\" w:bottom=\"1134\" w:left=\"1134\" w:header=\"709\" w:footer=\"709\" w:gutter=\"0\"/></w:sectPr>\n</w:body>\n</w:document>\n",
+    body.xml
+  );
+
+  let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+  let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+  let entries = [
+    ("[Content_Types].xml", CONTENT_TYPES_XML.to_string()),
+    ("_rels/.rels", PACKAGE_RELS_XML.to_string()),
+    ("word/document.xml", document),
+    ("word/styles.xml", STYLES_XML.to_string()),
+    ("word/_rels/document.xml.rels", DOCUMENT_RELS_XML.to_string()),
+    ("docProps/core.xml", core_properties(title, language)),
+  ];
+  for (name, content) in entries {
+    zip.start_file(name, options).map_err(|err| err.to_string())?;
+    zip.write_all(content.as_bytes()).map_err(|err| err.to_string())?;
+  }
+  Ok(zip.finish().map_err(|err| err.to_string())?.into_inner())
+}
+
+#[tauri::command]
+pub(crate) fn export_docx(app: tauri::AppHandle, state: AppState, project_id: Option<EntityId>) -> Result<String, String> {
+  let projects = state.select_projects(project_id.as_ref())?;
+  let workspace = project_id.is_none();
+  let (title, stem) = match projects.first() {
+    Some(project) if !workspace => (project.name.trim().to_string(), file_stem(&project.name, "project")),
+    _ => (doc_labels(&state.settings.language).workspace.to_string(), "workspace".to_string()),
+  };
+  let bytes = render_docx(&projects, &title, workspace, &state.settings.language)?;
+  write_export_file(&app, &stem, ".docx", &bytes)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::collections::BTreeMap;
+  use std::io::Read;
+  use zip::ZipArchive;
+
+  fn sample() -> AppState {
+    serde_json::from_str(
+      r#"{"projects":[{"id":1,"name":"Проект <А>","description":"Описание\nстрока\n\nвторой","pinned":true,
+        "notes":[{"id":2,"title":"Заметка & Ё","body":"Текст\u000b & ещё"}],
+        "steps":[{"id":4,"text":"Шаг 1","done":true,"children":[{"text":"Шаг 1.1"}]},{"text":"Шаг 2"}]},
+        {"name":"Empty"}]}"#,
+    )
+    .unwrap()
+  }
+
+  fn entries(bytes: Vec<u8>) -> BTreeMap<String, String> {
+    let mut archive = ZipArchive::new(Cursor::new(bytes)).unwrap();
+    (0..archive.len())
+      .map(|index| {
+        let mut file = archive.by_index(index).unwrap();
+        let mut content = String::new();
+        file.read_to_string(&mut content).unwrap();
+        (file.name().to_string(), content)
+      })
+      .collect()
+  }
+
+  fn paragraph_texts(xml: &str) -> Vec<String> {
+    let document = roxmltree::Document::parse(xml).unwrap();
+    document
+      .descendants()
+      .filter(|node| node.has_tag_name("p"))
+      .map(|paragraph| paragraph.descendants().filter(|node| node.has_tag_name("t")).filter_map(|node| node.text()).collect())
+      .collect()
+  }
+
+  #[test]
+  fn packages_a_well_formed_document() {
+    let state = sample();
+    let entries = entries(render_docx(&state.select_projects(None).unwrap(), "Рабочее & <всё>", true, "ru").unwrap());
+    let names: Vec<&str> = entries.keys().map(String::as_str).collect();
+    assert_eq!(
+      names,
+      ["[Content_Types].xml", "_rels/.rels", "docProps/core.xml", "word/_rels/document.xml.rels", "word/document.xml", "word/styles.xml"]
+    );
+    for (name, content) in &entries {
+      roxmltree::Document::parse(content).unwrap_or_else(|err| panic!("{name} is not well-formed: {err}"));
+    }
+
+    let texts = paragraph_texts(&entries["word/document.xml"]);
+    assert_eq!(texts[0], "Рабочее & <всё>");
+    assert!(texts.contains(&"Проект <А>".to_string()));
+    assert!(texts.contains(&"Описаниестрока".to_string()));
+    assert!(texts.contains(&"☑Шаг 1".to_string()));
+    assert!(texts.contains(&"☐Шаг 1.1".to_string()));
+    assert!(texts.contains(&"Текст & ещё".to_string()));
+    assert!(entries["word/document.xml"].contains("<w:br w:type=\"page\"/>"));
+    assert!(entries["word/document.xml"].contains("<w:ind w:left=\"720\" w:hanging=\"360\"/>"));
+    assert!(entries["docProps/core.xml"].contains("<dc:title>Рабочее &amp; &lt;всё&gt;</dc:title>"));
+  }
+
+  #[test]
+  fn single_project_documents_start_with_the_project_title() {
+    let state = sample();
+    let projects = state.select_projects(Some(&EntityId::Num(1))).unwrap();
+    let entries = entries(render_docx(&projects, "Проект", false, "en").unwrap());
+    let document = &entries["word/document.xml"];
+    assert!(document.starts_with("<?xml") && !document.contains("w:type=\"page\""));
+    assert!(document.contains("<w:pStyle w:val=\"Title\"/></w:pPr><w:r><w:t xml:space=\"preserve\">Проект &lt;А&gt;</w:t>"));
+  }
+}
@@ -0,0 +1,173 @@
+use crate::{attachments, data_dir, write_export_file};
+use argon2::{Algorithm, Argon2, Params, Version};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng, Payload, rand_core::RngCore};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+use serde::{Deserialize, Serialize};
+
+const FORMAT: &str = "project-notes-studio-encrypted";
+const FORMAT_VERSION: u32 = 1;
+const EXTENSION: &str = ".pns-locked";
+const KDF_NAME: &str = "argon2id";
+const CIPHER_NAME: &str = "xchacha20poly1305";
+const KDF_MEMORY_KIB: u32 = 64 * 1024;
+const KDF_ITERATIONS: u32 = 3;
+const KDF_PARALLELISM: u32 = 1;
+const MAX_KDF_MEMORY_KIB: u32 = 1024 * 1024;
+const MAX_KDF_ITERATIONS: u32 = 64;
+const SALT_LEN: usize = 16;
+const KEY_LEN: usize = 32;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct KdfParams {
+  name: String,
+  memory_kib: u32,
+  iterations: u32,
+  parallelism: u32,
+  salt: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Envelope {
+  format: String,
+  version: u32,
+  kdf: KdfParams,
+  cipher: String,
+  nonce: String,
+  payload: String,
+}
+
+fn associated_data(envelope: &Envelope) -> Result<Vec<u8>, String> {
+  serde_json::to_vec(&(&envelope.format, envelope.version, &envelope.kdf, &envelope.cipher)).map_err(|err| err.to_string())
+}
+
+fn derive_key(password: &str, kdf: &KdfParams) -> Result<[u8; KEY_LEN], String> {
+  if kdf.name != KDF_NAME || kdf.memory_kib > MAX_KDF_MEMORY_KIB || kdf.iterations > MAX_KDF_ITERATIONS {
+    return Err("unsupported key derivation settings".to_string());
+  }
+  let salt = BASE64.decode(&kdf.salt).map_err(|_| "damaged encrypted file".to_string())?;
+  let params = Params::new(kdf.memory_kib, kdf.iterations, kdf.parallelism, Some(KEY_LEN)).map_err(|err| err.to_string())?;
+  let mut key = [0u8; KEY_LEN];
+  Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+    .hash_password_into(password.as_bytes(), &salt, &mut key)
+    .map_err(|err| err.to_string())?;
+  Ok(key)
+}
+
+fn encrypt(content: &str, password: &str) -> Result<String, String> {
+  if password.is_empty() {
+    return Err("password is required".to_string());
+  }
+  let mut salt = [0u8; SALT_LEN];
+  OsRng.fill_bytes(&mut salt);
+  let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+  let mut envelope = Envelope {
+    format: FORMAT.to_string(),
+    version: FORMAT_VERSION,
+    kdf: KdfParams {
+      name: KDF_NAME.to_string(),
+      memory_kib: KDF_MEMORY_KIB,
+      iterations: KDF_ITERATIONS,
+      parallelism: KDF_PARALLELISM,
+      salt: BASE64.encode(salt),
+    },
+    cipher: CIPHER_NAME.to_string(),
+    nonce: BASE64.encode(nonce),
+    payload: String::new(),
+  };
+
+  let key = derive_key(password, &envelope.kdf)?;
+  let aad = associated_data(&envelope)?;
+  let ciphertext = XChaCha20Poly1305::new(&key.into())
+    .encrypt(&nonce, Payload { msg: content.as_bytes(), aad: &aad })
+    .map_err(|_| "encryption failed".to_string())?;
+  envelope.payload = BASE64.encode(ciphertext);
+  serde_json::to_string_pretty(&envelope).map_err(|err| err.to_string())
+}
+
+fn decrypt(content: &str, password: &str) -> Result<String, String> {
+  let envelope: Envelope = serde_json::from_str(content.trim_start_matches('\u{feff}'))
+    .map_err(|_| "not an encrypted project file".to_string())?;
+  if envelope.format != FORMAT {
+    return Err("not an encrypted project file".to_string());
+  }
+  if envelope.version > FORMAT_VERSION || envelope.cipher != CIPHER_NAME {
+    return Err("unsupported encrypted file version".to_string());
+  }
+
+  let nonce = BASE64.decode(&envelope.nonce).map_err(|_| "damaged encrypted file".to_string())?;
+  if nonce.len() != 24 {
+    return Err("damaged encrypted file".to_string());
+  }
+  let ciphertext = BASE64.decode(&envelope.payload).map_err(|_| "damaged encrypted file".to_string())?;
+  let key = derive_key(password, &envelope.kdf)?;
+  let aad = associated_data(&envelope)?;
+  let plaintext = XChaCha20Poly1305::new(&key.into())
+    .decrypt(XNonce::from_slice(&nonce), Payload { msg: &ciphertext, aad: &aad })
+    .map_err(|_| "wrong password or damaged file".to_string())?;
+  String::from_utf8(plaintext).map_err(|_| "damaged encrypted file".to_string())
+}
+
+async fn run_blocking<T: Send + 'static>(task: impl FnOnce() -> Result<T, String> + Send + 'static) -> Result<T, String> {
+  tauri::async_runtime::spawn_blocking(task).await.map_err(|err| err.to_string())?
+}
+
+#[tauri::command]
+pub(crate) async fn export_encrypted_project(
+  app: tauri::AppHandle,
+  filename: String,
+  content: String,
+  password: String,
+) -> Result<String, String> {
+  let content = attachments::embed_files(&data_dir(&app)?, &content)?;
+  let sealed = run_blocking(move || encrypt(&content, &password)).await?;
+  let stem = filename.trim_end_matches(".pns-project.json").trim_end_matches(EXTENSION);
+  write_export_file(&app, stem, EXTENSION, sealed.as_bytes())
+}
+
+#[tauri::command]
+pub(crate) async fn decrypt_project_file(content: String, password: String) -> Result<String, String> {
+  run_blocking(move || decrypt(&content, &password)).await
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const CONTENT: &str = r#"{"project":{"name":"секрет"}}"#;
+
+  #[test]
+  fn round_trips_with_the_right_password() {
+    let sealed = encrypt(CONTENT, "pässword").unwrap();
+    assert!(!sealed.contains("секрет"));
+    assert_eq!(decrypt(&sealed, "pässword").unwrap(), CONTENT);
+    assert!(decrypt(&sealed, "password").is_err_and(|err| err == "wrong password or damaged file"));
+    assert!(encrypt(CONTENT, "").is_err());
+  }
+
+  #[test]
+  fn rejects_tampered_envelopes() {
+    let sealed = encrypt(CONTENT, "pw").unwrap();
+    let mut envelope: Envelope = serde_json::from_str(&sealed).unwrap();
+    envelope.kdf.iterations = 2;
+    assert!(decrypt(&serde_json::to_string(&envelope).unwrap(), "pw").is_err());
+
+    let mut envelope: Envelope = serde_json::from_str(&sealed).unwrap();
+    envelope.kdf.memory_kib = MAX_KDF_MEMORY_KIB * 2;
+    assert!(decrypt(&serde_json::to_string(&envelope).unwrap(), "pw").is_err_and(|err| err.contains("unsupported")));
+
+    let mut envelope: Envelope = serde_json::from_str(&sealed).unwrap();
+    envelope.nonce = BASE64.encode([0u8; 12]);
+    assert!(decrypt(&serde_json::to_string(&envelope).unwrap(), "pw").is_err_and(|err| err == "damaged encrypted file"));
+
+    let mut envelope: Envelope = serde_json::from_str(&sealed).unwrap();
+    envelope.version = FORMAT_VERSION + 1;
+    assert!(decrypt(&serde_json::to_string(&envelope).unwrap(), "pw").is_err());
+
+    assert!(decrypt("{}", "pw").is_err_and(|err| err == "not an encrypted project file"));
+    assert!(decrypt(CONTENT, "pw").is_err());
+  }
+}
@@ -0,0 +1,333 @@
+use crate::labels::{DocLabels, doc_labels};
+use crate::markup::{escape, paragraphs};
+use crate::{AppState, EntityId, NoteState, ProjectState, StepState, file_stem, write_export_file};
+use sha2::{Digest, Sha256};
+use std::fmt::Write as _;
+use std::io::{Cursor, Write};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+const CONTAINER_XML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<container version="1.0" xmlns="urn:oasis:names:tc:opendocument:xmlns:container">
+  <rootfiles>
+    <rootfile full-path="OEBPS/content.opf" media-type="application/oebps-package+xml"/>
+  </rootfiles>
+</container>
+"#;
+
+const EPUB_CSS: &str = r#"body { font-family: serif; line-height: 1.5; margin: 0 5%; }
+h1 { font-size: 1.6em; margin: 1.2em 0 0.4em; }
+h2 { font-size: 1.2em; margin: 1.2em 0 0.4em; }
+.meta { color: #555; font-size: 0.9em; margin: 0.2em 0; }
+.steps { list-style: none; padding: 0; }
+.steps li { margin: 0.3em 0; }
+.steps .steps { margin: 0.3em 0 0 1.4em; }
+.steps li.done { color: #666; text-decoration: line-through; }
+.empty { color: #777; font-style: italic; }
+"#;
+
+struct Chapter {
+  file: String,
+  title: String,
+  body: String,
+  children: Vec<usize>,
+  top_level: bool,
+}
+
+fn xhtml_page(title: &str, language: &str, body: &str) -> String {
+  format!(
+    "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<!DOCTYPE html>\n<html xmlns=\"http://www.w3.org/1999/xhtml\" xmlns:epub=\"http://www.idpf.org/2007/ops\" xml:lang=\"{language}\" lang=\"{language}\">\n<head>\n<meta charset=\"UTF-8\"/>\n<title>{}</title>\n<link rel=\"stylesheet\" type=\"text/css\" href=\"style.css\"/>\n</head>\n<body>\n{body}</body>\n</html>\n",
+    escape(title)
+  )
+}
+
+fn write_paragraphs(out: &mut String, text: &str) {
+  for paragraph in paragraphs(text) {
+    let lines: Vec<String> = paragraph.iter().map(|line| escape(line)).collect();
+    let _ = writeln!(out, "<p>{}</p>", lines.join("<br/>"));
+  }
+}
+
+fn project_chapter(project: &ProjectState, labels: &DocLabels) -> String {
+  let mut out = format!("<h1>{}</h1>\n", escape(project.name.trim()));
+  let status = if project.status.trim().is_empty() { labels.status_not_set } else { project.status.trim() };
+  let _ = writeln!(out, "<p class=\"meta\">{}: {}</p>", escape(labels.project_status), escape(status));
+  if project.pinned {
+    let _ = writeln!(out, "<p class=\"meta\">{}</p>", escape(labels.pinned));
+  }
+  let (done, total) = project.step_progress();
+  let _ = writeln!(out, "<p class=\"meta\">{}: {}</p>", escape(labels.progress), escape(&labels.progress_caption(done, total)));
+  write_paragraphs(&mut out, &project.description);
+
+  let _ = writeln!(out, "<h2>{}</h2>", escape(labels.project_steps));
+  if project.steps.is_empty() {
+    let _ = writeln!(out, "<p class=\"empty\">{}</p>", escape(labels.no_steps));
+  } else {
+    write_steps(&mut out, &project.steps);
+  }
+  if project.notes.is_empty() {
+    let _ = writeln!(out, "<h2>{}</h2>\n<p class=\"empty\">{}</p>", escape(labels.project_notes), escape(labels.no_notes));
+  }
+  out
+}
+
+fn write_steps(out: &mut String, steps: &[StepState]) {
+  out.push_str("<ul class=\"steps\">\n");
+  for step in steps {
+    let (class, mark) = if step.done { (" class=\"done\"", "☑") } else { ("", "☐") };
+    let _ = write!(out, "<li{class}>{mark} {}", escape(step.text.trim()));
+    if !step.children.is_empty() {
+      out.push('\n');
+      write_steps(out, &step.children);
+    }
+    out.push_str("</li>\n");
+  }
+  out.push_str("</ul>\n");
+}
+
+fn note_chapter(note: &NoteState) -> String {
+  let mut out = format!("<h1>{}</h1>\n", escape(note.title.trim()));
+  write_paragraphs(&mut out, &note.body);
+  out
+}
+
+fn book_id(projects: &[&ProjectState]) -> String {
+  let mut hasher = Sha256::new();
+  for project in projects {
+    hasher.update(project.id.as_ref().map(ToString::to_string).unwrap_or_else(|| project.name.clone()).as_bytes());
+    hasher.update([0]);
+  }
+  let hex = hex::encode(&hasher.finalize()[..16]);
+  format!("urn:uuid:{}-{}-{}-{}-{}", &hex[..8], &hex[8..12], &hex[12..16], &hex[16..20], &hex[20..32])
+}
+
+fn collect_chapters(projects: &[&ProjectState], title: &str, workspace: bool, labels: &DocLabels) -> Vec<Chapter> {
+  let mut chapters = Vec::new();
+  if workspace {
+    let mut body = format!("<h1>{}</h1>\n<ol>\n", escape(title));
+    for (index, project) in projects.iter().enumerate() {
+      let (done, total) = project.step_progress();
+      let _ = writeln!(
+        body,
+        "<li><a href=\"project-{}.xhtml\">{}</a> <span class=\"meta\">({done}/{total})</span></li>",
+        index + 1,
+        escape(project.name.trim())
+      );
+    }
+    body.push_str("</ol>\n");
+    chapters.push(Chapter { file: "title.xhtml".to_string(), title: title.to_string(), body, children: Vec::new(), top_level: true });
+  }
+
+  for (p_index, project) in projects.iter().enumerate() {
+    let project_chapter_index = chapters.len();
+    chapters.push(Chapter {
+      file: format!("project-{}.xhtml", p_index + 1),
+      title: project.name.trim().to_string(),
+      body: project_chapter(project, labels),
+      children: Vec::new(),
+      top_level: true,
+    });
+    for (n_index, note) in project.notes.iter().enumerate() {
+      let note_chapter_index = chapters.len();
+      chapters[project_chapter_index].children.push(note_chapter_index);
+      chapters.push(Chapter {
+        file: format!("project-{}-note-{}.xhtml", p_index + 1, n_index + 1),
+        title: note.title.trim().to_string(),
+        body: note_chapter(note),
+        children: Vec::new(),
+        top_level: false,
+      });
+    }
+  }
+  chapters
+}
+
+fn nav_document(chapters: &[Chapter], labels: &DocLabels, language: &str) -> String {
+  let mut body = format!("<nav epub:type=\"toc\" id=\"toc\">\n<h1>{}</h1>\n<ol>\n", escape(labels.contents));
+  for chapter in chapters.iter().filter(|chapter| chapter.top_level) {
+    let _ = write!(body, "<li><a href=\"{}\">{}</a>", chapter.file, escape(&chapter.title));
+    if !chapter.children.is_empty() {
+      body.push_str("\n<ol>\n");
+      for child in &chapter.children {
+        let child = &chapters[*child];
+        let _ = writeln!(body, "<li><a href=\"{}\">{}</a></li>", child.file, escape(&child.title));
+      }
+      body.push_str("</ol>\n");
+    }
+    body.push_str("</li>\n");
+  }
+  body.push_str("</ol>\n</nav>\n");
+  xhtml_page(labels.contents, language, &body)
+}
+
+fn ncx_document(chapters: &[Chapter], id: &str, title: &str) -> String {
+  let mut out = format!(
+    "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<ncx xmlns=\"http://www.daisy.org/z3986/2005/ncx/\" version=\"2005-1\">\n<head><meta name=\"dtb:uid\" content=\"{}\"/></head>\n<docTitle><text>{}</text></docTitle>\n<navMap>\n",
+    escape(id),
+    escape(title)
+  );
+  let mut order = 0;
+  for chapter in chapters.iter().filter(|chapter| chapter.top_level) {
+    order += 1;
+    let _ = writeln!(
+      out,
+      "<navPoint id=\"nav-{order}\" playOrder=\"{order}\"><navLabel><text>{}</text></navLabel><content src=\"{}\"/>",
+      escape(&chapter.title),
+      chapter.file
+    );
+    for child in &chapter.children {
+      order += 1;
+      let child = &chapters[*child];
+      let _ = writeln!(
+        out,
+        "<navPoint id=\"nav-{order}\" playOrder=\"{order}\"><navLabel><text>{}</text></navLabel><content src=\"{}\"/></navPoint>",
+        escape(&child.title),
+        child.file
+      );
+    }
+    out.push_str("</navPoint>\n");
+  }
+  out.push_str("</navMap>\n</ncx>\n");
+  out
+}
+
+fn package_document(chapters: &[Chapter], id: &str, title: &str, language: &str) -> String {
+  let modified = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ");
+  let mut manifest = String::from(
+    "<item id=\"nav\" href=\"nav.xhtml\" media-type=\"application/xhtml+xml\" properties=\"nav\"/>\n<item id=\"ncx\" href=\"toc.ncx\" media-type=\"application/x-dtbncx+xml\"/>\n<item id=\"css\" href=\"style.css\" media-type=\"text/css\"/>\n",
+  );
+  let mut spine = String::new();
+  for (index, chapter) in chapters.iter().enumerate() {
+    let _ = writeln!(manifest, "<item id=\"chapter-{index}\" href=\"{}\" media-type=\"application/xhtml+xml\"/>", chapter.file);
+    let _ = writeln!(spine, "<itemref idref=\"chapter-{index}\"/>");
+  }
+  format!(
+    "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<package xmlns=\"http://www.idpf.org/2007/opf\" version=\"3.0\" unique-identifier=\"book-id\" xml:lang=\"{language}\">\n<metadata xmlns:dc=\"http://purl.org/dc/elements/1.1/\">\n<dc:identifier id=\"book-id\">{}</dc:identifier>\n<dc:title>{}</dc:title>\n<dc:language>{language}</dc:language>\n<dc:creator>{}</dc:creator>\n<meta property=\"dcterms:modified\">{modified}</meta>\n</metadata>\n<manifest>\n{manifest}</manifest>\n<spine toc=\"ncx\">\n{spine}</spine>\n</package>\n",
+    escape(id),
+    escape(title),
+    escape(doc_labels(language).app_title),
+  )
+}
+
+pub(crate) fn render_epub(projects: &[&ProjectState], title: &str, workspace: bool, language: &str) -> Result<Vec<u8>, String> {
+  let labels = doc_labels(language);
+  let language = match language {
+    "en" | "uk" => language,
+    _ => "ru",
+  };
+  let id = book_id(projects);
+  let chapters = collect_chapters(projects, title, workspace, labels);
+
+  let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+  let stored = SimpleFileOptions::default().compression_method(CompressionMethod::Stored);
+  let deflated = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+  let mut add = |name: &str, content: &str, options: SimpleFileOptions| -> Result<(), String> {
+    zip.start_file(name, options).map_err(|err| err.to_string())?;
+    zip.write_all(content.as_bytes()).map_err(|err| err.to_string())
+  };
+
+  add("mimetype", "application/epub+zip", stored)?;
+  add("META-INF/container.xml", CONTAINER_XML, deflated)?;
+  add("OEBPS/content.opf", &package_document(&chapters, &id, title, language), deflated)?;
+  add("OEBPS/nav.xhtml", &nav_document(&chapters, labels, language), deflated)?;
+  add("OEBPS/toc.ncx", &ncx_document(&chapters, &id, title), deflated)?;
+  add("OEBPS/style.css", EPUB_CSS, deflated)?;
+  for chapter in &chapters {
+    add(&format!("OEBPS/{}", chapter.file), &xhtml_page(&chapter.title, language, &chapter.body), deflated)?;
+  }
+  Ok(zip.finish().map_err(|err| err.to_string())?.into_inner())
+}
+
+#[tauri::command]
+pub(crate) fn export_epub(app: tauri::AppHandle, state: AppState, project_id: Option<EntityId>) -> Result<String, String> {
+  let projects = state.select_projects(project_id.as_ref())?;
+  let workspace = project_id.is_none();
+  let (title, stem) = match projects.first() {
+    Some(project) if !workspace => (project.name.trim().to_string(), file_stem(&project.name, "project")),
+    _ => (doc_labels(&state.settings.language).workspace.to_string(), "workspace".to_string()),
+  };
+  let bytes = render_epub(&projects, &title, workspace, &state.settings.language)?;
+  write_export_file(&app, &stem, ".epub", &bytes)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::io::Read;
+  use zip::ZipArchive;
+
+  fn sample() -> AppState {
+    serde_json::from_str(
+      r#"{"projects":[{"id":1,"name":"Проект <А>","description":"Описание\nстрока\n\nвторой","status":"в работе","pinned":true,
+        "notes":[{"id":2,"title":"Заметка & Ё","body":"Текст & ещё\u0007"},{"id":3,"title":"Вторая","body":""}],
+        "steps":[{"id":4,"text":"Шаг 1","done":true,"children":[{"text":"Шаг 1.1"}]},{"text":"Шаг 2\u0001"}]},
+        {"name":"Empty"}]}"#,
+    )
+    .unwrap()
+  }
+
+  fn parse(content: &str) -> Result<roxmltree::Document<'_>, roxmltree::Error> {
+    roxmltree::Document::parse_with_options(content, roxmltree::ParsingOptions { allow_dtd: true, ..Default::default() })
+  }
+
+  fn entries(bytes: Vec<u8>) -> Vec<(String, CompressionMethod, String)> {
+    let mut archive = ZipArchive::new(Cursor::new(bytes)).unwrap();
+    (0..archive.len())
+      .map(|index| {
+        let mut file = archive.by_index(index).unwrap();
+        let mut content = String::new();
+        file.read_to_string(&mut content).unwrap();
+        (file.name().to_string(), file.compression(), content)
+      })
+      .collect()
+  }
+
+  #[test]
+  fn packages_a_well_formed_book() {
+    let state = sample();
+    let bytes = render_epub(&state.select_projects(None).unwrap(), "Рабочее & <всё>", true, "ru").unwrap();
+    let entries = entries(bytes);
+    assert_eq!(entries[0], ("mimetype".to_string(), CompressionMethod::Stored, "application/epub+zip".to_string()));
+    let names: Vec<&str> = entries.iter().map(|(name, ..)| name.as_str()).collect();
+    assert_eq!(
+      names,
+      [
+        "mimetype",
+        "META-INF/container.xml",
+        "OEBPS/content.opf",
+        "OEBPS/nav.xhtml",
+        "OEBPS/toc.ncx",
+        "OEBPS/style.css",
+        "OEBPS/title.xhtml",
+        "OEBPS/project-1.xhtml",
+        "OEBPS/project-1-note-1.xhtml",
+        "OEBPS/project-1-note-2.xhtml",
+        "OEBPS/project-2.xhtml",
+      ]
+    );
+    for (name, _, content) in entries.iter().filter(|(name, ..)| !name.ends_with("mimetype") && !name.ends_with(".css")) {
+      parse(content).unwrap_or_else(|err| panic!("{name} is not well-formed: {err}"));
+    }
+
+    let content = |name: &str| entries.iter().find(|entry| entry.0 == name).map(|entry| entry.2.as_str()).unwrap();
+    let package = parse(content("OEBPS/content.opf")).unwrap();
+    let spine: Vec<&str> = package.descendants().filter(|node| node.has_tag_name("itemref")).filter_map(|node| node.attribute("idref")).collect();
+    assert_eq!(spine.len(), 5);
+    let title = package.descendants().find(|node| node.has_tag_name("title")).and_then(|node| node.text());
+    assert_eq!(title, Some("Рабочее & <всё>"));
+    assert!(content("OEBPS/project-1.xhtml").contains("<h1>Проект &lt;А&gt;</h1>"));
+    assert!(content("OEBPS/project-1.xhtml").contains("<li class=\"done\">☑ Шаг 1\n<ul class=\"steps\">\n<li>☐ Шаг 1.1</li>"));
+    assert!(content("OEBPS/project-1-note-1.xhtml").contains("<p>Текст &amp; ещё</p>"));
+    assert!(content("OEBPS/nav.xhtml").contains("<a href=\"project-1-note-1.xhtml\">Заметка &amp; Ё</a>"));
+  }
+
+  #[test]
+  fn single_project_books_skip_the_title_page_and_keep_a_stable_id() {
+    let state = sample();
+    let projects = state.select_projects(Some(&EntityId::Num(1))).unwrap();
+    let names: Vec<String> = entries(render_epub(&projects, "Проект", false, "en").unwrap()).into_iter().map(|entry| entry.0).collect();
+    assert!(!names.iter().any(|name| name.ends_with("title.xhtml")));
+    assert_eq!(book_id(&projects), book_id(&projects));
+    assert_ne!(book_id(&projects), book_id(&state.select_projects(None).unwrap()));
+  }
+}
@@ -0,0 +1,129 @@
+use crate::{EntityId, ProjectState, step_tree};
+use serde::Deserialize;
+use serde_json::Value;
+
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+pub(crate) struct ExportFilter {
+  note_ids: Option<Vec<EntityId>>,
+  open_steps_only: bool,
+  exclude_descriptions: bool,
+  strip_ids: bool,
+}
+
+impl ExportFilter {
+  pub(crate) fn apply(&self, project: &ProjectState) -> ProjectState {
+    let mut result = project.clone();
+    if let Some(note_ids) = &self.note_ids {
+      result
+        .notes
+        .retain(|note| note.id.as_ref().is_some_and(|id| note_ids.contains(id)));
+    }
+    if self.open_steps_only {
+      step_tree::retain_open(&mut result.steps);
+    }
+    if self.exclude_descriptions {
+      result.description.clear();
+    }
+    if self.strip_ids {
+      result.id = None;
+      for note in &mut result.notes {
+        note.id = None;
+      }
+      step_tree::for_each_mut(&mut result.steps, &mut |step| step.id = None);
+    }
+    result
+  }
+}
+
+fn remove_empty_ids(value: &mut Value) {
+  match value {
+    Value::Object(map) => {
+      if map.get("id").is_some_and(Value::is_null) {
+        map.remove("id");
+      }
+      map.values_mut().for_each(remove_empty_ids);
+    }
+    Value::Array(items) => items.iter_mut().for_each(remove_empty_ids),
+    _ => {}
+  }
+}
+
+pub(crate) fn filter_project_payload(content: &str, filter: &ExportFilter) -> Result<String, String> {
+  let mut payload: Value = serde_json::from_str(content).map_err(|err| err.to_string())?;
+  let wrapped = payload.get("project").is_some();
+  let source = if wrapped { payload["project"].take() } else { payload.take() };
+  let project: ProjectState = serde_json::from_value(source).map_err(|err| err.to_string())?;
+
+  let mut filtered = serde_json::to_value(filter.apply(&project)).map_err(|err| err.to_string())?;
+  remove_empty_ids(&mut filtered);
+  if wrapped {
+    payload["project"] = filtered;
+    if filter.strip_ids {
+      payload["template"] = Value::Bool(true);
+    }
+  } else {
+    payload = filtered;
+  }
+  serde_json::to_string_pretty(&payload).map_err(|err| err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const PAYLOAD: &str = r#"{"format":"project-notes-studio-project","version":2,"project":{"id":"p","name":"A","description":"secret",
+    "notes":[{"id":"n1","title":"keep","body":"b"},{"id":"n2","title":"drop","body":"c"},{"title":"no id"}],
+    "steps":[{"id":"s1","text":"done","done":true},{"id":"s2","text":"open","children":[{"id":"s3","text":"closed","done":true}]}]}}"#;
+
+  fn filtered(content: &str, filter: &str) -> Value {
+    let filter: ExportFilter = serde_json::from_str(filter).unwrap();
+    serde_json::from_str(&filter_project_payload(content, &filter).unwrap()).unwrap()
+  }
+
+  #[test]
+  fn keeps_selected_notes_and_open_steps() {
+    let payload = filtered(PAYLOAD, r#"{"noteIds":["n1"],"openStepsOnly":true,"excludeDescriptions":true}"#);
+    let project = &payload["project"];
+    assert_eq!(payload["format"], "project-notes-studio-project");
+    assert!(payload.get("template").is_none());
+    assert_eq!(project["description"], "");
+    assert_eq!(project["notes"].as_array().unwrap().len(), 1);
+    assert_eq!(project["notes"][0]["title"], "keep");
+    assert_eq!(project["steps"].as_array().unwrap().len(), 1);
+    assert_eq!(project["steps"][0]["id"], "s2");
+    assert_eq!(project["steps"][0]["children"], serde_json::json!([]));
+  }
+
+  #[test]
+  fn an_empty_filter_keeps_everything() {
+    let payload = filtered(PAYLOAD, "{}");
+    let original: Value = serde_json::from_str(PAYLOAD).unwrap();
+    let project: ProjectState = serde_json::from_value(original["project"].clone()).unwrap();
+    let mut expected = serde_json::to_value(project).unwrap();
+    remove_empty_ids(&mut expected);
+    assert_eq!(payload["project"], expected);
+    assert!(payload["project"]["notes"][2].get("id").is_none());
+  }
+
+  #[test]
+  fn stripping_ids_marks_the_payload_as_a_template() {
+    let payload = filtered(PAYLOAD, r#"{"noteIds":[],"stripIds":true}"#);
+    assert_eq!(payload["template"], true);
+    assert!(payload["project"].get("id").is_none());
+    assert_eq!(payload["project"]["notes"], serde_json::json!([]));
+    assert!(payload["project"]["steps"][1]["children"][0].get("id").is_none());
+
+    let bare = filtered(r#"{"id":"p","name":"Bare","steps":[{"id":"s","text":"x"}]}"#, r#"{"stripIds":true}"#);
+    assert_eq!(bare["name"], "Bare");
+    assert!(bare.get("template").is_none() && bare["steps"][0].get("id").is_none());
+  }
+
+  #[test]
+  fn rejects_payloads_that_are_not_projects() {
+    let filter = ExportFilter::default();
+    assert!(filter_project_payload("{", &filter).is_err());
+    assert!(filter_project_payload(r#"{"project":"x"}"#, &filter).is_err());
+    assert!(filter_project_payload(r#"{"name":5}"#, &filter).is_err());
+  }
+}
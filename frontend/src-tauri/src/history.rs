@@ -0,0 +1,620 @@
+use crate::{AppState, EntityId, NoteState, ProjectState, SettingsState, StepState, data_dir, file_stem, step_tree, write_state_file};
+use git2::{DiffFormat, Oid, Patch, Repository, Signature, Sort, Tree};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+const PROJECTS_DIR: &str = "projects";
+const WORKSPACE_FILE: &str = "workspace.json";
+const GITIGNORE_FILE: &str = ".gitignore";
+const GITIGNORE: &str = "/app_state.json\n/attachments/\n/thumbnails/\n/revisions/\n/reminders.json\n/timer.json\n/templates.json\n";
+const AUTHOR_NAME: &str = "Project Notes Studio";
+const AUTHOR_EMAIL: &str = "history@project-notes-studio.local";
+const DEFAULT_LOG_LIMIT: usize = 200;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct WorkspaceFile {
+  projects: Vec<String>,
+  settings: SettingsState,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct HistoryEntry {
+  commit: String,
+  short_id: String,
+  summary: String,
+  message: String,
+  time: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct HistoryFileDiff {
+  path: String,
+  status: String,
+  additions: usize,
+  deletions: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct HistoryDiff {
+  from: String,
+  to: String,
+  files: Vec<HistoryFileDiff>,
+  patch: String,
+}
+
+fn encode_id(id: &EntityId) -> String {
+  match id {
+    EntityId::Num(number) => number.to_string(),
+    EntityId::Str(value) if value.is_empty() => "%".to_string(),
+    EntityId::Str(value) => {
+      let numeric = value.bytes().all(|byte| byte.is_ascii_digit());
+      value
+        .bytes()
+        .enumerate()
+        .map(|(index, byte)| {
+          let plain = byte.is_ascii_lowercase() || byte.is_ascii_digit() || byte == b'-' || byte == b'_';
+          if plain && !(numeric && index == 0) { char::from(byte).to_string() } else { format!("%{byte:02X}") }
+        })
+        .collect()
+    }
+  }
+}
+
+fn project_file(id: Option<&EntityId>, index: usize) -> String {
+  match id {
+    Some(id) => format!("{}.json", encode_id(id)),
+    None => format!("project-{}.json", index + 1),
+  }
+}
+
+fn to_json<T: Serialize>(value: &T) -> Result<String, String> {
+  serde_json::to_string_pretty(value)
+    .map(|json| format!("{json}\n"))
+    .map_err(|err| err.to_string())
+}
+
+fn snapshot(state: &AppState) -> Result<(String, Vec<(String, String)>), String> {
+  let mut files = Vec::with_capacity(state.projects.len());
+  for (index, project) in state.projects.iter().enumerate() {
+    files.push((project_file(project.id.as_ref(), index), to_json(project)?));
+  }
+  let workspace = WorkspaceFile {
+    projects: files.iter().map(|(name, _)| name.clone()).collect(),
+    settings: state.settings.clone(),
+  };
+  Ok((to_json(&workspace)?, files))
+}
+
+fn open_repository(dir: &Path) -> Result<Repository, String> {
+  let repo = match Repository::open(dir) {
+    Ok(repo) => repo,
+    Err(_) => Repository::init(dir).map_err(|err| err.to_string())?,
+  };
+  let ignore = dir.join(GITIGNORE_FILE);
+  if fs::read_to_string(&ignore).ok().as_deref() != Some(GITIGNORE) {
+    fs::write(ignore, GITIGNORE).map_err(|err| err.to_string())?;
+  }
+  Ok(repo)
+}
+
+fn existing_repository(app: &tauri::AppHandle) -> Result<Repository, String> {
+  Repository::open(data_dir(app)?).map_err(|_| "history is not enabled".to_string())
+}
+
+fn build_tree(repo: &Repository, workspace: &str, files: &[(String, String)]) -> Result<Oid, String> {
+  let blob = |content: &str| repo.blob(content.as_bytes()).map_err(|err| err.to_string());
+  let mut projects = repo.treebuilder(None).map_err(|err| err.to_string())?;
+  for (name, content) in files {
+    projects.insert(name, blob(content)?, 0o100644).map_err(|err| err.to_string())?;
+  }
+  let projects = projects.write().map_err(|err| err.to_string())?;
+
+  let mut root = repo.treebuilder(None).map_err(|err| err.to_string())?;
+  root.insert(GITIGNORE_FILE, blob(GITIGNORE)?, 0o100644).map_err(|err| err.to_string())?;
+  root.insert(WORKSPACE_FILE, blob(workspace)?, 0o100644).map_err(|err| err.to_string())?;
+  root.insert(PROJECTS_DIR, projects, 0o040000).map_err(|err| err.to_string())?;
+  root.write().map_err(|err| err.to_string())
+}
+
+fn write_working_tree(dir: &Path, workspace: &str, files: &[(String, String)]) -> Result<(), String> {
+  let projects_dir = dir.join(PROJECTS_DIR);
+  fs::create_dir_all(&projects_dir).map_err(|err| err.to_string())?;
+  for entry in fs::read_dir(&projects_dir).map_err(|err| err.to_string())? {
+    let path = entry.map_err(|err| err.to_string())?.path();
+    let name = path.file_name().and_then(|name| name.to_str()).unwrap_or_default();
+    if path.is_file() && !files.iter().any(|(file, _)| file == name) {
+      fs::remove_file(&path).map_err(|err| err.to_string())?;
+    }
+  }
+  for (name, content) in files {
+    fs::write(projects_dir.join(name), content).map_err(|err| err.to_string())?;
+  }
+  fs::write(dir.join(WORKSPACE_FILE), workspace).map_err(|err| err.to_string())
+}
+
+fn read_blob(repo: &Repository, tree: &Tree, path: &str) -> Option<String> {
+  let entry = tree.get_path(Path::new(path)).ok()?;
+  let blob = repo.find_blob(entry.id()).ok()?;
+  String::from_utf8(blob.content().to_vec()).ok()
+}
+
+fn read_project(repo: &Repository, tree: &Tree, file: &str) -> Option<ProjectState> {
+  let content = read_blob(repo, tree, &format!("{PROJECTS_DIR}/{file}"))?;
+  serde_json::from_str(&content).ok()
+}
+
+fn state_at(repo: &Repository, tree: &Tree) -> Result<AppState, String> {
+  let workspace: WorkspaceFile = read_blob(repo, tree, WORKSPACE_FILE)
+    .and_then(|content| serde_json::from_str(&content).ok())
+    .ok_or_else(|| "history commit has no workspace file".to_string())?;
+  let projects = workspace
+    .projects
+    .iter()
+    .filter_map(|file| read_project(repo, tree, file))
+    .collect();
+  Ok(AppState { projects, settings: workspace.settings })
+}
+
+fn count_changes<T>(old: &[T], new: &[T], id: fn(&T) -> Option<&EntityId>, same: fn(&T, &T) -> bool) -> (usize, usize, usize) {
+  let find = |items: &[T], item: &T| items.iter().any(|other| id(other).is_some() && id(other) == id(item));
+  let added = new.iter().filter(|item| !find(old, item)).count();
+  let removed = old.iter().filter(|item| !find(new, item)).count();
+  let edited = new
+    .iter()
+    .filter(|item| old.iter().any(|other| id(other).is_some() && id(other) == id(item) && !same(other, item)))
+    .count();
+  (added, removed, edited)
+}
+
+fn describe_project(old: &ProjectState, new: &ProjectState) -> Vec<String> {
+  let mut parts = Vec::new();
+  if old.name != new.name {
+    parts.push(format!("renamed from \"{}\"", old.name.trim()));
+  }
+  if old.description != new.description {
+    parts.push("description".to_string());
+  }
+  if old.status != new.status {
+    parts.push(format!("status \"{}\"", new.status.trim()));
+  }
+  if old.pinned != new.pinned {
+    parts.push(if new.pinned { "pinned" } else { "unpinned" }.to_string());
+  }
+
+  let (added, removed, edited) = count_changes(
+    &old.notes,
+    &new.notes,
+    |note| note.id.as_ref(),
+    |a, b| a.title == b.title && a.body == b.body,
+  );
+  for (count, verb) in [(added, "added"), (removed, "removed"), (edited, "edited")] {
+    if count > 0 {
+      parts.push(format!("{count} note(s) {verb}"));
+    }
+  }
+
+  let old_steps: Vec<&StepState> = step_tree::flatten(&old.steps).into_iter().map(|(_, step)| step).collect();
+  let new_steps: Vec<&StepState> = step_tree::flatten(&new.steps).into_iter().map(|(_, step)| step).collect();
+  let (added, removed, edited) = count_changes(&old_steps, &new_steps, |step| step.id.as_ref(), |a, b| a.text == b.text);
+  let completed = new_steps
+    .iter()
+    .filter(|step| step.done && old_steps.iter().any(|other| other.id.is_some() && other.id == step.id && !other.done))
+    .count();
+  let reopened = new_steps
+    .iter()
+    .filter(|step| !step.done && old_steps.iter().any(|other| other.id.is_some() && other.id == step.id && other.done))
+    .count();
+  for (count, verb) in [(added, "added"), (removed, "removed"), (edited, "edited"), (completed, "completed"), (reopened, "reopened")] {
+    if count > 0 {
+      parts.push(format!("{count} step(s) {verb}"));
+    }
+  }
+  if parts.is_empty() {
+    parts.push("reordered".to_string());
+  }
+  parts
+}
+
+fn describe_changes(old: Option<&AppState>, new: &AppState) -> String {
+  let Some(old) = old else {
+    return format!("Start history with {} project(s)", new.projects.len());
+  };
+  let same_project = |a: &ProjectState, b: &ProjectState| a.id.is_some() && a.id == b.id;
+  let mut lines = Vec::new();
+  for project in &new.projects {
+    match old.projects.iter().find(|other| same_project(other, project)) {
+      None => lines.push(format!("Add project \"{}\"", project.name.trim())),
+      Some(previous) => {
+        if to_json(previous).ok() != to_json(project).ok() {
+          let parts = describe_project(previous, project);
+          lines.push(format!("Update project \"{}\": {}", project.name.trim(), parts.join(", ")));
+        }
+      }
+    }
+  }
+  for project in &old.projects {
+    if !new.projects.iter().any(|other| same_project(other, project)) {
+      lines.push(format!("Delete project \"{}\"", project.name.trim()));
+    }
+  }
+  let old_order: Vec<_> = old.projects.iter().map(|project| &project.id).collect();
+  let new_order: Vec<_> = new.projects.iter().map(|project| &project.id).collect();
+  if lines.is_empty() && old_order != new_order {
+    lines.push("Reorder projects".to_string());
+  }
+  if to_json(&old.settings).ok() != to_json(&new.settings).ok() {
+    lines.push("Update settings".to_string());
+  }
+
+  match lines.len() {
+    0 => "Save workspace".to_string(),
+    1 => lines.remove(0),
+    count => format!("Update workspace ({count} changes)\n\n{}", lines.join("\n")),
+  }
+}
+
+fn commit_state(repo: &Repository, dir: &Path, state: &AppState, message: Option<String>) -> Result<Option<Oid>, String> {
+  let (workspace, files) = snapshot(state)?;
+  let tree_id = build_tree(repo, &workspace, &files)?;
+  let parent = repo.head().ok().and_then(|head| head.peel_to_commit().ok());
+  if parent.as_ref().is_some_and(|commit| commit.tree_id() == tree_id) {
+    return Ok(None);
+  }
+
+  let previous = match &parent {
+    Some(commit) => Some(state_at(repo, &commit.tree().map_err(|err| err.to_string())?)?),
+    None => None,
+  };
+  let message = message.unwrap_or_else(|| describe_changes(previous.as_ref(), state));
+  let tree = repo.find_tree(tree_id).map_err(|err| err.to_string())?;
+  let signature = Signature::now(AUTHOR_NAME, AUTHOR_EMAIL).map_err(|err| err.to_string())?;
+  let parents: Vec<_> = parent.iter().collect();
+  let oid = repo
+    .commit(Some("HEAD"), &signature, &signature, &message, &tree, &parents)
+    .map_err(|err| err.to_string())?;
+
+  write_working_tree(dir, &workspace, &files)?;
+  let mut index = repo.index().map_err(|err| err.to_string())?;
+  index.read_tree(&tree).map_err(|err| err.to_string())?;
+  index.write().map_err(|err| err.to_string())?;
+  Ok(Some(oid))
+}
+
+pub(crate) fn record_save(app: &tauri::AppHandle, state: &AppState) -> Result<(), String> {
+  let dir = data_dir(app)?;
+  let repo = open_repository(&dir)?;
+  commit_state(&repo, &dir, state, None).map(|_| ())
+}
+
+fn project_in(state: &AppState, project_id: &EntityId) -> Option<ProjectState> {
+  state.projects.iter().find(|project| project.id.as_ref() == Some(project_id)).cloned()
+}
+
+fn note_in(project: &ProjectState, note_id: &EntityId) -> Option<NoteState> {
+  project.notes.iter().find(|note| note.id.as_ref() == Some(note_id)).cloned()
+}
+
+fn note_text(note: &NoteState) -> String {
+  format!("# {}\n\n{}\n", note.title.trim(), note.body.trim_end())
+}
+
+fn subject_at(
+  repo: &Repository,
+  tree: &Tree,
+  project_id: Option<&EntityId>,
+  note_id: Option<&EntityId>,
+) -> Option<String> {
+  let project_id = project_id?;
+  let project = read_project(repo, tree, &project_file(Some(project_id), 0))?;
+  match note_id {
+    Some(note_id) => note_in(&project, note_id).map(|note| note_text(&note)),
+    None => to_json(&project).ok(),
+  }
+}
+
+fn resolve_tree<'repo>(repo: &'repo Repository, spec: &str) -> Result<(Oid, Tree<'repo>), String> {
+  let object = repo.revparse_single(spec).map_err(|_| format!("history version {spec} not found"))?;
+  let commit = object.peel_to_commit().map_err(|err| err.to_string())?;
+  let tree = commit.tree().map_err(|err| err.to_string())?;
+  Ok((commit.id(), tree))
+}
+
+fn patch_text(patch: &mut Patch) -> Result<String, String> {
+  let buf = patch.to_buf().map_err(|err| err.to_string())?;
+  Ok(String::from_utf8_lossy(&buf).into_owned())
+}
+
+fn log_entries(
+  repo: &Repository,
+  project_id: Option<&EntityId>,
+  note_id: Option<&EntityId>,
+  limit: Option<usize>,
+) -> Result<Vec<HistoryEntry>, String> {
+  if note_id.is_some() && project_id.is_none() {
+    return Err("note history needs a project id".to_string());
+  }
+  if repo.head().is_err() {
+    return Ok(Vec::new());
+  }
+  let mut walk = repo.revwalk().map_err(|err| err.to_string())?;
+  walk.set_sorting(Sort::TIME | Sort::TOPOLOGICAL).map_err(|err| err.to_string())?;
+  walk.push_head().map_err(|err| err.to_string())?;
+
+  let limit = limit.unwrap_or(DEFAULT_LOG_LIMIT);
+  let mut entries = Vec::new();
+  for oid in walk {
+    if entries.len() >= limit {
+      break;
+    }
+    let commit = repo.find_commit(oid.map_err(|err| err.to_string())?).map_err(|err| err.to_string())?;
+    if project_id.is_some() {
+      let tree = commit.tree().map_err(|err| err.to_string())?;
+      let current = subject_at(repo, &tree, project_id, note_id);
+      let previous = commit
+        .parent(0)
+        .ok()
+        .and_then(|parent| parent.tree().ok())
+        .and_then(|tree| subject_at(repo, &tree, project_id, note_id));
+      if current == previous {
+        continue;
+      }
+    }
+    let time = chrono::DateTime::from_timestamp(commit.time().seconds(), 0)
+      .map(|time| time.to_rfc3339())
+      .unwrap_or_default();
+    let id = commit.id().to_string();
+    entries.push(HistoryEntry {
+      short_id: id.chars().take(8).collect(),
+      commit: id,
+      summary: commit.summary().unwrap_or_default().to_string(),
+      message: commit.message().unwrap_or_default().trim().to_string(),
+      time,
+    });
+  }
+  Ok(entries)
+}
+
+fn diff_versions(
+  repo: &Repository,
+  from: &str,
+  to: Option<&str>,
+  project_id: Option<&EntityId>,
+  note_id: Option<&EntityId>,
+) -> Result<HistoryDiff, String> {
+  let (from_id, from_tree) = resolve_tree(repo, from)?;
+  let (to_id, to_tree) = resolve_tree(repo, to.unwrap_or("HEAD"))?;
+
+  if let (Some(project_id), Some(note_id)) = (project_id, note_id) {
+    let old = subject_at(repo, &from_tree, Some(project_id), Some(note_id)).unwrap_or_default();
+    let new = subject_at(repo, &to_tree, Some(project_id), Some(note_id)).unwrap_or_default();
+    let path = format!("note-{}.md", file_stem(&note_id.to_string(), "note"));
+    let mut patch = Patch::from_buffers(old.as_bytes(), Some(Path::new(&path)), new.as_bytes(), Some(Path::new(&path)), None)
+      .map_err(|err| err.to_string())?;
+    let (_, additions, deletions) = patch.line_stats().map_err(|err| err.to_string())?;
+    let status = match (old.is_empty(), new.is_empty()) {
+      (true, false) => "added",
+      (false, true) => "deleted",
+      _ if old == new => "unmodified",
+      _ => "modified",
+    };
+    return Ok(HistoryDiff {
+      from: from_id.to_string(),
+      to: to_id.to_string(),
+      files: vec![HistoryFileDiff { path, status: status.to_string(), additions, deletions }],
+      patch: patch_text(&mut patch)?,
+    });
+  }
+
+  let mut options = git2::DiffOptions::new();
+  if let Some(project_id) = project_id {
+    options.pathspec(format!("{PROJECTS_DIR}/{}", project_file(Some(project_id), 0)));
+  }
+  let diff = repo
+    .diff_tree_to_tree(Some(&from_tree), Some(&to_tree), Some(&mut options))
+    .map_err(|err| err.to_string())?;
+
+  let mut files = Vec::new();
+  for index in 0..diff.deltas().len() {
+    let Some(patch) = Patch::from_diff(&diff, index).map_err(|err| err.to_string())? else {
+      continue;
+    };
+    let delta = patch.delta();
+    let path = delta
+      .new_file()
+      .path()
+      .or_else(|| delta.old_file().path())
+      .map(|path| path.to_string_lossy().into_owned())
+      .unwrap_or_default();
+    let status = format!("{:?}", delta.status()).to_lowercase();
+    let (_, additions, deletions) = patch.line_stats().map_err(|err| err.to_string())?;
+    files.push(HistoryFileDiff { path, status, additions, deletions });
+  }
+
+  let mut text = String::new();
+  diff
+    .print(DiffFormat::Patch, |_, _, line| {
+      if matches!(line.origin(), '+' | '-' | ' ') {
+        text.push(line.origin());
+      }
+      text.push_str(&String::from_utf8_lossy(line.content()));
+      true
+    })
+    .map_err(|err| err.to_string())?;
+
+  Ok(HistoryDiff { from: from_id.to_string(), to: to_id.to_string(), files, patch: text })
+}
+
+fn restored_state(
+  repo: &Repository,
+  commit: &str,
+  current: AppState,
+  project_id: Option<&EntityId>,
+  note_id: Option<&EntityId>,
+) -> Result<(AppState, String), String> {
+  let (commit_id, tree) = resolve_tree(repo, commit)?;
+  let past = state_at(repo, &tree)?;
+  let short_id: String = commit_id.to_string().chars().take(8).collect();
+  let mut state = current;
+
+  let message = match (project_id, note_id) {
+    (None, None) => {
+      state.projects = past.projects;
+      format!("Restore workspace from {short_id}")
+    }
+    (Some(project_id), None) => {
+      let project = project_in(&past, project_id).ok_or_else(|| "project not found in this version".to_string())?;
+      let message = format!("Restore project \"{}\" from {short_id}", project.name.trim());
+      match state.projects.iter().position(|existing| existing.id.as_ref() == Some(project_id)) {
+        Some(index) => state.projects[index] = project,
+        None => {
+          let index = past
+            .projects
+            .iter()
+            .position(|existing| existing.id.as_ref() == Some(project_id))
+            .unwrap_or(state.projects.len())
+            .min(state.projects.len());
+          state.projects.insert(index, project);
+        }
+      }
+      message
+    }
+    (Some(project_id), Some(note_id)) => {
+      let past_project = project_in(&past, project_id).ok_or_else(|| "project not found in this version".to_string())?;
+      let note = note_in(&past_project, note_id).ok_or_else(|| "note not found in this version".to_string())?;
+      let project = state
+        .projects
+        .iter_mut()
+        .find(|existing| existing.id.as_ref() == Some(project_id))
+        .ok_or_else(|| "project not found".to_string())?;
+      let message = format!("Restore note \"{}\" in \"{}\" from {short_id}", note.title.trim(), project.name.trim());
+      match project.notes.iter().position(|existing| existing.id.as_ref() == Some(note_id)) {
+        Some(index) => project.notes[index] = note,
+        None => project.notes.push(note),
+      }
+      message
+    }
+    (None, Some(_)) => return Err("note history needs a project id".to_string()),
+  };
+  Ok((state, message))
+}
+
+#[tauri::command]
+pub(crate) fn history_log(
+  app: tauri::AppHandle,
+  project_id: Option<EntityId>,
+  note_id: Option<EntityId>,
+  limit: Option<usize>,
+) -> Result<Vec<HistoryEntry>, String> {
+  log_entries(&existing_repository(&app)?, project_id.as_ref(), note_id.as_ref(), limit)
+}
+
+#[tauri::command]
+pub(crate) fn history_diff(
+  app: tauri::AppHandle,
+  from: String,
+  to: Option<String>,
+  project_id: Option<EntityId>,
+  note_id: Option<EntityId>,
+) -> Result<HistoryDiff, String> {
+  diff_versions(&existing_repository(&app)?, &from, to.as_deref(), project_id.as_ref(), note_id.as_ref())
+}
+
+#[tauri::command]
+pub(crate) fn history_restore(
+  app: tauri::AppHandle,
+  commit: String,
+  current: AppState,
+  project_id: Option<EntityId>,
+  note_id: Option<EntityId>,
+) -> Result<AppState, String> {
+  let dir = data_dir(&app)?;
+  let repo = existing_repository(&app)?;
+  let (state, message) = restored_state(&repo, &commit, current, project_id.as_ref(), note_id.as_ref())?;
+  write_state_file(&app, &state)?;
+  commit_state(&repo, &dir, &state, Some(message))?;
+  Ok(state)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test_support::TempDir;
+
+  fn sample() -> AppState {
+    serde_json::from_str(
+      r#"{"projects":[{"id":"p1","name":"Alpha","notes":[{"id":"n1","title":"N","body":"one\ntwo"}],"steps":[{"id":"s1","text":"a"}]}]}"#,
+    )
+    .unwrap()
+  }
+
+  fn id(value: &str) -> EntityId {
+    EntityId::parse(value)
+  }
+
+  #[test]
+  fn file_names_are_lossless() {
+    let ids = [
+      EntityId::Num(12),
+      EntityId::Str("12".into()),
+      EntityId::Str("a b".into()),
+      EntityId::Str("a-b".into()),
+      EntityId::Str("A-B".into()),
+      EntityId::Str("a/b".into()),
+      EntityId::Str("a_b".into()),
+      EntityId::Str("ы".into()),
+      EntityId::Str(String::new()),
+    ];
+    let names: std::collections::HashSet<_> = ids.iter().map(|id| project_file(Some(id), 0).to_lowercase()).collect();
+    assert_eq!(names.len(), ids.len());
+    assert_eq!(project_file(Some(&id("0f3c-9a")), 0), "0f3c-9a.json");
+    assert_eq!(project_file(Some(&EntityId::Str("A/b".into())), 0), "%41%2Fb.json");
+    assert_eq!(project_file(None, 2), "project-3.json");
+  }
+
+  #[test]
+  fn records_browses_and_restores() {
+    let dir = TempDir::new("history", "flow");
+    let repo = open_repository(&dir.0).unwrap();
+    let mut state = sample();
+    assert!(commit_state(&repo, &dir.0, &state, None).unwrap().is_some());
+    assert!(commit_state(&repo, &dir.0, &state, None).unwrap().is_none());
+    state.projects[0].steps[0].done = true;
+    state.projects[0].notes[0].body = "one\nthree".into();
+    commit_state(&repo, &dir.0, &state, None).unwrap();
+    state.projects.push(serde_json::from_str(r#"{"id":"P2","name":"Beta"}"#).unwrap());
+    commit_state(&repo, &dir.0, &state, None).unwrap();
+
+    let log = log_entries(&repo, None, None, None).unwrap();
+    assert_eq!(log.len(), 3);
+    assert_eq!(log[0].summary, "Add project \"Beta\"");
+    assert_eq!(log[1].summary, "Update project \"Alpha\": 1 note(s) edited, 1 step(s) completed");
+    assert_eq!(log_entries(&repo, Some(&id("p1")), Some(&id("n1")), None).unwrap().len(), 2);
+    assert_eq!(log_entries(&repo, Some(&id("P2")), None, None).unwrap().len(), 1);
+    assert_eq!(log_entries(&repo, None, None, Some(1)).unwrap().len(), 1);
+    assert!(log_entries(&repo, None, Some(&id("n1")), None).is_err());
+    assert!(dir.0.join(PROJECTS_DIR).join("%502.json").exists());
+
+    let note = diff_versions(&repo, &log[2].commit, None, Some(&id("p1")), Some(&id("n1"))).unwrap();
+    assert!(note.patch.contains("-two") && note.patch.contains("+three"), "{}", note.patch);
+    assert_eq!((note.files[0].additions, note.files[0].deletions), (1, 1));
+    let project = diff_versions(&repo, &log[1].commit, Some(&log[0].commit), Some(&id("p1")), None).unwrap();
+    assert!(project.files.is_empty());
+    assert!(diff_versions(&repo, "nope", None, None, None).is_err());
+
+    let (restored, message) = restored_state(&repo, &log[2].commit, state.clone(), Some(&id("p1")), None).unwrap();
+    assert!(!restored.projects[0].steps[0].done);
+    assert_eq!(restored.projects.len(), 2);
+    assert!(message.starts_with("Restore project \"Alpha\""));
+    let (restored, _) = restored_state(&repo, &log[2].commit, state.clone(), Some(&id("p1")), Some(&id("n1"))).unwrap();
+    assert_eq!(restored.projects[0].notes[0].body, "one\ntwo");
+    assert!(restored.projects[0].steps[0].done);
+    let (restored, _) = restored_state(&repo, &log[2].commit, state.clone(), None, None).unwrap();
+    assert_eq!(restored.projects.len(), 1);
+    assert!(restored_state(&repo, &log[2].commit, state, Some(&id("P2")), None).is_err());
+  }
+}
@@ -0,0 +1,101 @@
+use super::{ImportReport, Unmapped, files_with_extension, group_step, html, name_from_file, note, project};
+use crate::NoteState;
+use crate::tags::clean_tags;
+use std::collections::BTreeMap;
+
+fn child_text<'a>(node: roxmltree::Node<'a, 'a>, name: &str) -> Option<&'a str> {
+  node
+    .children()
+    .find(|child| child.has_tag_name(name))
+    .and_then(|child| child.text())
+}
+
+pub(super) fn import(files: &BTreeMap<String, String>) -> Result<ImportReport, String> {
+  let mut unmapped = Unmapped::default();
+  let mut projects = Vec::new();
+
+  for (file, content) in files_with_extension(files, "enex") {
+    let options = roxmltree::ParsingOptions { allow_dtd: true, ..Default::default() };
+    let document = roxmltree::Document::parse_with_options(content, options)
+      .map_err(|err| format!("not an Evernote export: {err}"))?;
+    let root = document.root_element();
+    if !root.has_tag_name("en-export") {
+      return Err("not an Evernote export".to_string());
+    }
+
+    let notebook = name_from_file(file, "Evernote");
+    let mut result = project(&notebook, "");
+    for element in root.children().filter(|child| child.has_tag_name("note")) {
+      let title = child_text(element, "title").unwrap_or_default();
+      let markdown = html::to_markdown(child_text(element, "content").unwrap_or_default());
+      let (mut body, steps) = html::split_checklist(&markdown);
+      if !steps.is_empty() {
+        result.steps.push(group_step(title, steps));
+      }
+
+      if let Some(attributes) = element.children().find(|child| child.has_tag_name("note-attributes")) {
+        if let Some(url) = child_text(attributes, "source-url").filter(|url| !url.trim().is_empty()) {
+          body.push_str(&format!("\n\n{}", url.trim()));
+        }
+        unmapped.add_if(child_text(attributes, "reminder-time").is_some(), "reminders");
+        unmapped.add_if(child_text(attributes, "author").is_some(), "authors");
+        unmapped.add_if(child_text(attributes, "latitude").is_some(), "locations");
+      }
+      let tags = clean_tags(element.children().filter(|child| child.has_tag_name("tag")).filter_map(|child| child.text()));
+      result.notes.push(NoteState { tags, ..note(title, &body) });
+
+      let resources = element.children().filter(|child| child.has_tag_name("resource")).count();
+      unmapped.add_if(resources > 0, "attachments");
+    }
+    projects.push(result);
+  }
+
+  if projects.is_empty() {
+    return Err("no Evernote notebooks found".to_string());
+  }
+  Ok(unmapped.into_report(projects))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const NOTEBOOK: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE en-export SYSTEM "http://xml.evernote.com/pub/evernote-export3.dtd">
+<en-export>
+  <note>
+    <title>Встреча</title>
+    <content><![CDATA[<en-note><div>Повестка &amp; итоги</div><div><en-todo checked="true"/>Отправить</div><div><en-todo/>Позвонить</div></en-note>]]></content>
+    <tag>Работа</tag><tag>работа</tag><tag>Клиенты</tag>
+    <note-attributes><source-url>https://example.com</source-url><author>Me</author></note-attributes>
+    <resource><data encoding="base64">AAAA</data></resource>
+  </note>
+</en-export>"#;
+
+  #[test]
+  fn imports_each_notebook_as_a_project() {
+    let files = BTreeMap::from([("Рабочий блокнот.enex".to_string(), NOTEBOOK.to_string()), ("notes.txt".to_string(), "x".to_string())]);
+    let report = serde_json::to_value(import(&files).unwrap()).unwrap();
+    let project = &report["projects"][0];
+    assert_eq!(project["name"], "Рабочий блокнот");
+    assert_eq!(project["notes"][0]["title"], "Встреча");
+    assert_eq!(project["notes"][0]["tags"], serde_json::json!(["Работа", "Клиенты"]));
+    assert!(project["notes"][0]["body"].as_str().unwrap().starts_with("Повестка & итоги"));
+    assert!(project["notes"][0]["body"].as_str().unwrap().ends_with("https://example.com"));
+    assert_eq!(project["steps"][0]["children"][0]["text"], "Отправить");
+    assert_eq!(project["steps"][0]["children"][0]["done"], true);
+    assert_eq!(project["steps"][0]["children"][1]["done"], false);
+    assert_eq!(
+      report["unmapped"],
+      serde_json::json!([{ "field": "attachments", "count": 1 }, { "field": "authors", "count": 1 }])
+    );
+  }
+
+  #[test]
+  fn rejects_malformed_exports() {
+    let file = |content: &str| BTreeMap::from([("a.enex".to_string(), content.to_string())]);
+    assert!(import(&file("<en-export><note>")).is_err());
+    assert!(import(&file("<html/>")).is_err());
+    assert!(import(&BTreeMap::from([("a.txt".to_string(), NOTEBOOK.to_string())])).is_err());
+  }
+}
@@ -0,0 +1,367 @@
+use crate::StepState;
+
+fn decode_entity(entity: &str) -> Option<char> {
+  match entity {
+    "amp" => Some('&'),
+    "lt" => Some('<'),
+    "gt" => Some('>'),
+    "quot" => Some('"'),
+    "apos" => Some('\''),
+    "nbsp" => Some(' '),
+    "mdash" => Some('—'),
+    "ndash" => Some('–'),
+    "laquo" => Some('«'),
+    "raquo" => Some('»'),
+    "hellip" => Some('…'),
+    _ => {
+      let number = entity.strip_prefix('#')?;
+      let code = match number.strip_prefix(['x', 'X']) {
+        Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+        None => number.parse().ok()?,
+      };
+      char::from_u32(code)
+    }
+  }
+}
+
+pub(super) fn decode_entities(text: &str) -> String {
+  let mut out = String::with_capacity(text.len());
+  let mut rest = text;
+  while let Some(start) = rest.find('&') {
+    out.push_str(&rest[..start]);
+    let tail = &rest[start + 1..];
+    match tail.find(';').filter(|end| *end <= 10).and_then(|end| decode_entity(&tail[..end]).map(|ch| (ch, end))) {
+      Some((ch, end)) => {
+        out.push(ch);
+        rest = &tail[end + 1..];
+      }
+      None => {
+        out.push('&');
+        rest = tail;
+      }
+    }
+  }
+  out.push_str(rest);
+  out
+}
+
+fn tag_name(tag: &str) -> String {
+  tag
+    .trim_start_matches('/')
+    .split(|ch: char| ch.is_whitespace() || ch == '/' || ch == '>')
+    .next()
+    .unwrap_or_default()
+    .to_lowercase()
+}
+
+fn attribute(tag: &str, name: &str) -> Option<String> {
+  let lower = tag.to_ascii_lowercase();
+  let mut search = 0;
+  while let Some(found) = lower[search..].find(name) {
+    let start = search + found;
+    search = start + name.len();
+    let boundary = lower[..start].ends_with(|ch: char| ch.is_whitespace());
+    let rest = lower[search..].trim_start();
+    if !boundary || !rest.starts_with('=') {
+      continue;
+    }
+    let value_start = lower.len() - rest.len() + 1;
+    let value = tag[value_start..].trim_start();
+    let (value, _) = match value.chars().next() {
+      Some(quote @ ('"' | '\'')) => value[1..].split_once(quote).unwrap_or((&value[1..], "")),
+      _ => value.split_once(|ch: char| ch.is_whitespace() || ch == '/').unwrap_or((value, "")),
+    };
+    return Some(decode_entities(value));
+  }
+  None
+}
+
+#[derive(Default)]
+struct MarkdownWriter {
+  out: String,
+  lists: Vec<Option<usize>>,
+  links: Vec<Option<String>>,
+  in_pre: bool,
+}
+
+impl MarkdownWriter {
+  fn at_line_start(&self) -> bool {
+    self.out.is_empty() || self.out.ends_with('\n')
+  }
+
+  fn newline(&mut self) {
+    if !self.at_line_start() {
+      self.out.push('\n');
+    }
+  }
+
+  fn blank_line(&mut self) {
+    self.newline();
+    if !self.out.is_empty() && !self.out.ends_with("\n\n") {
+      self.out.push('\n');
+    }
+  }
+
+  fn text(&mut self, raw: &str) {
+    let decoded = decode_entities(raw);
+    if self.in_pre {
+      self.out.push_str(&decoded);
+      return;
+    }
+    let mut collapsed = String::with_capacity(decoded.len());
+    for ch in decoded.chars() {
+      if ch.is_whitespace() {
+        if !collapsed.ends_with(' ') {
+          collapsed.push(' ');
+        }
+      } else {
+        collapsed.push(ch);
+      }
+    }
+    if self.at_line_start() || self.out.ends_with(' ') {
+      collapsed = collapsed.trim_start().to_string();
+    }
+    self.out.push_str(&collapsed);
+  }
+
+  fn checkbox(&mut self, checked: bool) {
+    let line_start = self.out.rfind('\n').map_or(0, |index| index + 1);
+    if self.out[line_start..].trim() == "-" {
+      let marker = self.out[line_start..].find('-').unwrap_or_default();
+      self.out.truncate(line_start + marker);
+    } else {
+      self.newline();
+    }
+    self.out.push_str(if checked { "- [x] " } else { "- [ ] " });
+  }
+
+  fn tag(&mut self, tag: &str) {
+    let name = tag_name(tag);
+    let closing = tag.starts_with('/');
+    match (name.as_str(), closing) {
+      ("br", _) => self.out.push('\n'),
+      ("p" | "section" | "article" | "header" | "footer" | "table", _) => self.blank_line(),
+      ("div" | "tr", _) => self.newline(),
+      ("td" | "th", false) if !self.at_line_start() => self.out.push_str(" | "),
+      ("blockquote", false) => {
+        self.blank_line();
+        self.out.push_str("> ");
+      }
+      ("blockquote", true) => self.blank_line(),
+      ("h1" | "h2" | "h3" | "h4" | "h5" | "h6", false) => {
+        self.blank_line();
+        let level = name[1..].parse::<usize>().unwrap_or(1);
+        self.out.push_str(&"#".repeat(level));
+        self.out.push(' ');
+      }
+      ("h1" | "h2" | "h3" | "h4" | "h5" | "h6", true) => self.blank_line(),
+      ("ul", false) => {
+        self.newline();
+        self.lists.push(None);
+      }
+      ("ol", false) => {
+        self.newline();
+        self.lists.push(Some(0));
+      }
+      ("ul" | "ol", true) => {
+        self.lists.pop();
+        if self.lists.is_empty() {
+          self.blank_line();
+        }
+      }
+      ("li", false) => {
+        self.newline();
+        let depth = self.lists.len().saturating_sub(1);
+        self.out.push_str(&"  ".repeat(depth));
+        match self.lists.last_mut() {
+          Some(Some(counter)) => {
+            *counter += 1;
+            let marker = format!("{counter}. ");
+            self.out.push_str(&marker);
+          }
+          _ => self.out.push_str("- "),
+        }
+      }
+      ("li", true) => self.newline(),
+      ("b" | "strong", _) => self.out.push_str("**"),
+      ("i" | "em", _) => self.out.push('_'),
+      ("s" | "strike" | "del", _) => self.out.push_str("~~"),
+      ("code", _) if !self.in_pre => self.out.push('`'),
+      ("pre", false) => {
+        self.blank_line();
+        self.out.push_str("```\n");
+        self.in_pre = true;
+      }
+      ("pre", true) => {
+        self.in_pre = false;
+        self.newline();
+        self.out.push_str("```");
+        self.blank_line();
+      }
+      ("hr", _) => {
+        self.blank_line();
+        self.out.push_str("---");
+        self.blank_line();
+      }
+      ("a", false) => {
+        let href = attribute(tag, "href").filter(|href| !href.is_empty() && !href.starts_with('#'));
+        if href.is_some() {
+          self.out.push('[');
+        }
+        self.links.push(href);
+      }
+      ("a", true) => {
+        if let Some(Some(href)) = self.links.pop() {
+          self.out.push_str(&format!("]({href})"));
+        }
+      }
+      ("img", _) => {
+        let source = attribute(tag, "src").unwrap_or_default();
+        if !source.is_empty() && !source.starts_with("data:") {
+          let alt = attribute(tag, "alt").unwrap_or_default();
+          self.out.push_str(&format!("![{alt}]({source})"));
+        }
+      }
+      ("en-todo", false) => {
+        let checked = attribute(tag, "checked").is_some_and(|value| value == "true");
+        self.checkbox(checked);
+      }
+      ("input", false) if attribute(tag, "type").is_some_and(|kind| kind.eq_ignore_ascii_case("checkbox")) => {
+        self.checkbox(attribute(tag, "checked").is_some());
+      }
+      _ => {}
+    }
+  }
+
+  fn finish(self) -> String {
+    let mut lines: Vec<&str> = Vec::new();
+    let mut in_fence = false;
+    for line in self.out.lines() {
+      if line.trim_start().starts_with("```") {
+        in_fence = !in_fence;
+      }
+      let line = if in_fence { line } else { line.trim_end() };
+      if !in_fence && line.trim().is_empty() && lines.last().is_none_or(|last| last.trim().is_empty()) {
+        continue;
+      }
+      lines.push(line);
+    }
+    lines.join("\n").trim().to_string()
+  }
+}
+
+pub(super) fn to_markdown(html: &str) -> String {
+  let mut writer = MarkdownWriter::default();
+  let mut rest = html;
+  let mut skip_until: Option<String> = None;
+
+  while let Some(start) = rest.find('<') {
+    if skip_until.is_none() {
+      writer.text(&rest[..start]);
+    }
+    if rest[start..].starts_with("<!--") {
+      rest = rest[start..].split_once("-->").map(|(_, tail)| tail).unwrap_or("");
+      continue;
+    }
+    let Some(end) = rest[start..].find('>') else {
+      rest = "";
+      break;
+    };
+    let tag = &rest[start + 1..start + end];
+    rest = &rest[start + end + 1..];
+    let name = tag_name(tag);
+
+    if let Some(waiting) = &skip_until {
+      if tag.starts_with('/') && name == *waiting {
+        skip_until = None;
+      }
+      continue;
+    }
+    if matches!(name.as_str(), "script" | "style" | "head" | "title") && !tag.starts_with('/') && !tag.ends_with('/') {
+      skip_until = Some(name);
+      continue;
+    }
+    if tag.starts_with('!') || tag.starts_with('?') {
+      continue;
+    }
+    writer.tag(tag);
+  }
+  if skip_until.is_none() {
+    writer.text(rest);
+  }
+  writer.finish()
+}
+
+pub(super) fn split_checklist(markdown: &str) -> (String, Vec<StepState>) {
+  let mut body = Vec::new();
+  let mut steps = Vec::new();
+  for line in markdown.lines() {
+    let trimmed = line.trim_start();
+    let level: usize = line[..line.len() - trimmed.len()].chars().map(|ch| if ch == '\t' { 4 } else { 1 }).sum();
+    let item = trimmed
+      .strip_prefix("- [ ] ")
+      .or_else(|| trimmed.strip_prefix("* [ ] "))
+      .map(|text| (text, false))
+      .or_else(|| {
+        ["- [x] ", "- [X] ", "* [x] ", "* [X] "]
+          .iter()
+          .find_map(|prefix| trimmed.strip_prefix(prefix))
+          .map(|text| (text, true))
+      });
+    match item {
+      Some((text, done)) if !text.trim().is_empty() => steps.push((level, super::step(text, done))),
+      Some(_) => {}
+      None => body.push(line),
+    }
+  }
+  (body.join("\n").trim().to_string(), super::nest_steps(steps))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn attribute_offsets_survive_non_ascii_values() {
+    let tag = r#"a title="İstanbul ẞtraße" HREF='https://example.com/x' data-x=1"#;
+    assert_eq!(attribute(tag, "href").as_deref(), Some("https://example.com/x"));
+    assert_eq!(attribute(tag, "title").as_deref(), Some("İstanbul ẞtraße"));
+    assert_eq!(attribute(tag, "data-x").as_deref(), Some("1"));
+    assert_eq!(attribute(r#"img data-src="a" src=b.png/"#, "src").as_deref(), Some("b.png"));
+    let trailing = r#"a href="https://example.com/y" title="İİİ ẞ""#;
+    assert_eq!(attribute(trailing, "href").as_deref(), Some("https://example.com/y"));
+    assert_eq!(attribute(trailing, "title").as_deref(), Some("İİİ ẞ"));
+    assert_eq!(attribute(r#"a title="İİİ""#, "href"), None);
+  }
+
+  #[test]
+  fn converts_html_to_markdown() {
+    let html = "<html><head><title>x</title></head><body><h2>Plan &amp; notes</h2>\
+      <p>See <a href=\"https://example.com\">site</a><br>next</p>\
+      <ul><li>one</li><li>two<ol><li>nested</li></ol></li></ul>\
+      <script>alert(1)</script><!-- hidden --><p>&#1055;&#x440;&laquo;&raquo;</p></body></html>";
+    let markdown = to_markdown(html);
+    assert!(markdown.contains("Plan & notes"), "{markdown}");
+    assert!(markdown.contains("[site](https://example.com)"), "{markdown}");
+    assert!(markdown.contains("- one") && markdown.contains("nested"), "{markdown}");
+    assert!(markdown.contains("Пр«»"), "{markdown}");
+    assert!(!markdown.contains("alert") && !markdown.contains("hidden"), "{markdown}");
+  }
+
+  #[test]
+  fn tolerates_malformed_html() {
+    assert_eq!(to_markdown("plain <b>bold"), "plain **bold");
+    assert_eq!(to_markdown("broken <a href=\"x"), "broken");
+    assert_eq!(decode_entities("&bogus; &amp &#xZZ; &lt;"), "&bogus; &amp &#xZZ; <");
+  }
+
+  #[test]
+  fn splits_checklists_into_nested_steps() {
+    let (body, steps) = split_checklist("Intro\n- [ ] first\n  - [x] child\n- [X] second\n- [ ] ");
+    assert_eq!(body, "Intro");
+    assert_eq!(steps.len(), 2);
+    assert_eq!((steps[0].text.as_str(), steps[0].done), ("first", false));
+    assert_eq!((steps[0].children[0].text.as_str(), steps[0].children[0].done), ("child", true));
+    assert!(steps[1].done);
+  }
+}
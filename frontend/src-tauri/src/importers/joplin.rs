@@ -0,0 +1,199 @@
+use super::{ImportReport, Unmapped, files_with_extension, group_step, html, note, project, step};
+use crate::tags::clean_tags;
+use crate::{NoteState, ProjectState, StepState};
+use std::collections::{BTreeMap, HashMap};
+
+const TYPE_NOTE: &str = "1";
+const TYPE_FOLDER: &str = "2";
+const TYPE_RESOURCE: &str = "4";
+const TYPE_TAG: &str = "5";
+const TYPE_NOTE_TAG: &str = "6";
+const UNFILED_PROJECT: &str = "Joplin";
+
+struct Item {
+  title: String,
+  body: String,
+  meta: HashMap<String, String>,
+}
+
+impl Item {
+  fn meta(&self, key: &str) -> &str {
+    self.meta.get(key).map(String::as_str).unwrap_or_default()
+  }
+}
+
+fn is_meta_line(line: &str) -> bool {
+  line.split_once(": ").or_else(|| line.strip_suffix(':').map(|key| (key, ""))).is_some_and(|(key, _)| {
+    !key.is_empty() && key.chars().all(|ch| ch.is_ascii_lowercase() || ch.is_ascii_digit() || ch == '_')
+  })
+}
+
+fn parse_item(content: &str) -> Option<Item> {
+  let lines: Vec<&str> = content.lines().collect();
+  let mut meta_start = lines.len();
+  while meta_start > 0 && is_meta_line(lines[meta_start - 1]) {
+    meta_start -= 1;
+  }
+  let meta: HashMap<String, String> = lines[meta_start..]
+    .iter()
+    .filter_map(|line| line.split_once(':'))
+    .map(|(key, value)| (key.to_string(), value.trim().to_string()))
+    .collect();
+  if !meta.contains_key("id") || !meta.contains_key("type_") {
+    return None;
+  }
+
+  let text = &lines[..meta_start];
+  let title = text.first().copied().unwrap_or_default().trim().to_string();
+  let body = text.get(1..).unwrap_or_default().join("\n").trim().to_string();
+  Some(Item { title, body, meta })
+}
+
+fn folder_path(folders: &HashMap<String, Item>, id: &str) -> Option<String> {
+  let mut names = Vec::new();
+  let mut current = folders.get(id);
+  while let Some(folder) = current {
+    names.push(folder.title.clone());
+    if names.len() > 32 {
+      break;
+    }
+    current = folders.get(folder.meta("parent_id"));
+  }
+  if names.is_empty() {
+    return None;
+  }
+  names.reverse();
+  Some(names.join(" / "))
+}
+
+pub(super) fn import(files: &BTreeMap<String, String>) -> Result<ImportReport, String> {
+  let mut notes = Vec::new();
+  let mut folders = HashMap::new();
+  let mut tag_titles = HashMap::new();
+  let mut note_tags: Vec<(String, String)> = Vec::new();
+  let mut unmapped = Unmapped::default();
+  for (_, content) in files_with_extension(files, "md") {
+    let Some(item) = parse_item(content) else {
+      continue;
+    };
+    match item.meta("type_") {
+      TYPE_NOTE => notes.push(item),
+      TYPE_FOLDER => {
+        folders.insert(item.meta("id").to_string(), item);
+      }
+      TYPE_RESOURCE => unmapped.add("attachments"),
+      TYPE_TAG => {
+        tag_titles.insert(item.meta("id").to_string(), item.title);
+      }
+      TYPE_NOTE_TAG => note_tags.push((item.meta("note_id").to_string(), item.meta("tag_id").to_string())),
+      other => unmapped.add(&format!("item type {other}")),
+    }
+  }
+  if notes.is_empty() && folders.is_empty() {
+    return Err("no Joplin notes found".to_string());
+  }
+  notes.sort_by_key(|item| item.meta("order").parse::<i64>().unwrap_or(0).saturating_neg());
+  let mut tags: HashMap<&str, Vec<&str>> = HashMap::new();
+  for (note_id, tag_id) in &note_tags {
+    if let Some(title) = tag_titles.get(tag_id) {
+      tags.entry(note_id.as_str()).or_default().push(title.as_str());
+    }
+  }
+
+  let mut projects: Vec<ProjectState> = Vec::new();
+  let mut folder_ids: Vec<&str> = folders.keys().map(String::as_str).collect();
+  folder_ids.sort_by_key(|id| folder_path(&folders, id));
+  let mut project_index: HashMap<String, usize> = HashMap::new();
+  for id in folder_ids {
+    let name = folder_path(&folders, id).unwrap_or_default();
+    project_index.insert(id.to_string(), projects.len());
+    projects.push(project(&name, ""));
+  }
+
+  for item in notes {
+    let index = match project_index.get(item.meta("parent_id")) {
+      Some(index) => *index,
+      None => *project_index.entry(String::new()).or_insert_with(|| {
+        projects.push(project(UNFILED_PROJECT, ""));
+        projects.len() - 1
+      }),
+    };
+    let target = &mut projects[index];
+    let note_tags = clean_tags(tags.get(item.meta("id")).into_iter().flatten().copied());
+    let markup = if item.meta("markup_language") == "2" { html::to_markdown(&item.body) } else { item.body.clone() };
+    let (body, steps) = html::split_checklist(&markup);
+    if item.meta("is_todo") == "1" {
+      let todo = step(&item.title, !matches!(item.meta("todo_completed"), "" | "0"));
+      target.steps.push(StepState { children: steps, tags: note_tags.clone(), ..todo });
+      unmapped.add_if(!matches!(item.meta("todo_due"), "" | "0"), "todo_due");
+    } else if !steps.is_empty() {
+      target.steps.push(group_step(&item.title, steps));
+    }
+    if !body.is_empty() || item.meta("is_todo") != "1" {
+      target.notes.push(NoteState { tags: note_tags, ..note(&item.title, &body) });
+    }
+    unmapped.add_if(body.contains("](:/"), "attachment links");
+    unmapped.add_if(!item.meta("source_url").is_empty(), "source_url");
+    unmapped.add_if(item.meta("is_conflict") == "1", "conflict notes");
+  }
+  unmapped.add_if(
+    folders.values().any(|folder| !folder.meta("icon").is_empty()),
+    "folder icons",
+  );
+
+  Ok(unmapped.into_report(projects))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn files(entries: &[(&str, &str)]) -> BTreeMap<String, String> {
+    entries.iter().map(|(name, content)| (name.to_string(), content.to_string())).collect()
+  }
+
+  fn export() -> BTreeMap<String, String> {
+    files(&[
+      ("f1.md", "Work\n\nid: f1\nparent_id: \ntype_: 2"),
+      ("f2.md", "Clients\n\nid: f2\nparent_id: f1\ntype_: 2"),
+      (
+        "n1.md",
+        "Call ACME\n\nDiscuss contract\n- [x] send draft\n- [ ] get signature\n![img](:/abc)\n\nid: n1\nparent_id: f2\nis_todo: 1\ntodo_completed: 1600000\ntype_: 1",
+      ),
+      ("n2.md", "Loose\n\ntext\nkey: looks like meta but is body\n\nid: n2\nparent_id: \nis_todo: 0\ntype_: 1"),
+      ("r1.md", "image.png\n\nid: r1\ntype_: 4"),
+      ("resources/r1.png", "binary"),
+      ("t1.md", "Срочно\n\nid: t1\ntype_: 5"),
+      ("t2.md", "client\n\nid: t2\ntype_: 5"),
+      ("nt1.md", "\n\nid: nt1\nnote_id: n1\ntag_id: t1\ntype_: 6"),
+      ("nt2.md", "\n\nid: nt2\nnote_id: n1\ntag_id: t2\ntype_: 6"),
+      ("nt3.md", "\n\nid: nt3\nnote_id: n2\ntag_id: missing\ntype_: 6"),
+    ])
+  }
+
+  #[test]
+  fn builds_projects_from_the_folder_tree() {
+    let report = serde_json::to_value(import(&export()).unwrap()).unwrap();
+    let names: Vec<_> = report["projects"].as_array().unwrap().iter().map(|project| project["name"].as_str().unwrap()).collect();
+    assert_eq!(names, ["Work", "Work / Clients", "Joplin"]);
+    let clients = &report["projects"][1];
+    assert_eq!(clients["steps"][0]["text"], "Call ACME");
+    assert_eq!(clients["steps"][0]["done"], true);
+    assert_eq!(clients["steps"][0]["children"][1]["text"], "get signature");
+    assert!(clients["notes"][0]["body"].as_str().unwrap().starts_with("Discuss contract"));
+    assert_eq!(clients["notes"][0]["tags"], serde_json::json!(["Срочно", "client"]));
+    assert_eq!(clients["steps"][0]["tags"], serde_json::json!(["Срочно", "client"]));
+    assert_eq!(report["projects"][2]["notes"][0]["tags"], serde_json::json!([]));
+    assert_eq!(report["projects"][2]["notes"][0]["body"], "text\nkey: looks like meta but is body");
+    assert_eq!(
+      report["unmapped"],
+      serde_json::json!([{ "field": "attachment links", "count": 1 }, { "field": "attachments", "count": 1 }])
+    );
+  }
+
+  #[test]
+  fn rejects_selections_without_joplin_items() {
+    assert!(import(&files(&[("readme.md", "# Hello\n\nno metadata here")])).is_err());
+    assert!(import(&files(&[("n1.txt", "Note\n\nid: n1\ntype_: 1")])).is_err());
+  }
+}
@@ -0,0 +1,152 @@
+use super::{ImportReport, Unmapped, files_with_extension, group_step, note, project, step};
+use crate::tags::clean_tags;
+use crate::{NoteState, ProjectState, StepState};
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+const UNLABELED_PROJECT: &str = "Google Keep";
+
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct KeepNote {
+  title: String,
+  text_content: String,
+  list_content: Vec<KeepListItem>,
+  labels: Vec<KeepLabel>,
+  annotations: Vec<KeepAnnotation>,
+  attachments: Vec<Value>,
+  sharees: Vec<Value>,
+  color: String,
+  is_trashed: bool,
+  is_pinned: bool,
+  is_archived: bool,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct KeepListItem {
+  text: String,
+  is_checked: bool,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct KeepLabel {
+  name: String,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct KeepAnnotation {
+  title: String,
+  url: String,
+}
+
+fn read_notes(files: &BTreeMap<String, String>) -> Result<Vec<KeepNote>, String> {
+  let mut notes = Vec::new();
+  for (_, content) in files_with_extension(files, "json") {
+    if let Ok(note) = serde_json::from_str::<KeepNote>(content)
+      && (!note.text_content.is_empty() || !note.list_content.is_empty() || !note.title.is_empty())
+    {
+      notes.push(note);
+    }
+  }
+  if notes.is_empty() {
+    return Err("no Google Keep notes found".to_string());
+  }
+  Ok(notes)
+}
+
+pub(super) fn import(files: &BTreeMap<String, String>) -> Result<ImportReport, String> {
+  let mut unmapped = Unmapped::default();
+  let mut projects: Vec<ProjectState> = Vec::new();
+
+  for keep in read_notes(files)? {
+    if keep.is_trashed {
+      unmapped.add("trashed notes");
+      continue;
+    }
+    let label = keep
+      .labels
+      .first()
+      .map(|label| label.name.trim())
+      .filter(|name| !name.is_empty())
+      .unwrap_or(UNLABELED_PROJECT);
+    let index = match projects.iter().position(|project| project.name == label) {
+      Some(index) => index,
+      None => {
+        projects.push(project(label, ""));
+        projects.len() - 1
+      }
+    };
+    let target = &mut projects[index];
+
+    let title = if keep.title.trim().is_empty() {
+      keep.text_content.lines().next().unwrap_or("Google Keep").chars().take(60).collect::<String>()
+    } else {
+      keep.title.clone()
+    };
+    let mut body = keep.text_content.trim().to_string();
+    for annotation in keep.annotations.iter().filter(|annotation| !annotation.url.is_empty()) {
+      let caption = if annotation.title.trim().is_empty() { &annotation.url } else { &annotation.title };
+      body.push_str(&format!("\n\n[{}]({})", caption.trim(), annotation.url));
+    }
+    let tags = clean_tags(keep.labels.iter().skip(1).map(|label| label.name.as_str()));
+    if !keep.list_content.is_empty() {
+      let items = keep.list_content.iter().map(|item| step(&item.text, item.is_checked)).collect();
+      target.steps.push(StepState { tags: tags.clone(), ..group_step(&title, items) });
+    }
+    if !body.trim().is_empty() || keep.list_content.is_empty() {
+      target.notes.push(NoteState { tags, ..note(&title, &body) });
+    }
+
+    unmapped.add_if(!keep.attachments.is_empty(), "attachments");
+    unmapped.add_if(!keep.sharees.is_empty(), "sharees");
+    unmapped.add_if(keep.is_pinned, "pinned notes");
+    unmapped.add_if(keep.is_archived, "archived notes");
+    unmapped.add_if(!matches!(keep.color.as_str(), "" | "DEFAULT"), "colors");
+  }
+
+  Ok(unmapped.into_report(projects))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn files(entries: &[(&str, &str)]) -> BTreeMap<String, String> {
+    entries.iter().map(|(name, content)| (name.to_string(), content.to_string())).collect()
+  }
+
+  #[test]
+  fn groups_notes_by_first_label() {
+    let report = import(&files(&[
+      ("Keep/b.json", r#"{"title":"Список","listContent":[{"text":"молоко","isChecked":true},{"text":"хлеб"}],"labels":[{"name":"Дом"},{"name":"Покупки"}]}"#),
+      ("Keep/a.json", r#"{"title":"","textContent":"First line\nmore","annotations":[{"title":"Site","url":"https://example.com"}],"isPinned":true}"#),
+      ("Keep/c.json", r#"{"title":"Gone","textContent":"x","isTrashed":true}"#),
+      ("Keep/labels.txt", "Дом"),
+    ]))
+    .unwrap();
+    let report = serde_json::to_value(report).unwrap();
+    let projects = report["projects"].as_array().unwrap();
+    assert_eq!(projects[0]["name"], "Google Keep");
+    assert_eq!(projects[0]["notes"][0]["title"], "First line");
+    assert_eq!(projects[0]["notes"][0]["body"], "First line\nmore\n\n[Site](https://example.com)");
+    assert_eq!(projects[1]["name"], "Дом");
+    assert_eq!(projects[1]["steps"][0]["text"], "Список");
+    assert_eq!(projects[1]["steps"][0]["tags"], serde_json::json!(["Покупки"]));
+    assert_eq!(projects[1]["steps"][0]["children"][0]["done"], true);
+    assert!(projects[1]["notes"].as_array().unwrap().is_empty());
+    assert_eq!(
+      report["unmapped"],
+      serde_json::json!([{ "field": "pinned notes", "count": 1 }, { "field": "trashed notes", "count": 1 }])
+    );
+  }
+
+  #[test]
+  fn rejects_selections_without_notes() {
+    assert!(import(&files(&[("a.json", "not json"), ("b.json", "{}"), ("c.html", "<p>x</p>")])).is_err());
+    assert!(import(&BTreeMap::new()).is_err());
+  }
+}
@@ -0,0 +1,266 @@
+use crate::time_zone::Zone;
+use crate::{NoteState, ProjectState, StepState};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+mod enex;
+mod html;
+mod joplin;
+mod keep;
+mod ms_todo;
+mod todoist;
+mod trello;
+
+const LOCAL_TIME_FORMAT: &str = "%Y-%m-%dT%H:%M";
+const WEEKDAYS: [(&str, &str); 7] = [
+  ("monday", "MO"),
+  ("tuesday", "TU"),
+  ("wednesday", "WE"),
+  ("thursday", "TH"),
+  ("friday", "FR"),
+  ("saturday", "SA"),
+  ("sunday", "SU"),
+];
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum TaskImportSource {
+  Todoist,
+  Trello,
+  MicrosoftTodo,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum NoteImportSource {
+  GoogleKeep,
+  Evernote,
+  Joplin,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct UnmappedField {
+  field: String,
+  count: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ImportReport {
+  projects: Vec<ProjectState>,
+  unmapped: Vec<UnmappedField>,
+}
+
+#[derive(Default)]
+pub(crate) struct Unmapped(BTreeMap<String, usize>);
+
+impl Unmapped {
+  pub(crate) fn add(&mut self, field: &str) {
+    *self.0.entry(field.to_string()).or_default() += 1;
+  }
+
+  pub(crate) fn add_if(&mut self, present: bool, field: &str) {
+    if present {
+      self.add(field);
+    }
+  }
+
+  pub(crate) fn into_report(self, projects: Vec<ProjectState>) -> ImportReport {
+    let unmapped = self.0.into_iter().map(|(field, count)| UnmappedField { field, count }).collect();
+    ImportReport { projects, unmapped }
+  }
+}
+
+pub(crate) fn project(name: &str, description: &str) -> ProjectState {
+  ProjectState {
+    name: name.trim().to_string(),
+    description: description.trim().to_string(),
+    ..Default::default()
+  }
+}
+
+pub(crate) fn note(title: &str, body: &str) -> NoteState {
+  NoteState {
+    title: title.trim().to_string(),
+    body: body.trim().to_string(),
+    ..Default::default()
+  }
+}
+
+pub(crate) fn step(text: &str, done: bool) -> StepState {
+  StepState {
+    text: text.trim().to_string(),
+    done,
+    ..Default::default()
+  }
+}
+
+pub(crate) fn group_step(text: &str, children: Vec<StepState>) -> StepState {
+  let done = !children.is_empty() && children.iter().all(|child| child.done);
+  StepState { children, ..step(text, done) }
+}
+
+pub(crate) fn nest_steps(items: Vec<(usize, StepState)>) -> Vec<StepState> {
+  let mut roots = Vec::new();
+  let mut open: Vec<(usize, StepState)> = Vec::new();
+  let close = |open: &mut Vec<(usize, StepState)>, roots: &mut Vec<StepState>| {
+    let (_, finished) = open.pop().expect("only called with open steps");
+    match open.last_mut() {
+      Some((_, parent)) => parent.children.push(finished),
+      None => roots.push(finished),
+    }
+  };
+  for (level, item) in items {
+    while open.last().is_some_and(|(open_level, _)| *open_level >= level) {
+      close(&mut open, &mut roots);
+    }
+    open.push((level, item));
+  }
+  while !open.is_empty() {
+    close(&mut open, &mut roots);
+  }
+  roots
+}
+
+pub(crate) fn format_local(value: &DateTime<Utc>, zone: &Zone) -> String {
+  zone.local(value).format(LOCAL_TIME_FORMAT).to_string()
+}
+
+pub(crate) fn format_wall_clock(value: &NaiveDateTime) -> String {
+  value.format(LOCAL_TIME_FORMAT).to_string()
+}
+
+pub(crate) fn local_time(value: &str, zone: &Zone) -> Option<String> {
+  let value = value.trim();
+  if NaiveDate::parse_from_str(value, "%Y-%m-%d").is_ok() {
+    return Some(value.to_string());
+  }
+  if let Ok(parsed) = DateTime::parse_from_rfc3339(value) {
+    return Some(format_local(&parsed.with_timezone(&Utc), zone));
+  }
+  ["%Y-%m-%dT%H:%M:%S%.f", "%Y-%m-%dT%H:%M", "%Y-%m-%d %H:%M:%S", "%Y-%m-%d %H:%M"]
+    .iter()
+    .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+    .map(|value| format_wall_clock(&value))
+}
+
+pub(crate) fn weekday_code(name: &str) -> Option<&'static str> {
+  let name = name.trim().to_lowercase();
+  let name = name.trim_end_matches('s');
+  WEEKDAYS
+    .iter()
+    .find(|(full, _)| name.len() >= 3 && full.starts_with(name))
+    .map(|(_, code)| *code)
+}
+
+fn files_with_extension<'a>(
+  files: &'a BTreeMap<String, String>,
+  extension: &'a str,
+) -> impl Iterator<Item = (&'a str, &'a str)> + 'a {
+  files
+    .iter()
+    .filter(move |(name, _)| Path::new(name).extension().is_some_and(|ext| ext.eq_ignore_ascii_case(extension)))
+    .map(|(name, content)| (name.as_str(), content.trim_start_matches('\u{feff}')))
+}
+
+fn name_from_file(file_name: &str, fallback: &str) -> String {
+  let stem = std::path::Path::new(file_name)
+    .file_stem()
+    .and_then(|value| value.to_str())
+    .unwrap_or_default()
+    .trim();
+  if stem.is_empty() { fallback.to_string() } else { stem.to_string() }
+}
+
+#[tauri::command]
+pub(crate) fn import_task_export(
+  source: TaskImportSource,
+  file_name: String,
+  content: String,
+  time_zone: String,
+) -> Result<ImportReport, String> {
+  let content = content.trim_start_matches('\u{feff}');
+  let zone = Zone::from_settings(&time_zone);
+  match source {
+    TaskImportSource::Todoist => todoist::import(&name_from_file(&file_name, "Todoist"), content, &zone),
+    TaskImportSource::Trello => trello::import(content, &zone),
+    TaskImportSource::MicrosoftTodo => ms_todo::import(&name_from_file(&file_name, "Microsoft To Do"), content, &zone),
+  }
+}
+
+#[tauri::command]
+pub(crate) fn import_notes_export(source: NoteImportSource, files: BTreeMap<String, String>) -> Result<ImportReport, String> {
+  if files.is_empty() {
+    return Err("no files selected".to_string());
+  }
+  match source {
+    NoteImportSource::GoogleKeep => keep::import(&files),
+    NoteImportSource::Evernote => enex::import(&files),
+    NoteImportSource::Joplin => joplin::import(&files),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn texts(steps: &[StepState]) -> Vec<String> {
+    steps
+      .iter()
+      .map(|step| match step.children.as_slice() {
+        [] => step.text.clone(),
+        children => format!("{}({})", step.text, texts(children).join(",")),
+      })
+      .collect()
+  }
+
+  #[test]
+  fn nests_steps_by_level() {
+    let items = [(0, "a"), (1, "a1"), (2, "a1x"), (1, "a2"), (0, "b"), (2, "b-deep"), (1, "b1")];
+    let nested = nest_steps(items.into_iter().map(|(level, text)| (level, step(text, false))).collect());
+    assert_eq!(texts(&nested), ["a(a1(a1x),a2)", "b(b-deep,b1)"]);
+    assert!(nest_steps(Vec::new()).is_empty());
+    assert!(group_step("g", vec![step("x", true), step("y", true)]).done);
+    assert!(!group_step("g", Vec::new()).done);
+  }
+
+  #[test]
+  fn converts_times_into_the_local_zone() {
+    let berlin = Zone::from_settings("Europe/Berlin");
+    assert_eq!(local_time(" 2026-10-20 ", &berlin).as_deref(), Some("2026-10-20"));
+    assert_eq!(local_time("2026-10-20T09:00:00Z", &berlin).as_deref(), Some("2026-10-20T11:00"));
+    assert_eq!(local_time("2026-10-20T09:00:00.0000000", &berlin).as_deref(), Some("2026-10-20T09:00"));
+    assert_eq!(local_time("2026-10-20 09:05", &berlin).as_deref(), Some("2026-10-20T09:05"));
+    assert_eq!(local_time("tomorrow", &berlin), None);
+    assert_eq!(local_time("2026-02-30", &berlin), None);
+  }
+
+  #[test]
+  fn reads_weekday_names() {
+    assert_eq!(weekday_code("Monday"), Some("MO"));
+    assert_eq!(weekday_code(" thurs "), Some("TH"));
+    assert_eq!(weekday_code("sundays"), Some("SU"));
+    assert_eq!(weekday_code("mo"), None);
+    assert_eq!(weekday_code("weekday"), None);
+  }
+
+  #[test]
+  fn picks_files_by_extension_and_strips_the_bom() {
+    let files = BTreeMap::from([
+      ("a.JSON".to_string(), "\u{feff}{}".to_string()),
+      ("b.md".to_string(), "x".to_string()),
+      ("json".to_string(), "y".to_string()),
+    ]);
+    assert_eq!(files_with_extension(&files, "json").collect::<Vec<_>>(), [("a.JSON", "{}")]);
+    assert_eq!(name_from_file("dir/My list.csv", "Todoist"), "My list");
+    assert_eq!(name_from_file(" .csv", "Todoist"), "Todoist");
+  }
+
+  #[test]
+  fn rejects_empty_note_imports() {
+    assert!(import_notes_export(NoteImportSource::Joplin, BTreeMap::new()).is_err_and(|err| err == "no files selected"));
+  }
+}
@@ -0,0 +1,283 @@
+use super::{ImportReport, Unmapped, format_local, format_wall_clock, html, note, project, step, weekday_code};
+use crate::recurrence;
+use crate::tags::clean_tags;
+use crate::time_zone::Zone;
+use chrono::{NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use serde::Deserialize;
+use serde_json::Value;
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Export {
+  Lists { lists: Vec<TaskList> },
+  Graph { value: Vec<TaskList> },
+  Single(TaskList),
+  Many(Vec<TaskList>),
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct TaskList {
+  display_name: String,
+  tasks: Vec<Task>,
+  is_shared: bool,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct Task {
+  title: String,
+  status: String,
+  importance: String,
+  body: Option<Body>,
+  checklist_items: Vec<ChecklistItem>,
+  due_date_time: Option<Value>,
+  reminder_date_time: Option<Value>,
+  recurrence: Option<Value>,
+  categories: Vec<String>,
+  attachments: Vec<Value>,
+  linked_resources: Vec<Value>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct Body {
+  content: String,
+  content_type: String,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct ChecklistItem {
+  display_name: String,
+  is_checked: bool,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct DateTimeZone {
+  date_time: String,
+  time_zone: String,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct Recurrence {
+  pattern: Pattern,
+  range: Range,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct Pattern {
+  #[serde(rename = "type")]
+  kind: String,
+  interval: u32,
+  days_of_week: Vec<String>,
+  day_of_month: u32,
+  index: String,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct Range {
+  #[serde(rename = "type")]
+  kind: String,
+  end_date: String,
+  number_of_occurrences: u32,
+}
+
+fn recurrence_rule(value: Option<&Value>) -> Option<String> {
+  let Recurrence { pattern, range } = serde_json::from_value(value?.clone()).ok()?;
+  let days = pattern.days_of_week.iter().map(|day| weekday_code(day)).collect::<Option<Vec<_>>>()?;
+  let mut parts = match pattern.kind.as_str() {
+    "daily" => vec!["FREQ=DAILY".to_string()],
+    "weekly" if days.is_empty() => vec!["FREQ=WEEKLY".to_string()],
+    "weekly" => vec!["FREQ=WEEKLY".to_string(), format!("BYDAY={}", days.join(","))],
+    "absoluteMonthly" => vec!["FREQ=MONTHLY".to_string(), format!("BYMONTHDAY={}", pattern.day_of_month)],
+    "relativeMonthly" => {
+      let ordinal = match pattern.index.as_str() {
+        "first" | "" => "1",
+        "second" => "2",
+        "third" => "3",
+        "fourth" => "4",
+        "last" => "-1",
+        _ => return None,
+      };
+      let days: Vec<_> = days.iter().map(|day| format!("{ordinal}{day}")).collect();
+      vec!["FREQ=MONTHLY".to_string(), format!("BYDAY={}", days.join(","))]
+    }
+    "absoluteYearly" => vec!["FREQ=YEARLY".to_string()],
+    _ => return None,
+  };
+  if pattern.interval > 1 {
+    parts.push(format!("INTERVAL={}", pattern.interval));
+  }
+  match range.kind.as_str() {
+    "endDate" => parts.push(format!("UNTIL={}", range.end_date.replace('-', ""))),
+    "numbered" if range.number_of_occurrences > 0 => parts.push(format!("COUNT={}", range.number_of_occurrences)),
+    _ => {}
+  }
+  let rule = parts.join(";");
+  recurrence::is_supported(&rule).then_some(rule)
+}
+
+fn graph_time(value: Option<&Value>) -> Option<(NaiveDateTime, String)> {
+  let value: DateTimeZone = serde_json::from_value(value?.clone()).ok()?;
+  let at = NaiveDateTime::parse_from_str(value.date_time.trim(), "%Y-%m-%dT%H:%M:%S%.f").ok()?;
+  Some((at, value.time_zone.trim().to_string()))
+}
+
+fn due_date(value: Option<&Value>) -> Option<String> {
+  graph_time(value).map(|(at, _)| at.date().format("%Y-%m-%d").to_string())
+}
+
+fn reminder_time(value: Option<&Value>, zone: &Zone) -> Option<String> {
+  let (at, time_zone) = graph_time(value)?;
+  let source = if time_zone.is_empty() || time_zone.eq_ignore_ascii_case("utc") { Some(Tz::UTC) } else { time_zone.parse::<Tz>().ok() };
+  match source.and_then(|source| source.from_local_datetime(&at).earliest()) {
+    Some(instant) => Some(format_local(&instant.with_timezone(&Utc), zone)),
+    None => Some(format_wall_clock(&at)),
+  }
+}
+
+pub(super) fn import(name: &str, content: &str, zone: &Zone) -> Result<ImportReport, String> {
+  let export: Export = serde_json::from_str(content).map_err(|_| "not a Microsoft To Do export".to_string())?;
+  let lists = match export {
+    Export::Lists { lists } | Export::Graph { value: lists } | Export::Many(lists) => lists,
+    Export::Single(list) => vec![list],
+  };
+  if lists.iter().all(|list| list.tasks.is_empty() && list.display_name.is_empty()) {
+    return Err("not a Microsoft To Do export".to_string());
+  }
+
+  let mut unmapped = Unmapped::default();
+  let mut projects = Vec::new();
+  for list in lists {
+    let title = if list.display_name.trim().is_empty() { name } else { list.display_name.as_str() };
+    let mut result = project(title, "");
+    unmapped.add_if(list.is_shared, "shared lists");
+
+    for task in list.tasks {
+      let mut task_step = step(&task.title, task.status == "completed");
+      task_step.due_at = due_date(task.due_date_time.as_ref());
+      task_step.remind_at = reminder_time(task.reminder_date_time.as_ref(), zone);
+      task_step.recurrence = recurrence_rule(task.recurrence.as_ref());
+      unmapped.add_if(task.due_date_time.is_some() && task_step.due_at.is_none(), "dueDateTime");
+      unmapped.add_if(task.reminder_date_time.is_some() && task_step.remind_at.is_none(), "reminderDateTime");
+      unmapped.add_if(task.recurrence.is_some() && task_step.recurrence.is_none(), "recurrence");
+      task_step.tags = clean_tags(task.categories.iter().map(String::as_str));
+      task_step.children = task.checklist_items.iter().map(|item| step(&item.display_name, item.is_checked)).collect();
+      result.steps.push(task_step);
+
+      let body = task
+        .body
+        .map(|body| {
+          if body.content_type.eq_ignore_ascii_case("html") { html::to_markdown(&body.content) } else { body.content }
+        })
+        .unwrap_or_default();
+      if !body.trim().is_empty() {
+        result.notes.push(note(&task.title, &body));
+      }
+
+      unmapped.add_if(task.importance == "high", "importance");
+      unmapped.add_if(!task.attachments.is_empty(), "attachments");
+      unmapped.add_if(!task.linked_resources.is_empty(), "linkedResources");
+    }
+    projects.push(result);
+  }
+
+  Ok(unmapped.into_report(projects))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn imports_lists_bodies_and_checklists() {
+    let export = r#"{"value":[{"displayName":"Groceries","isShared":true,"tasks":[{"title":"Milk","status":"completed","importance":"high",
+      "categories":["Shop"],"body":{"content":"<p>Two &amp; <b>fresh</b></p>","contentType":"html"},
+      "checklistItems":[{"displayName":"skim","isChecked":true}],"attachments":[{}]}]}]}"#;
+    let report = serde_json::to_value(import("x", export, &Zone::from_settings("UTC")).unwrap()).unwrap();
+    let project = &report["projects"][0];
+    assert_eq!(project["name"], "Groceries");
+    assert_eq!(project["steps"][0]["done"], true);
+    assert_eq!(project["steps"][0]["tags"], serde_json::json!(["Shop"]));
+    assert_eq!(project["steps"][0]["children"][0]["text"], "skim");
+    assert_eq!(project["notes"][0]["body"], "Two & **fresh**");
+    assert_eq!(
+      report["unmapped"],
+      serde_json::json!([
+        { "field": "attachments", "count": 1 },
+        { "field": "importance", "count": 1 },
+        { "field": "shared lists", "count": 1 },
+      ])
+    );
+  }
+
+  #[test]
+  fn maps_due_dates_and_reminders() {
+    let export = r#"[{"displayName":"L","tasks":[
+      {"title":"A","dueDateTime":{"dateTime":"2026-10-20T00:00:00.0000000","timeZone":"UTC"},
+        "reminderDateTime":{"dateTime":"2026-10-20T07:00:00.0000000","timeZone":"UTC"}},
+      {"title":"B","reminderDateTime":{"dateTime":"2026-10-20T09:15:00","timeZone":"Pacific Standard Time"}},
+      {"title":"C","reminderDateTime":{"dateTime":"2026-10-20T09:15:00","timeZone":"America/New_York"}},
+      {"title":"D","dueDateTime":"2026-10-20","reminderDateTime":{"dateTime":"soon"}}]}]"#;
+    let report = serde_json::to_value(import("x", export, &Zone::from_settings("Europe/Berlin")).unwrap()).unwrap();
+    let steps = &report["projects"][0]["steps"];
+    assert_eq!((steps[0]["dueAt"].as_str(), steps[0]["remindAt"].as_str()), (Some("2026-10-20"), Some("2026-10-20T09:00")));
+    assert_eq!(steps[1]["remindAt"], "2026-10-20T09:15");
+    assert_eq!(steps[2]["remindAt"], "2026-10-20T15:15");
+    assert!(steps[3]["dueAt"].is_null() && steps[3]["remindAt"].is_null());
+    assert_eq!(
+      report["unmapped"],
+      serde_json::json!([{ "field": "dueDateTime", "count": 1 }, { "field": "reminderDateTime", "count": 1 }])
+    );
+  }
+
+  #[test]
+  fn rejects_files_that_are_not_exports() {
+    let zone = Zone::from_settings("UTC");
+    assert!(import("x", "{}", &zone).is_err());
+    assert!(import("x", "[]", &zone).is_err());
+    assert!(import("x", "not json", &zone).is_err());
+  }
+
+  #[test]
+  fn maps_recurrence_patterns_onto_rules() {
+    let rule = |value: serde_json::Value| recurrence_rule(Some(&value));
+    let cases = [
+      (serde_json::json!({ "pattern": { "type": "daily", "interval": 1 }, "range": { "type": "noEnd" } }), Some("FREQ=DAILY")),
+      (
+        serde_json::json!({ "pattern": { "type": "weekly", "interval": 2, "daysOfWeek": ["monday", "thursday"] } }),
+        Some("FREQ=WEEKLY;BYDAY=MO,TH;INTERVAL=2"),
+      ),
+      (
+        serde_json::json!({ "pattern": { "type": "absoluteMonthly", "interval": 1, "dayOfMonth": 31 },
+          "range": { "type": "endDate", "endDate": "2027-06-30" } }),
+        Some("FREQ=MONTHLY;BYMONTHDAY=31;UNTIL=20270630"),
+      ),
+      (
+        serde_json::json!({ "pattern": { "type": "relativeMonthly", "daysOfWeek": ["friday"], "index": "last" },
+          "range": { "type": "numbered", "numberOfOccurrences": 5 } }),
+        Some("FREQ=MONTHLY;BYDAY=-1FR;COUNT=5"),
+      ),
+      (serde_json::json!({ "pattern": { "type": "absoluteYearly", "interval": 1, "month": 3, "dayOfMonth": 1 } }), Some("FREQ=YEARLY")),
+      (serde_json::json!({ "pattern": { "type": "relativeYearly", "daysOfWeek": ["monday"] } }), None),
+      (serde_json::json!({ "pattern": { "type": "weekly", "daysOfWeek": ["someday"] } }), None),
+      (serde_json::json!({ "pattern": { "type": "daily", "interval": 1000 } }), None),
+      (serde_json::json!("FREQ=DAILY"), None),
+    ];
+    for (value, expected) in cases {
+      assert_eq!(rule(value.clone()).as_deref(), expected, "{value}");
+    }
+
+    let export = r#"[{"displayName":"L","tasks":[{"title":"Bins","recurrence":{"pattern":{"type":"weekly","daysOfWeek":["tuesday"]}}},
+      {"title":"Taxes","recurrence":{"pattern":{"type":"relativeYearly"}}}]}]"#;
+    let report = serde_json::to_value(import("x", export, &Zone::from_settings("UTC")).unwrap()).unwrap();
+    assert_eq!(report["projects"][0]["steps"][0]["recurrence"], "FREQ=WEEKLY;BYDAY=TU");
+    assert_eq!(report["unmapped"], serde_json::json!([{ "field": "recurrence", "count": 1 }]));
+  }
+}
@@ -0,0 +1,221 @@
+use super::{ImportReport, Unmapped, local_time, nest_steps, note, project, step, weekday_code};
+use crate::StepState;
+use crate::recurrence;
+use crate::time_zone::Zone;
+use std::collections::HashMap;
+
+const UNMAPPED_COLUMNS: [&str; 3] = ["AUTHOR", "RESPONSIBLE", "DURATION"];
+
+struct Section {
+  title: String,
+  lines: Vec<String>,
+  has_details: bool,
+}
+
+impl Section {
+  fn new(title: &str) -> Self {
+    Self { title: title.to_string(), lines: Vec::new(), has_details: false }
+  }
+}
+
+fn recurrence_rule(value: &str) -> Option<String> {
+  let value = value.trim().to_lowercase();
+  let rest = value.strip_prefix("every ")?.trim();
+  let (interval, unit) = rest
+    .split_once(' ')
+    .and_then(|(count, unit)| Some((count.parse::<u32>().ok()?, unit.trim())))
+    .unwrap_or((1, rest));
+  let frequency = match unit.trim_end_matches('s') {
+    "day" => "DAILY",
+    "week" => "WEEKLY",
+    "month" => "MONTHLY",
+    "year" => "YEARLY",
+    "weekday" if interval == 1 => return Some("FREQ=WEEKLY;BYDAY=MO,TU,WE,TH,FR".to_string()),
+    _ if interval == 1 => {
+      let days = unit
+        .split([',', '&'])
+        .flat_map(|part| part.split(" and "))
+        .map(weekday_code)
+        .collect::<Option<Vec<_>>>()?;
+      return Some(format!("FREQ=WEEKLY;BYDAY={}", days.join(",")));
+    }
+    _ => return None,
+  };
+  let rule = if interval == 1 { format!("FREQ={frequency}") } else { format!("FREQ={frequency};INTERVAL={interval}") };
+  recurrence::is_supported(&rule).then_some(rule)
+}
+
+pub(super) fn import(name: &str, content: &str, zone: &Zone) -> Result<ImportReport, String> {
+  let mut reader = csv::ReaderBuilder::new()
+    .flexible(true)
+    .from_reader(content.as_bytes());
+  let headers: HashMap<String, usize> = reader
+    .headers()
+    .map_err(|err| err.to_string())?
+    .iter()
+    .enumerate()
+    .map(|(index, header)| (header.trim().to_uppercase(), index))
+    .collect();
+  if !headers.contains_key("TYPE") || !headers.contains_key("CONTENT") {
+    return Err("not a Todoist CSV export".to_string());
+  }
+
+  let mut unmapped = Unmapped::default();
+  let mut result = project(name, "");
+  let mut sections = vec![Section::new("")];
+  let mut tasks: Vec<(usize, StepState)> = Vec::new();
+  let mut last_task: Option<usize> = None;
+
+  for record in reader.records() {
+    let record = record.map_err(|err| err.to_string())?;
+    let get = |column: &str| {
+      headers
+        .get(column)
+        .and_then(|index| record.get(*index))
+        .map(str::trim)
+        .unwrap_or_default()
+    };
+    let content = get("CONTENT");
+
+    match get("TYPE").to_lowercase().as_str() {
+      "task" => {
+        let level = get("INDENT").parse::<usize>().unwrap_or(1).max(1);
+        let mut task = step(content, false);
+        let date = local_time(get("DATE"), zone);
+        let deadline = local_time(get("DEADLINE"), zone);
+        task.recurrence = recurrence_rule(get("DATE"));
+        unmapped.add_if(date.is_none() && task.recurrence.is_none() && !get("DATE").is_empty(), "DATE");
+        unmapped.add_if(deadline.is_none() && !get("DEADLINE").is_empty() || date.is_some() && deadline.is_some(), "DEADLINE");
+        task.due_at = date.or(deadline);
+        tasks.push((level, task));
+        last_task = Some(tasks.len() - 1);
+        let description = get("DESCRIPTION");
+        let section = sections.last_mut().expect("sections always has a root entry");
+        let indent = "  ".repeat(level - 1);
+        if description.is_empty() {
+          section.lines.push(format!("{indent}- {content}"));
+        } else {
+          section.lines.push(format!("{indent}- {content}\n{indent}  {}", description.replace('\n', &format!("\n{indent}  "))));
+          section.has_details = true;
+        }
+        unmapped.add_if(!matches!(get("PRIORITY"), "" | "4"), "PRIORITY");
+        for column in UNMAPPED_COLUMNS {
+          unmapped.add_if(!get(column).is_empty(), column);
+        }
+      }
+      "section" => {
+        sections.push(Section::new(content));
+        last_task = None;
+      }
+      "note" => match last_task {
+        Some(index) => {
+          let task = tasks[index].1.text.clone();
+          let section = sections.last_mut().expect("sections always has a root entry");
+          section.lines.push(format!("  > {task}: {}", content.replace('\n', "\n  > ")));
+          section.has_details = true;
+        }
+        None => {
+          let section = sections.last_mut().expect("sections always has a root entry");
+          section.lines.push(content.to_string());
+          section.has_details = true;
+        }
+      },
+      "meta" | "" => {}
+      other => unmapped.add(&format!("TYPE={other}")),
+    }
+  }
+
+  result.steps = nest_steps(tasks);
+  for section in sections {
+    if section.title.is_empty() && !section.has_details {
+      continue;
+    }
+    let title = if section.title.is_empty() { name } else { section.title.as_str() };
+    result.notes.push(note(title, &section.lines.join("\n")));
+  }
+
+  Ok(unmapped.into_report(vec![result]))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const HEADER: &str = "TYPE,CONTENT,DESCRIPTION,PRIORITY,INDENT,AUTHOR,RESPONSIBLE,DATE,DATE_LANG,TIMEZONE,DEADLINE\n";
+
+  fn import_rows(rows: &str) -> serde_json::Value {
+    let report = import("Work", &format!("{HEADER}{rows}"), &Zone::from_settings("UTC")).unwrap();
+    serde_json::to_value(report).unwrap()
+  }
+
+  #[test]
+  fn nests_tasks_and_keeps_sections_and_comments() {
+    let report = import_rows(
+      "meta,view_style=list,,,,,,,,,\n\
+       task,Купить молоко,,4,1,Me (1),,,en,UTC,\n\
+       task,Sub,,4,2,Me (1),,,en,UTC,\n\
+       note,comment here,,,,Me (1),,,,,\n\
+       section,Later,,,,,,,,,\n\
+       task,Write doc,Some details,1,1,Me (1),,,en,UTC,\n",
+    );
+    let project = &report["projects"][0];
+    assert_eq!(project["name"], "Work");
+    assert_eq!(project["steps"][0]["text"], "Купить молоко");
+    assert_eq!(project["steps"][0]["children"][0]["text"], "Sub");
+    assert_eq!(project["steps"][1]["text"], "Write doc");
+    assert_eq!(project["notes"][0]["title"], "Work");
+    assert!(project["notes"][0]["body"].as_str().unwrap().contains("> Sub: comment here"));
+    assert_eq!(project["notes"][1]["title"], "Later");
+    assert_eq!(report["unmapped"], serde_json::json!([{ "field": "AUTHOR", "count": 3 }, { "field": "PRIORITY", "count": 1 }]));
+  }
+
+  #[test]
+  fn maps_dates_and_deadlines_onto_due_dates() {
+    let report = import_rows(
+      "task,Dated,,4,1,,,2026-10-20,en,UTC,\n\
+       task,Timed,,4,1,,,2026-10-20 09:30,en,UTC,\n\
+       task,Deadline only,,4,1,,,,en,UTC,2026-10-25\n\
+       task,Both,,4,1,,,2026-10-21,en,UTC,2026-10-25\n\
+       task,Spoken,,4,1,,,someday,en,UTC,\n",
+    );
+    let steps = &report["projects"][0]["steps"];
+    assert_eq!(steps[0]["dueAt"], "2026-10-20");
+    assert_eq!(steps[1]["dueAt"], "2026-10-20T09:30");
+    assert_eq!(steps[2]["dueAt"], "2026-10-25");
+    assert_eq!(steps[3]["dueAt"], "2026-10-21");
+    assert!(steps[4]["dueAt"].is_null());
+    assert_eq!(report["unmapped"], serde_json::json!([{ "field": "DATE", "count": 1 }, { "field": "DEADLINE", "count": 1 }]));
+  }
+
+  #[test]
+  fn rejects_files_that_are_not_todoist_exports() {
+    let zone = Zone::from_settings("UTC");
+    assert!(import("x", "name,value\na,b\n", &zone).is_err());
+    assert!(import("x", "", &zone).is_err());
+    let report = import("x", "TYPE,CONTENT\ntask,\"unterminated\n", &zone);
+    assert!(report.is_ok_and(|report| serde_json::to_value(report).unwrap()["projects"][0]["steps"].is_array()));
+  }
+
+  #[test]
+  fn maps_repeating_dates_onto_recurrence_rules() {
+    let cases = [
+      ("every day", Some("FREQ=DAILY")),
+      ("Every 3 weeks", Some("FREQ=WEEKLY;INTERVAL=3")),
+      ("every 2 months", Some("FREQ=MONTHLY;INTERVAL=2")),
+      ("every year", Some("FREQ=YEARLY")),
+      ("every weekday", Some("FREQ=WEEKLY;BYDAY=MO,TU,WE,TH,FR")),
+      ("every mon, wednesday and fri", Some("FREQ=WEEKLY;BYDAY=MO,WE,FR")),
+      ("every 0 days", None),
+      ("every 2 mondays", None),
+      ("every funday", None),
+      ("every! 3 days", None),
+      ("2026-10-20", None),
+    ];
+    for (value, rule) in cases {
+      assert_eq!(recurrence_rule(value).as_deref(), rule, "{value}");
+    }
+    let report = import_rows("task,Standup,,4,1,,,every weekday,en,UTC,\n");
+    assert_eq!(report["projects"][0]["steps"][0]["recurrence"], "FREQ=WEEKLY;BYDAY=MO,TU,WE,TH,FR");
+    assert_eq!(report["unmapped"], serde_json::json!([]));
+  }
+}
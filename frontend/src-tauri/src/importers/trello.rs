@@ -0,0 +1,216 @@
+use super::{ImportReport, Unmapped, format_local, note, project, step};
+use crate::StepState;
+use crate::tags::clean_tags;
+use crate::time_zone::Zone;
+use chrono::{DateTime, Duration, Utc};
+use serde::Deserialize;
+use serde_json::Value;
+
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct Board {
+  name: String,
+  desc: String,
+  lists: Vec<List>,
+  cards: Vec<Card>,
+  checklists: Vec<Checklist>,
+  actions: Vec<Action>,
+  custom_fields: Vec<Value>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct List {
+  id: String,
+  name: String,
+  closed: bool,
+  pos: f64,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct Card {
+  id: String,
+  name: String,
+  desc: String,
+  closed: bool,
+  id_list: String,
+  pos: f64,
+  due: Option<String>,
+  due_reminder: Option<i64>,
+  due_complete: bool,
+  start: Option<String>,
+  labels: Vec<Value>,
+  id_members: Vec<String>,
+  attachments: Vec<Value>,
+  id_attachment_cover: Option<String>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct Checklist {
+  id_card: String,
+  name: String,
+  pos: f64,
+  check_items: Vec<CheckItem>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct CheckItem {
+  name: String,
+  state: String,
+  pos: f64,
+  due: Option<String>,
+  id_member: Option<String>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct Action {
+  #[serde(rename = "type")]
+  kind: String,
+}
+
+fn due_instant(value: Option<&str>) -> Option<DateTime<Utc>> {
+  DateTime::parse_from_rfc3339(value?.trim()).ok().map(|value| value.with_timezone(&Utc))
+}
+
+fn set_due(step: &mut StepState, due: Option<DateTime<Utc>>, reminder_minutes: Option<i64>, zone: &Zone) {
+  let Some(due) = due else {
+    return;
+  };
+  step.due_at = Some(format_local(&due, zone));
+  step.remind_at = reminder_minutes
+    .filter(|minutes| *minutes >= 0)
+    .and_then(Duration::try_minutes)
+    .map(|before| format_local(&(due - before), zone));
+}
+
+pub(super) fn import(content: &str, zone: &Zone) -> Result<ImportReport, String> {
+  let mut board: Board = serde_json::from_str(content).map_err(|_| "not a Trello board export".to_string())?;
+  if board.lists.is_empty() && board.cards.is_empty() {
+    return Err("not a Trello board export".to_string());
+  }
+
+  let mut unmapped = Unmapped::default();
+  let mut result = project(&board.name, &board.desc);
+  board.lists.sort_by(|a, b| a.pos.total_cmp(&b.pos));
+  board.cards.sort_by(|a, b| a.pos.total_cmp(&b.pos));
+  board.checklists.sort_by(|a, b| a.pos.total_cmp(&b.pos));
+
+  for list in &board.lists {
+    if list.closed {
+      unmapped.add("archived lists");
+      continue;
+    }
+    let mut body = Vec::new();
+    for card in board.cards.iter().filter(|card| card.id_list == list.id) {
+      if card.closed {
+        unmapped.add("archived cards");
+        continue;
+      }
+      let mut card_step = step(&card.name, card.due_complete);
+      let due = due_instant(card.due.as_deref());
+      set_due(&mut card_step, due, card.due_reminder, zone);
+      card_step.tags = clean_tags(card.labels.iter().filter_map(|label| label.get("name").and_then(Value::as_str)));
+
+      let mut section = vec![format!("## {}", card.name.trim())];
+      if !card.desc.trim().is_empty() {
+        section.push(card.desc.trim().to_string());
+      }
+      for checklist in board.checklists.iter_mut().filter(|checklist| checklist.id_card == card.id) {
+        checklist.check_items.sort_by(|a, b| a.pos.total_cmp(&b.pos));
+        section.push(format!("{}:", checklist.name.trim()));
+        for item in &checklist.check_items {
+          let done = item.state == "complete";
+          section.push(format!("- [{}] {}", if done { "x" } else { " " }, item.name.trim()));
+          let mut item_step = step(&item.name, done);
+          let item_due = due_instant(item.due.as_deref());
+          set_due(&mut item_step, item_due, None, zone);
+          card_step.children.push(item_step);
+          unmapped.add_if(item.due.is_some() && item_due.is_none(), "checklist item due dates");
+          unmapped.add_if(item.id_member.is_some(), "checklist item members");
+        }
+      }
+      result.steps.push(card_step);
+      body.push(section.join("\n"));
+
+      unmapped.add_if(card.due.is_some() && due.is_none(), "card due dates");
+      unmapped.add_if(card.start.is_some(), "card start dates");
+      unmapped.add_if(!card.id_members.is_empty(), "card members");
+      unmapped.add_if(!card.attachments.is_empty(), "card attachments");
+      unmapped.add_if(card.id_attachment_cover.is_some(), "card covers");
+    }
+    result.notes.push(note(&list.name, &body.join("\n\n")));
+  }
+
+  for card in &board.cards {
+    if !board.lists.iter().any(|list| list.id == card.id_list) {
+      unmapped.add("cards without a list");
+    }
+  }
+  for action in &board.actions {
+    unmapped.add_if(action.kind == "commentCard", "card comments");
+  }
+  unmapped.add_if(!board.custom_fields.is_empty(), "custom fields");
+
+  Ok(unmapped.into_report(vec![result]))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const BOARD: &str = r#"{"name":"Board","desc":"d",
+    "lists":[{"id":"l2","name":"Done","pos":2},{"id":"l1","name":"Todo","pos":1},{"id":"l3","name":"Old","closed":true}],
+    "cards":[
+      {"id":"c1","name":"Card A","desc":"desc A","idList":"l1","pos":1,"due":"2026-10-20T09:00:00.000Z","dueReminder":60,"labels":[{"name":"x"}]},
+      {"id":"c2","name":"Card B","idList":"l2","dueComplete":true,"due":"tomorrow","dueReminder":-1},
+      {"id":"c3","name":"Lost","idList":"gone"}],
+    "checklists":[{"idCard":"c1","name":"CL","checkItems":[
+      {"name":"i2","state":"incomplete","pos":2,"due":"2026-10-19T22:30:00.000Z"},
+      {"name":"i1","state":"complete","pos":1}]}],
+    "actions":[{"type":"commentCard"},{"type":"updateCard"}]}"#;
+
+  #[test]
+  fn imports_lists_cards_and_checklists() {
+    let report = serde_json::to_value(import(BOARD, &Zone::from_settings("UTC")).unwrap()).unwrap();
+    let project = &report["projects"][0];
+    assert_eq!((project["name"].as_str(), project["description"].as_str()), (Some("Board"), Some("d")));
+    let names: Vec<_> = project["notes"].as_array().unwrap().iter().map(|note| note["title"].as_str().unwrap()).collect();
+    assert_eq!(names, ["Todo", "Done"]);
+    assert!(project["notes"][0]["body"].as_str().unwrap().contains("- [x] i1\n- [ ] i2"));
+    assert_eq!(project["steps"][0]["tags"], serde_json::json!(["x"]));
+    assert_eq!(project["steps"][0]["children"][0]["text"], "i1");
+    assert_eq!(project["steps"][1]["done"], true);
+    assert_eq!(
+      report["unmapped"],
+      serde_json::json!([
+        { "field": "archived lists", "count": 1 },
+        { "field": "card comments", "count": 1 },
+        { "field": "card due dates", "count": 1 },
+        { "field": "cards without a list", "count": 1 },
+      ])
+    );
+  }
+
+  #[test]
+  fn maps_due_dates_and_reminders_into_the_local_zone() {
+    let report = serde_json::to_value(import(BOARD, &Zone::from_settings("Europe/Berlin")).unwrap()).unwrap();
+    let card = &report["projects"][0]["steps"][0];
+    assert_eq!(card["dueAt"], "2026-10-20T11:00");
+    assert_eq!(card["remindAt"], "2026-10-20T10:00");
+    assert_eq!(card["children"][1]["dueAt"], "2026-10-20T00:30");
+    assert!(card["children"][1]["remindAt"].is_null());
+    assert!(report["projects"][0]["steps"][1]["dueAt"].is_null());
+  }
+
+  #[test]
+  fn rejects_files_that_are_not_boards() {
+    let zone = Zone::from_settings("UTC");
+    assert!(import("[]", &zone).is_err());
+    assert!(import("{}", &zone).is_err());
+    assert!(import("{\"lists\":", &zone).is_err());
+  }
+}
@@ -0,0 +1,144 @@
+pub(crate) struct DocLabels {
+  pub app_title: &'static str,
+  pub workspace: &'static str,
+  pub projects: &'static str,
+  pub project_status: &'static str,
+  pub status_not_set: &'static str,
+  pub pinned: &'static str,
+  pub project_notes: &'static str,
+  pub no_notes: &'static str,
+  pub project_steps: &'static str,
+  pub no_steps: &'static str,
+  pub progress: &'static str,
+  pub steps_done: &'static str,
+  pub note_count: &'static str,
+  pub step_count: &'static str,
+  pub summary: &'static str,
+  pub generated: &'static str,
+  pub contents: &'static str,
+  pub open_steps: &'static str,
+  pub no_open_steps: &'static str,
+}
+
+impl DocLabels {
+  pub fn progress_caption(&self, done: usize, total: usize) -> String {
+    self
+      .steps_done
+      .replace("{done}", &done.to_string())
+      .replace("{total}", &total.to_string())
+  }
+}
+
+const RU: DocLabels = DocLabels {
+  app_title: "Студия проектов и заметок",
+  workspace: "Все проекты",
+  projects: "Проекты",
+  project_status: "Статус проекта",
+  status_not_set: "не задан",
+  pinned: "Закреплен",
+  project_notes: "Заметки проекта",
+  no_notes: "В проекте пока нет заметок.",
+  project_steps: "Шаги проекта",
+  no_steps: "В проекте пока нет шагов.",
+  progress: "Прогресс",
+  steps_done: "Выполнено шагов: {done} из {total}",
+  note_count: "заметок",
+  step_count: "шагов",
+  summary: "Итог",
+  generated: "Сформировано",
+  contents: "Содержание",
+  open_steps: "Открытые шаги",
+  no_open_steps: "Все шаги выполнены.",
+};
+
+const EN: DocLabels = DocLabels {
+  app_title: "Project Notes Studio",
+  workspace: "All projects",
+  projects: "Projects",
+  project_status: "Project status",
+  status_not_set: "not set",
+  pinned: "Pinned",
+  project_notes: "Project notes",
+  no_notes: "No notes in this project yet.",
+  project_steps: "Project steps",
+  no_steps: "No steps in this project yet.",
+  progress: "Progress",
+  steps_done: "{done}/{total} steps done",
+  note_count: "notes",
+  step_count: "steps",
+  summary: "Summary",
+  generated: "Generated",
+  contents: "Contents",
+  open_steps: "Open steps",
+  no_open_steps: "All steps are done.",
+};
+
+const UK: DocLabels = DocLabels {
+  app_title: "Студія проєктів і нотаток",
+  workspace: "Усі проєкти",
+  projects: "Проєкти",
+  project_status: "Статус проєкту",
+  status_not_set: "не задано",
+  pinned: "Закріплено",
+  project_notes: "Нотатки проєкту",
+  no_notes: "У цьому проєкті поки немає нотаток.",
+  project_steps: "Кроки проєкту",
+  no_steps: "У цьому проєкті поки немає кроків.",
+  progress: "Прогрес",
+  steps_done: "Виконано кроків: {done} з {total}",
+  note_count: "нотаток",
+  step_count: "кроків",
+  summary: "Підсумок",
+  generated: "Сформовано",
+  contents: "Зміст",
+  open_steps: "Відкриті кроки",
+  no_open_steps: "Усі кроки виконано.",
+};
+
+pub(crate) fn doc_labels(language: &str) -> &'static DocLabels {
+  match language {
+    "en" => &EN,
+    "uk" => &UK,
+    _ => &RU,
+  }
+}
+
+pub(crate) struct AppLabels {
+  pub due_date: &'static str,
+  pub due_reminders: &'static str,
+  pub missed_reminders: &'static str,
+  pub tray_open: &'static str,
+  pub tray_quit: &'static str,
+}
+
+const APP_RU: AppLabels = AppLabels {
+  due_date: "Срок",
+  due_reminders: "Напоминания: {count}",
+  missed_reminders: "Пропущенные напоминания: {count}",
+  tray_open: "Открыть",
+  tray_quit: "Выход",
+};
+
+const APP_EN: AppLabels = AppLabels {
+  due_date: "Due",
+  due_reminders: "Reminders: {count}",
+  missed_reminders: "Missed reminders: {count}",
+  tray_open: "Open",
+  tray_quit: "Quit",
+};
+
+const APP_UK: AppLabels = AppLabels {
+  due_date: "Термін",
+  due_reminders: "Нагадування: {count}",
+  missed_reminders: "Пропущені нагадування: {count}",
+  tray_open: "Відкрити",
+  tray_quit: "Вийти",
+};
+
+pub(crate) fn app_labels(language: &str) -> &'static AppLabels {
+  match language {
+    "en" => &APP_EN,
+    "uk" => &APP_UK,
+    _ => &APP_RU,
+  }
+}
@@ -1,15 +1,71 @@
 use serde::{Deserialize, Serialize};
+use std::fmt;
 use std::fs;
 use std::path::PathBuf;
 use tauri::Manager;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+mod attachments;
+mod backup;
+mod docx;
+mod encrypted;
+mod epub;
+mod export_filter;
+mod history;
+mod importers;
+mod labels;
+mod markdown;
+mod markup;
+mod opml;
+mod org;
+mod pdf;
+mod project_map;
+mod qr_transfer;
+mod recurrence;
+mod reminders;
+mod report;
+mod revisions;
+mod slides;
+mod step_tree;
+mod steps_csv;
+mod tags;
+mod templates;
+#[cfg(test)]
+mod test_support;
+mod time_tracking;
+mod time_zone;
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+mod tray;
+mod wiki_links;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(untagged)]
 enum EntityId {
   Str(String),
   Num(u64),
 }
 
+impl EntityId {
+  fn parse(value: &str) -> Self {
+    match value.parse::<u64>() {
+      Ok(number) => EntityId::Num(number),
+      Err(_) => EntityId::Str(value.to_string()),
+    }
+  }
+
+  fn generate() -> Self {
+    EntityId::Str(uuid::Uuid::new_v4().to_string())
+  }
+}
+
+impl fmt::Display for EntityId {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      EntityId::Str(value) => f.write_str(value),
+      EntityId::Num(value) => write!(f, "{value}"),
+    }
+  }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 enum WindowMode {
@@ -57,6 +113,39 @@ struct StepState {
   id: Option<EntityId>,
   text: String,
   done: bool,
+  due_at: Option<String>,
+  remind_at: Option<String>,
+  tags: Vec<String>,
+  time_entries: Vec<TimeEntryState>,
+  recurrence: Option<String>,
+  completions: Vec<CompletionState>,
+  children: Vec<StepState>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct CompletionState {
+  due_at: Option<String>,
+  completed_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct TimeEntryState {
+  id: Option<EntityId>,
+  started_at: String,
+  ended_at: String,
+  note: String,
+  manual: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct AttachmentState {
+  hash: String,
+  name: String,
+  mime: String,
+  size: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -65,6 +154,8 @@ struct NoteState {
   id: Option<EntityId>,
   title: String,
   body: String,
+  tags: Vec<String>,
+  attachments: Vec<AttachmentState>,
   steps: Vec<StepState>,
 }
 
@@ -78,6 +169,9 @@ struct ProjectState {
   pinned: bool,
   notes: Vec<NoteState>,
   steps: Vec<StepState>,
+  tags: Vec<String>,
+  due_at: Option<String>,
+  remind_at: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -98,6 +192,10 @@ struct SettingsState {
   always_on_top: bool,
   #[serde(default = "default_language")]
   language: String,
+  git_history: bool,
+  time_zone: String,
+  auto_complete_steps: bool,
+  close_to_tray: bool,
 }
 
 impl Default for SettingsState {
@@ -111,6 +209,10 @@ impl Default for SettingsState {
       window_mode: default_window_mode(),
       always_on_top: false,
       language: default_language(),
+      git_history: false,
+      time_zone: String::new(),
+      auto_complete_steps: false,
+      close_to_tray: false,
     }
   }
 }
@@ -122,50 +224,114 @@ struct AppState {
   settings: SettingsState,
 }
 
+impl StepState {
+  fn progress(&self) -> (usize, usize) {
+    if self.children.is_empty() {
+      return (usize::from(self.done), 1);
+    }
+    let (done, total) = step_tree::leaf_progress(&self.children);
+    if self.done { (total, total) } else { (done, total) }
+  }
+}
+
+impl ProjectState {
+  fn step_progress(&self) -> (usize, usize) {
+    step_tree::leaf_progress(&self.steps)
+  }
+}
+
+impl AppState {
+  fn select_projects(&self, project_id: Option<&EntityId>) -> Result<Vec<&ProjectState>, String> {
+    match project_id {
+      Some(id) => self
+        .projects
+        .iter()
+        .find(|project| project.id.as_ref() == Some(id))
+        .map(|project| vec![project])
+        .ok_or_else(|| "project not found".to_string()),
+      None => Ok(self.projects.iter().collect()),
+    }
+  }
+}
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 #[allow(dead_code)]
 struct WindowSettingsPayload {
   window_mode: WindowMode,
   always_on_top: bool,
+  #[serde(default)]
+  close_to_tray: bool,
 }
 
-fn state_file_path(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+fn data_dir(app: &tauri::AppHandle) -> Result<PathBuf, String> {
   let dir = app.path().app_data_dir().map_err(|err| err.to_string())?;
   fs::create_dir_all(&dir).map_err(|err| err.to_string())?;
-  Ok(dir.join("app_state.json"))
+  Ok(dir)
 }
 
-#[tauri::command]
-fn load_app_state(app: tauri::AppHandle) -> Result<AppState, String> {
-  let path = state_file_path(&app)?;
+fn state_file_path(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+  Ok(data_dir(app)?.join("app_state.json"))
+}
+
+fn read_state_file(app: &tauri::AppHandle) -> Result<Option<AppState>, String> {
+  let path = state_file_path(app)?;
   if !path.exists() {
-    return Ok(AppState::default());
+    return Ok(None);
   }
-
   let content = fs::read_to_string(path).map_err(|err| err.to_string())?;
-  serde_json::from_str(&content).map_err(|err| err.to_string())
+  serde_json::from_str(&content).map(Some).map_err(|err| err.to_string())
 }
 
-#[tauri::command]
-fn save_app_state(app: tauri::AppHandle, state: AppState) -> Result<(), String> {
-  let path = state_file_path(&app)?;
-  let serialized = serde_json::to_string_pretty(&state).map_err(|err| err.to_string())?;
+fn write_state_file(app: &tauri::AppHandle, state: &AppState) -> Result<(), String> {
+  let path = state_file_path(app)?;
+  let serialized = serde_json::to_string_pretty(state).map_err(|err| err.to_string())?;
   fs::write(path, serialized).map_err(|err| err.to_string())
 }
 
 #[tauri::command]
-fn export_project_file(app: tauri::AppHandle, filename: String, content: String) -> Result<String, String> {
+fn load_app_state(app: tauri::AppHandle) -> Result<AppState, String> {
+  Ok(read_state_file(&app)?.unwrap_or_default())
+}
+
+#[tauri::command]
+fn save_app_state(app: tauri::AppHandle, state: AppState) -> Result<(), String> {
+  write_state_file(&app, &state)?;
+  if let Err(err) = revisions::record(&app, &state) {
+    log::warn!("failed to record note revisions: {err}");
+  }
+  if state.settings.git_history
+    && let Err(err) = history::record_save(&app, &state)
+  {
+    log::warn!("failed to record history: {err}");
+  }
+  Ok(())
+}
+
+fn file_stem(name: &str, fallback: &str) -> String {
+  let mut stem = String::new();
+  for ch in name.to_lowercase().chars() {
+    if ch.is_alphanumeric() || ch == '_' || ch == '-' {
+      stem.push(ch);
+    } else if !stem.ends_with('-') {
+      stem.push('-');
+    }
+  }
+  let stem = stem.trim_matches('-');
+  if stem.is_empty() { fallback.to_string() } else { stem.to_string() }
+}
+
+fn write_export_file(app: &tauri::AppHandle, filename: &str, extension: &str, content: &[u8]) -> Result<String, String> {
   let file_name = PathBuf::from(filename)
     .file_name()
     .and_then(|value| value.to_str())
     .ok_or_else(|| "invalid file name".to_string())?
     .to_string();
 
-  let final_name = if file_name.ends_with(".pns-project.json") {
+  let final_name = if file_name.ends_with(extension) {
     file_name
   } else {
-    format!("{file_name}.pns-project.json")
+    format!("{file_name}{extension}")
   };
 
   let download_dir = app
@@ -181,6 +347,21 @@ fn export_project_file(app: tauri::AppHandle, filename: String, content: String)
   Ok(export_path.to_string_lossy().into_owned())
 }
 
+#[tauri::command]
+fn export_project_file(
+  app: tauri::AppHandle,
+  filename: String,
+  content: String,
+  filter: Option<export_filter::ExportFilter>,
+) -> Result<String, String> {
+  let content = match filter {
+    Some(filter) => export_filter::filter_project_payload(&content, &filter)?,
+    None => content,
+  };
+  let content = attachments::embed_files(&data_dir(&app)?, &content)?;
+  write_export_file(&app, &filename, ".pns-project.json", content.as_bytes())
+}
+
 #[tauri::command]
 fn apply_window_settings(app: tauri::AppHandle, payload: WindowSettingsPayload) -> Result<(), String> {
   #[cfg(any(target_os = "android", target_os = "ios"))]
@@ -219,6 +400,7 @@ fn apply_window_settings(app: tauri::AppHandle, payload: WindowSettingsPayload)
     }
   }
 
+  tray::set_close_to_tray(&app, payload.close_to_tray);
   window
     .set_always_on_top(payload.always_on_top)
     .map_err(|err| err.to_string())
@@ -230,6 +412,7 @@ pub fn run() {
   tauri::Builder::default()
     .setup(|app| {
       app.handle().plugin(tauri_plugin_opener::init())?;
+      app.handle().plugin(tauri_plugin_notification::init())?;
       if cfg!(debug_assertions) {
         app.handle().plugin(
           tauri_plugin_log::Builder::default()
@@ -237,12 +420,92 @@ pub fn run() {
             .build(),
         )?;
       }
+      app.manage(reminders::ReminderScheduler::default());
+      app.manage(wiki_links::LinkIndex::default());
+      app.manage(revisions::RevisionTracker::default());
+      app.manage(time_tracking::TimeTracker::load(app.handle()));
+      reminders::start(app.handle().clone());
+      #[cfg(not(any(target_os = "android", target_os = "ios")))]
+      tray::setup(app)?;
+      if let Err(err) = attachments::collect_on_startup(app.handle()) {
+        log::warn!("failed to clean up attachments: {err}");
+      }
       Ok(())
     })
+    .on_window_event(|window, event| {
+      #[cfg(not(any(target_os = "android", target_os = "ios")))]
+      tray::hide_on_close(window, event);
+      #[cfg(any(target_os = "android", target_os = "ios"))]
+      let _ = (window, event);
+    })
     .invoke_handler(tauri::generate_handler![
       load_app_state,
       save_app_state,
       export_project_file,
+      qr_transfer::encode_project_qr,
+      qr_transfer::decode_project_qr,
+      encrypted::export_encrypted_project,
+      encrypted::decrypt_project_file,
+      report::export_html_report,
+      slides::export_slides,
+      pdf::export_pdf_report,
+      epub::export_epub,
+      docx::export_docx,
+      project_map::project_map,
+      steps_csv::export_steps_csv,
+      steps_csv::import_steps_csv,
+      attachments::add_attachment,
+      attachments::attachment_thumbnail,
+      attachments::open_attachment,
+      attachments::export_attachment,
+      attachments::import_attachment_files,
+      attachments::clean_attachments,
+      backup::create_backup,
+      backup::restore_backup,
+      history::history_log,
+      history::history_diff,
+      history::history_restore,
+      importers::import_task_export,
+      importers::import_notes_export,
+      markdown::render_markdown,
+      markdown::markdown_css,
+      markdown::open_link,
+      opml::export_opml,
+      opml::import_opml,
+      org::export_org,
+      org::import_org,
+      recurrence::complete_recurring_step,
+      recurrence::preview_recurrence,
+      reminders::snooze_reminder,
+      revisions::note_revisions,
+      revisions::note_revision,
+      revisions::diff_revisions,
+      revisions::restore_revision,
+      step_tree::indent_step,
+      step_tree::outdent_step,
+      step_tree::move_step,
+      step_tree::set_step_done,
+      tags::tag_index,
+      tags::rename_tag,
+      tags::merge_tags,
+      tags::delete_tag,
+      tags::query_tags,
+      templates::project_templates,
+      templates::save_project_template,
+      templates::delete_project_template,
+      templates::create_project_from_template,
+      templates::export_template_library,
+      templates::import_template_library,
+      time_tracking::timer_status,
+      time_tracking::start_timer,
+      time_tracking::stop_timer,
+      time_tracking::add_time_entry,
+      time_tracking::remove_time_entry,
+      time_tracking::time_report,
+      time_tracking::export_timesheet,
+      wiki_links::link_report,
+      wiki_links::resolve_wiki_link,
+      wiki_links::rename_note,
       apply_window_settings
     ])
     .run(tauri::generate_context!())
@@ -0,0 +1,225 @@
+use crate::markup::escape;
+use crate::wiki_links;
+use pulldown_cmark::{CodeBlockKind, CowStr, Event, Options, Parser, Tag, TagEnd, html};
+use std::borrow::Cow;
+use std::collections::HashSet;
+use std::sync::OnceLock;
+use syntect::highlighting::ThemeSet;
+use syntect::html::{ClassStyle, ClassedHTMLGenerator, css_for_theme_with_class_style};
+use syntect::parsing::SyntaxSet;
+use syntect::util::LinesWithEndings;
+use tauri_plugin_opener::OpenerExt;
+
+const HIGHLIGHT_PREFIX: &str = "hl-";
+const HIGHLIGHT_STYLE: ClassStyle = ClassStyle::SpacedPrefixed { prefix: HIGHLIGHT_PREFIX };
+const HIGHLIGHT_THEME: &str = "base16-ocean.dark";
+const CODE_BLOCK_CLASS: &str = "code-block";
+const LINK_SCHEMES: [&str; 3] = ["http", "https", "mailto"];
+const NOTE_LINK_PREFIX: &str = "#note:";
+
+fn syntax_set() -> &'static SyntaxSet {
+  static SYNTAXES: OnceLock<SyntaxSet> = OnceLock::new();
+  SYNTAXES.get_or_init(SyntaxSet::load_defaults_newlines)
+}
+
+pub(crate) fn highlight_css() -> &'static str {
+  static CSS: OnceLock<String> = OnceLock::new();
+  CSS.get_or_init(|| {
+    let themes = ThemeSet::load_defaults();
+    themes
+      .themes
+      .get(HIGHLIGHT_THEME)
+      .and_then(|theme| css_for_theme_with_class_style(theme, HIGHLIGHT_STYLE).ok())
+      .unwrap_or_default()
+  })
+}
+
+fn highlight(code: &str, language: &str) -> String {
+  let syntaxes = syntax_set();
+  let token = language.split([',', ' ']).next().unwrap_or_default();
+  let syntax = syntaxes
+    .find_syntax_by_token(token)
+    .unwrap_or_else(|| syntaxes.find_syntax_plain_text());
+  let mut generator = ClassedHTMLGenerator::new_with_class_style(syntax, syntaxes, HIGHLIGHT_STYLE);
+  for line in LinesWithEndings::from(code) {
+    if generator.parse_html_for_line_which_includes_newline(line).is_err() {
+      return escape(code);
+    }
+  }
+  generator.finalize()
+}
+
+fn encode_target(target: &str) -> String {
+  let mut out = String::with_capacity(target.len());
+  for byte in target.bytes() {
+    if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.' | b'~') {
+      out.push(byte as char);
+    } else {
+      out.push_str(&format!("%{byte:02X}"));
+    }
+  }
+  out
+}
+
+fn flush_text(text: &mut String, events: &mut Vec<Event<'_>>) {
+  if text.is_empty() {
+    return;
+  }
+  let mut last = 0;
+  for link in wiki_links::parse_links(text) {
+    if link.span.start > last {
+      events.push(Event::Text(CowStr::from(text[last..link.span.start].to_string())));
+    }
+    let anchor = format!(
+      r#"<a href="{NOTE_LINK_PREFIX}{}">{}</a>"#,
+      encode_target(&link.target),
+      escape(link.label.as_deref().unwrap_or(&link.target)),
+    );
+    events.push(Event::InlineHtml(anchor.into()));
+    last = link.span.end;
+  }
+  if last < text.len() {
+    events.push(Event::Text(CowStr::from(text[last..].to_string())));
+  }
+  text.clear();
+}
+
+fn to_html(body: &str) -> String {
+  let options = Options::ENABLE_TABLES | Options::ENABLE_TASKLISTS | Options::ENABLE_STRIKETHROUGH;
+  let mut events = Vec::new();
+  let mut code: Option<(String, String)> = None;
+  let mut text = String::new();
+  for event in Parser::new_ext(body, options) {
+    if !matches!(event, Event::Text(_)) {
+      flush_text(&mut text, &mut events);
+    }
+    match event {
+      Event::Start(Tag::CodeBlock(kind)) => {
+        let language = match kind {
+          CodeBlockKind::Fenced(language) => language.to_string(),
+          CodeBlockKind::Indented => String::new(),
+        };
+        code = Some((language, String::new()));
+      }
+      Event::End(TagEnd::CodeBlock) => {
+        if let Some((language, text)) = code.take() {
+          let block = format!(r#"<pre class="{CODE_BLOCK_CLASS}"><code>{}</code></pre>"#, highlight(&text, &language));
+          events.push(Event::Html(block.into()));
+        }
+      }
+      Event::Text(chunk) => match &mut code {
+        Some((_, buffer)) => buffer.push_str(&chunk),
+        None => text.push_str(&chunk),
+      },
+      other => events.push(other),
+    }
+  }
+  flush_text(&mut text, &mut events);
+  let mut out = String::new();
+  html::push_html(&mut out, events.into_iter());
+  out
+}
+
+fn keep_classes<'u>(value: &'u str) -> Option<Cow<'u, str>> {
+  let classes: Vec<&str> = value
+    .split_whitespace()
+    .filter(|class| class.starts_with(HIGHLIGHT_PREFIX) || *class == CODE_BLOCK_CLASS)
+    .collect();
+  if classes.is_empty() { None } else { Some(classes.join(" ").into()) }
+}
+
+fn sanitize(html: &str) -> String {
+  ammonia::Builder::default()
+    .url_schemes(HashSet::from(LINK_SCHEMES))
+    .link_rel(Some("noopener noreferrer"))
+    .add_tags(["input"])
+    .add_tag_attributes("input", ["checked"])
+    .set_tag_attribute_value("input", "type", "checkbox")
+    .set_tag_attribute_value("input", "disabled", "")
+    .add_tag_attributes("span", ["class"])
+    .add_tag_attributes("pre", ["class"])
+    .attribute_filter(|_, attribute, value| if attribute == "class" { keep_classes(value) } else { Some(value.into()) })
+    .clean(html)
+    .to_string()
+}
+
+pub(crate) fn render(body: &str) -> String {
+  sanitize(&to_html(body))
+}
+
+fn allowed_link(url: &str) -> bool {
+  url
+    .split_once(':')
+    .is_some_and(|(scheme, _)| LINK_SCHEMES.contains(&scheme.to_ascii_lowercase().as_str()))
+}
+
+#[tauri::command]
+pub(crate) fn render_markdown(body: String) -> String {
+  render(&body)
+}
+
+#[tauri::command]
+pub(crate) fn markdown_css() -> String {
+  highlight_css().to_string()
+}
+
+#[tauri::command]
+pub(crate) fn open_link(app: tauri::AppHandle, url: String) -> Result<(), String> {
+  let url = url.trim();
+  if !allowed_link(url) {
+    return Err("unsupported link".to_string());
+  }
+  app.opener().open_url(url, None::<&str>).map_err(|err| err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn renders_common_markdown() {
+    let html = render("# Title\n\nSome **bold** ~~gone~~\n\n- [x] done\n- [ ] open\n\n| a | b |\n|:--|--:|\n| 1 | 2 |\n");
+    assert!(html.contains("<h1>Title</h1>"));
+    assert!(html.contains("<strong>bold</strong> <del>gone</del>"));
+    assert!(html.contains("<table>"));
+    assert_eq!(html.matches("checked").count(), 1);
+    assert_eq!(html.matches(r#"type="checkbox""#).count(), 2);
+  }
+
+  #[test]
+  fn strips_scripts_handlers_and_unsafe_links() {
+    let html = render(
+      "[ok](https://example.com) [bad](javascript:alert(1)) <a href=\"#\" onclick=\"x()\">raw</a>\n\n\
+       <script>alert(1)</script> <iframe src=\"https://x\"></iframe>\n\n<img src=\"x\" onerror=\"alert(1)\"> <input type=\"text\" value=\"v\">",
+    );
+    assert!(!html.contains("<script") && !html.contains("<iframe"));
+    assert!(!html.contains("javascript:") && !html.contains("onclick") && !html.contains("onerror"));
+    assert!(html.contains(r#"<a href="https://example.com" rel="noopener noreferrer">ok</a>"#));
+    assert!(!html.contains(r#"type="text""#));
+  }
+
+  #[test]
+  fn highlights_code_and_keeps_only_highlight_classes() {
+    let html = render("```rust\nfn main() { let x = \"<b>\"; }\n```\n\n    indented <i>\n\n<span class=\"evil hl-keyword\">s</span>");
+    assert!(html.contains(r#"<pre class="code-block"><code><span class="hl-source hl-rust">"#));
+    assert!(html.contains("&lt;b&gt;") && html.contains("indented &lt;i&gt;"));
+    assert!(html.contains(r#"class="hl-keyword""#) && !html.contains("evil"));
+    assert!(highlight_css().contains(".hl-keyword"));
+    assert!(render("```no-such-language\nplain <x>\n```").contains("plain &lt;x&gt;"));
+  }
+
+  #[test]
+  fn turns_wiki_links_into_note_anchors_outside_code() {
+    let html = render("See [[Beta/Spec|the &amp; spec]] and [[Заметка]] and `[[code]]`\n\n```\n[[x]]\n```");
+    assert!(html.contains(r##"<a href="#note:Beta%2FSpec" rel="noopener noreferrer">the &amp; spec</a>"##));
+    assert!(html.contains(r##"<a href="#note:%D0%97%D0%B0%D0%BC%D0%B5%D1%82%D0%BA%D0%B0" rel="noopener noreferrer">Заметка</a>"##));
+    assert!(html.contains("<code>[[code]]</code>"));
+    assert!(html.contains("[[x]]"));
+  }
+
+  #[test]
+  fn opens_only_web_and_mail_links() {
+    assert!(allowed_link("HTTPS://x") && allowed_link("mailto:a@b"));
+    assert!(!allowed_link("file:///etc") && !allowed_link("javascript:x") && !allowed_link("no-scheme"));
+  }
+}
@@ -0,0 +1,51 @@
+pub(crate) fn escape(value: &str) -> String {
+  let mut out = String::with_capacity(value.len());
+  for ch in value.chars() {
+    match ch {
+      '&' => out.push_str("&amp;"),
+      '<' => out.push_str("&lt;"),
+      '>' => out.push_str("&gt;"),
+      '"' => out.push_str("&quot;"),
+      '\'' => out.push_str("&#39;"),
+      '\t' | '\n' | '\r' => out.push(ch),
+      '\u{0}'..='\u{1f}' | '\u{fffe}' | '\u{ffff}' => {}
+      _ => out.push(ch),
+    }
+  }
+  out
+}
+
+pub(crate) fn paragraphs(text: &str) -> Vec<Vec<&str>> {
+  let mut result: Vec<Vec<&str>> = Vec::new();
+  let mut current = Vec::new();
+  for line in text.lines() {
+    if line.trim().is_empty() {
+      if !current.is_empty() {
+        result.push(std::mem::take(&mut current));
+      }
+    } else {
+      current.push(line.trim_end());
+    }
+  }
+  if !current.is_empty() {
+    result.push(current);
+  }
+  result
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn escapes_markup_and_drops_characters_xml_cannot_hold() {
+    assert_eq!(escape(r#"<a href="x">Tom & 'Jerry'</a>"#), "&lt;a href=&quot;x&quot;&gt;Tom &amp; &#39;Jerry&#39;&lt;/a&gt;");
+    assert_eq!(escape("a\u{1}b\tc\r\nd\u{ffff}ё"), "ab\tc\r\ndё");
+  }
+
+  #[test]
+  fn splits_text_into_paragraphs_of_lines() {
+    assert_eq!(paragraphs("  one  \ntwo\n\n \n\nthree\n"), [vec!["  one", "two"], vec!["three"]]);
+    assert!(paragraphs(" \n\n").is_empty());
+  }
+}
@@ -0,0 +1,254 @@
+use crate::importers::{ImportReport, Unmapped, note, project, step};
+use crate::labels::doc_labels;
+use crate::markup::escape;
+use crate::{AppState, EntityId, NoteState, ProjectState, StepState, file_stem, write_export_file};
+use roxmltree::Node;
+use std::fmt::Write;
+
+const KIND_PROJECT: &str = "project";
+const KIND_NOTE: &str = "note";
+const KIND_STEP: &str = "step";
+
+fn attr(value: &str) -> String {
+  escape(value)
+    .replace('\r', "&#13;")
+    .replace('\n', "&#10;")
+    .replace('\t', "&#9;")
+}
+
+fn id_attr(id: Option<&EntityId>) -> String {
+  id.map(|id| format!(" _id=\"{}\"", attr(&id.to_string()))).unwrap_or_default()
+}
+
+fn write_note(out: &mut String, note: &NoteState) {
+  let _ = write!(out, "      <outline text=\"{}\" type=\"{KIND_NOTE}\"{}", attr(&note.title), id_attr(note.id.as_ref()));
+  if !note.body.is_empty() {
+    let _ = write!(out, " _note=\"{}\"", attr(&note.body));
+  }
+  out.push_str("/>\n");
+}
+
+fn write_step(out: &mut String, step: &StepState, depth: usize) {
+  let indent = "  ".repeat(depth + 3);
+  let _ = write!(
+    out,
+    "{indent}<outline text=\"{}\" type=\"{KIND_STEP}\"{} _complete=\"{}\"",
+    attr(&step.text),
+    id_attr(step.id.as_ref()),
+    step.done
+  );
+  if step.children.is_empty() {
+    out.push_str("/>\n");
+    return;
+  }
+  out.push_str(">\n");
+  for child in &step.children {
+    write_step(out, child, depth + 1);
+  }
+  let _ = writeln!(out, "{indent}</outline>");
+}
+
+fn write_project(out: &mut String, project: &ProjectState) {
+  let _ = write!(out, "    <outline text=\"{}\" type=\"{KIND_PROJECT}\"{}", attr(&project.name), id_attr(project.id.as_ref()));
+  if !project.status.is_empty() {
+    let _ = write!(out, " _status=\"{}\"", attr(&project.status));
+  }
+  if project.pinned {
+    out.push_str(" _pinned=\"true\"");
+  }
+  if !project.description.is_empty() {
+    let _ = write!(out, " _note=\"{}\"", attr(&project.description));
+  }
+  if project.notes.is_empty() && project.steps.is_empty() {
+    out.push_str("/>\n");
+    return;
+  }
+  out.push_str(">\n");
+  for note in &project.notes {
+    write_note(out, note);
+  }
+  for step in &project.steps {
+    write_step(out, step, 0);
+  }
+  out.push_str("    </outline>\n");
+}
+
+pub(crate) fn render_opml(projects: &[&ProjectState], title: &str) -> String {
+  let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<opml version=\"2.0\">\n  <head>\n");
+  let _ = writeln!(out, "    <title>{}</title>", escape(title));
+  let _ = writeln!(out, "    <dateCreated>{}</dateCreated>", chrono::Utc::now().to_rfc2822());
+  out.push_str("  </head>\n  <body>\n");
+  for project in projects {
+    write_project(&mut out, project);
+  }
+  out.push_str("  </body>\n</opml>\n");
+  out
+}
+
+fn outline_id(node: Node) -> Option<EntityId> {
+  node
+    .attribute("_id")
+    .map(str::trim)
+    .filter(|id| !id.is_empty())
+    .map(EntityId::parse)
+}
+
+fn outline_text<'a>(node: Node<'a, '_>) -> &'a str {
+  node.attribute("text").or_else(|| node.attribute("title")).unwrap_or_default()
+}
+
+fn is_done(node: Node) -> bool {
+  node
+    .attribute("_complete")
+    .or_else(|| node.attribute("complete"))
+    .is_some_and(|value| value.eq_ignore_ascii_case("true"))
+}
+
+fn outlines<'a, 'input>(node: Node<'a, 'input>) -> impl Iterator<Item = Node<'a, 'input>> {
+  node.children().filter(|child| child.has_tag_name("outline"))
+}
+
+fn outline_list(node: Node, depth: usize, lines: &mut Vec<String>) {
+  for child in outlines(node) {
+    let marker = if child.attribute("_complete").is_some() {
+      if is_done(child) { "- [x] " } else { "- [ ] " }
+    } else {
+      "- "
+    };
+    lines.push(format!("{}{marker}{}", "  ".repeat(depth), outline_text(child).trim()));
+    outline_list(child, depth + 1, lines);
+  }
+}
+
+fn parse_note(node: Node) -> NoteState {
+  let mut body = node.attribute("_note").unwrap_or_default().to_string();
+  let mut lines = Vec::new();
+  outline_list(node, 0, &mut lines);
+  if !lines.is_empty() {
+    if !body.trim().is_empty() {
+      body.push_str("\n\n");
+    }
+    body.push_str(&lines.join("\n"));
+  }
+  NoteState { id: outline_id(node), ..note(outline_text(node), &body) }
+}
+
+fn parse_step(node: Node, unmapped: &mut Unmapped) -> StepState {
+  unmapped.add_if(node.attribute("_note").is_some_and(|value| !value.trim().is_empty()), "step notes");
+  let mut parsed = step(outline_text(node), is_done(node));
+  parsed.id = outline_id(node);
+  parsed.children = outlines(node).map(|child| parse_step(child, unmapped)).collect();
+  parsed
+}
+
+fn parse_project(node: Node, unmapped: &mut Unmapped) -> ProjectState {
+  let mut result = project(outline_text(node), node.attribute("_note").unwrap_or_default());
+  result.id = outline_id(node);
+  result.status = node.attribute("_status").unwrap_or_default().trim().to_string();
+  result.pinned = node.attribute("_pinned").is_some_and(|value| value.eq_ignore_ascii_case("true"));
+
+  for child in outlines(node) {
+    let kind = child.attribute("type").unwrap_or_default();
+    match kind {
+      KIND_NOTE => result.notes.push(parse_note(child)),
+      KIND_STEP => result.steps.push(parse_step(child, unmapped)),
+      _ if child.attribute("_complete").is_some() => result.steps.push(parse_step(child, unmapped)),
+      _ if child.attribute("_note").is_some() || outlines(child).next().is_some() => {
+        result.notes.push(parse_note(child))
+      }
+      _ => result.steps.push(step(outline_text(child), false)),
+    }
+    unmapped.add_if(!matches!(kind, "" | KIND_NOTE | KIND_STEP), &format!("outline type \"{kind}\""));
+  }
+  result
+}
+
+pub(crate) fn parse_opml(content: &str) -> Result<ImportReport, String> {
+  let document = roxmltree::Document::parse(content).map_err(|err| format!("not an OPML file: {err}"))?;
+  let root = document.root_element();
+  if !root.has_tag_name("opml") {
+    return Err("not an OPML file".to_string());
+  }
+  let body = root
+    .children()
+    .find(|child| child.has_tag_name("body"))
+    .ok_or_else(|| "OPML file has no body".to_string())?;
+
+  let mut unmapped = Unmapped::default();
+  let projects: Vec<ProjectState> = outlines(body).map(|node| parse_project(node, &mut unmapped)).collect();
+  if projects.is_empty() {
+    return Err("OPML file has no outlines".to_string());
+  }
+  Ok(unmapped.into_report(projects))
+}
+
+#[tauri::command]
+pub(crate) fn export_opml(app: tauri::AppHandle, state: AppState, project_id: Option<EntityId>) -> Result<String, String> {
+  let projects = state.select_projects(project_id.as_ref())?;
+  let (title, stem) = match projects.first() {
+    Some(project) if project_id.is_some() => (project.name.clone(), file_stem(&project.name, "project")),
+    _ => (doc_labels(&state.settings.language).workspace.to_string(), "workspace".to_string()),
+  };
+  let content = render_opml(&projects, &title);
+  write_export_file(&app, &stem, ".opml", content.as_bytes())
+}
+
+#[tauri::command]
+pub(crate) fn import_opml(content: String) -> Result<ImportReport, String> {
+  parse_opml(content.trim_start_matches('\u{feff}'))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn sample() -> AppState {
+    serde_json::from_str(
+      r#"{"projects":[{"id":"p1","name":"Alpha & <Beta>","description":"line1\nline2","status":"В работе","pinned":true,
+        "notes":[{"id":7,"title":"N1","body":"a\n\tb \"q\""}],
+        "steps":[{"id":"s1","text":"one","done":true,"children":[{"id":"s3","text":"nested"}]},{"id":"s2","text":"two"}]}]}"#,
+    )
+    .unwrap()
+  }
+
+  fn report(content: &str) -> serde_json::Value {
+    serde_json::to_value(parse_opml(content).unwrap()).unwrap()
+  }
+
+  #[test]
+  fn round_trips_projects_notes_and_steps() {
+    let state = sample();
+    let rendered = render_opml(&state.select_projects(None).unwrap(), "T & T");
+    assert!(rendered.contains("<title>T &amp; T</title>"));
+    let imported = report(&rendered);
+    let expected = serde_json::to_value(&state.projects).unwrap();
+    assert_eq!(imported["projects"], expected);
+    assert_eq!(imported["unmapped"], serde_json::json!([]));
+  }
+
+  #[test]
+  fn reads_generic_outlines() {
+    let imported = report(
+      r#"<opml version="2.0"><head/><body>
+        <outline text="Proj"><outline text="Idea" _note="x"><outline text="sub"/></outline>
+          <outline text="Task" _complete="true"><outline text="child"/></outline>
+          <outline title="plain"/><outline text="feed" type="rss"/></outline>
+      </body></opml>"#,
+    );
+    let project = &imported["projects"][0];
+    assert_eq!(project["name"], "Proj");
+    assert_eq!(project["notes"][0]["body"], "x\n\n- sub");
+    assert_eq!(project["steps"][0]["done"], true);
+    assert_eq!(project["steps"][0]["children"][0]["text"], "child");
+    assert_eq!(project["steps"][1]["text"], "plain");
+    assert_eq!(imported["unmapped"], serde_json::json!([{ "field": "outline type \"rss\"", "count": 1 }]));
+  }
+
+  #[test]
+  fn rejects_malformed_files() {
+    assert!(parse_opml("<opml><body>").is_err());
+    assert!(parse_opml("<rss><body/></rss>").is_err());
+    assert!(parse_opml("<opml><head/></opml>").is_err_and(|err| err.contains("no body")));
+    assert!(parse_opml("<opml><body/></opml>").is_err_and(|err| err.contains("no outlines")));
+  }
+}
@@ -0,0 +1,484 @@
+use crate::labels::doc_labels;
+use crate::tags::clean_tags;
+use crate::{AppState, EntityId, NoteState, ProjectState, StepState, file_stem, step_tree, write_export_file};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt::Write;
+
+const PROJECT_LEVEL: usize = 1;
+const ITEM_LEVEL: usize = 2;
+const KIND_PROJECT: &str = "project";
+const KIND_NOTE: &str = "note";
+const KIND_STEP: &str = "step";
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct OrgImportResult {
+  state: AppState,
+  updated: usize,
+  added: usize,
+}
+
+fn tag_suffix(tags: &[String]) -> String {
+  let tags: Vec<String> = tags.iter().map(|tag| tag.trim().replace([' ', ':'], "_")).filter(|tag| !tag.is_empty()).collect();
+  if tags.is_empty() { String::new() } else { format!(" :{}:", tags.join(":")) }
+}
+
+fn stars(level: usize) -> String {
+  "*".repeat(level)
+}
+
+fn write_properties(out: &mut String, properties: &[(&str, String)]) {
+  let properties: Vec<_> = properties.iter().filter(|(_, value)| !value.is_empty()).collect();
+  if properties.is_empty() {
+    return;
+  }
+  out.push_str(":PROPERTIES:\n");
+  for (key, value) in properties {
+    let _ = writeln!(out, ":{key}: {value}");
+  }
+  out.push_str(":END:\n");
+}
+
+fn id_value(id: Option<&EntityId>) -> String {
+  id.map(ToString::to_string).unwrap_or_default()
+}
+
+fn markdown_heading(line: &str) -> Option<(usize, &str)> {
+  let depth = line.chars().take_while(|ch| *ch == '#').count();
+  let text = line[depth..].strip_prefix(' ')?;
+  (1..=6).contains(&depth).then_some((depth, text))
+}
+
+fn write_body(out: &mut String, body: &str, heading_level: Option<usize>) {
+  let mut in_fence = false;
+  for line in body.lines() {
+    if line.trim_start().starts_with("```") {
+      in_fence = !in_fence;
+    }
+    match (heading_level, markdown_heading(line)) {
+      (Some(level), Some((depth, text))) if !in_fence => {
+        let _ = writeln!(out, "{} {text}", stars(level + depth));
+      }
+      _ if line.starts_with('*') || line.starts_with(",*") || line.starts_with(",,") => {
+        let _ = writeln!(out, ",{line}");
+      }
+      _ => {
+        let _ = writeln!(out, "{line}");
+      }
+    }
+  }
+}
+
+fn write_project(out: &mut String, project: &ProjectState) {
+  let _ = writeln!(out, "{} {}{}", stars(PROJECT_LEVEL), project.name.trim(), tag_suffix(&project.tags));
+  write_properties(
+    out,
+    &[
+      ("ID", id_value(project.id.as_ref())),
+      ("KIND", KIND_PROJECT.to_string()),
+      ("STATUS", project.status.trim().to_string()),
+      ("PINNED", if project.pinned { "t".to_string() } else { String::new() }),
+    ],
+  );
+  write_body(out, project.description.trim(), None);
+
+  for note in &project.notes {
+    let _ = writeln!(out, "{} {}{}", stars(ITEM_LEVEL), note.title.trim(), tag_suffix(&note.tags));
+    write_properties(out, &[("ID", id_value(note.id.as_ref())), ("KIND", KIND_NOTE.to_string())]);
+    write_body(out, note.body.trim(), Some(ITEM_LEVEL));
+  }
+  for (depth, step) in step_tree::flatten(&project.steps) {
+    let keyword = if step.done { "DONE" } else { "TODO" };
+    let _ = writeln!(out, "{} {keyword} {}{}", stars(ITEM_LEVEL + depth), step.text.trim(), tag_suffix(&step.tags));
+    write_properties(out, &[("ID", id_value(step.id.as_ref())), ("KIND", KIND_STEP.to_string())]);
+  }
+}
+
+pub(crate) fn render_org(projects: &[&ProjectState], title: &str) -> String {
+  let mut out = String::new();
+  let _ = writeln!(out, "#+TITLE: {title}");
+  out.push_str("#+STARTUP: overview\n#+TODO: TODO | DONE\n");
+  for project in projects {
+    out.push('\n');
+    write_project(&mut out, project);
+  }
+  out
+}
+
+struct Heading {
+  level: usize,
+  keyword: Option<bool>,
+  text: String,
+  tags: Vec<String>,
+  properties: HashMap<String, String>,
+  body: Vec<String>,
+}
+
+impl Heading {
+  fn kind(&self) -> Option<&str> {
+    self.properties.get("KIND").map(String::as_str)
+  }
+
+  fn step_done(&self) -> Option<bool> {
+    match self.kind() {
+      Some(KIND_STEP) => Some(self.keyword == Some(true)),
+      Some(_) => None,
+      None => self.keyword,
+    }
+  }
+
+  fn title(&self) -> String {
+    let kind = self.kind();
+    let mut title = self.text.as_str();
+    if kind.is_none_or(|kind| kind == KIND_STEP) && self.keyword.is_some() {
+      title = title.get(4..).unwrap_or_default().trim_start();
+    }
+    if kind.is_none()
+      && let Some(rest) = title.strip_prefix("[#").and_then(|rest| rest.get(2..))
+    {
+      title = rest.trim_start();
+    }
+    title.to_string()
+  }
+}
+
+fn parse_heading(line: &str) -> Option<(usize, Option<bool>, String, Vec<String>)> {
+  let level = line.chars().take_while(|ch| *ch == '*').count();
+  let rest = line[level..].strip_prefix(' ')?;
+  if level == 0 {
+    return None;
+  }
+  let mut text = rest.trim();
+  let keyword = match text.split_once(' ').map_or(text, |(word, _)| word) {
+    "TODO" => Some(false),
+    "DONE" => Some(true),
+    _ => None,
+  };
+  let mut tags = Vec::new();
+  if let Some((rest, suffix)) = text.rsplit_once(char::is_whitespace) {
+    let suffix = suffix.trim();
+    if suffix.len() > 2 && suffix.starts_with(':') && suffix.ends_with(':') && !suffix.contains(' ') {
+      text = rest.trim_end();
+      tags = clean_tags(suffix.split(':'));
+    }
+  }
+  Some((level, keyword, text.to_string(), tags))
+}
+
+fn parse_headings(content: &str) -> Vec<Heading> {
+  let mut headings: Vec<Heading> = Vec::new();
+  let mut in_drawer = false;
+  for line in content.lines() {
+    if let Some((level, keyword, text, tags)) = parse_heading(line) {
+      headings.push(Heading { level, keyword, text, tags, properties: HashMap::new(), body: Vec::new() });
+      in_drawer = false;
+      continue;
+    }
+    let Some(heading) = headings.last_mut() else {
+      continue;
+    };
+    let trimmed = line.trim();
+    if heading.body.is_empty() && !in_drawer {
+      if trimmed.eq_ignore_ascii_case(":PROPERTIES:") && heading.properties.is_empty() {
+        in_drawer = true;
+        continue;
+      }
+      if ["CLOSED:", "SCHEDULED:", "DEADLINE:"].iter().any(|keyword| trimmed.starts_with(keyword)) {
+        continue;
+      }
+    }
+    if in_drawer {
+      if trimmed.eq_ignore_ascii_case(":END:") {
+        in_drawer = false;
+      } else if let Some((key, value)) = trimmed.strip_prefix(':').and_then(|rest| rest.split_once(':')) {
+        heading.properties.insert(key.to_uppercase(), value.trim().to_string());
+      }
+      continue;
+    }
+    let line = if line.starts_with(",*") || line.starts_with(",,") { &line[1..] } else { line };
+    heading.body.push(line.to_string());
+  }
+  headings
+}
+
+fn heading_id(heading: &Heading) -> Option<EntityId> {
+  heading.properties.get("ID").filter(|id| !id.is_empty()).map(|id| EntityId::parse(id))
+}
+
+fn push_body(body: &mut String, lines: &[String]) {
+  for line in lines {
+    body.push_str(line);
+    body.push('\n');
+  }
+}
+
+pub(crate) fn parse_org(content: &str) -> Vec<ProjectState> {
+  let mut projects: Vec<ProjectState> = Vec::new();
+  let mut current_note: Option<usize> = None;
+  let mut step_levels: Vec<(usize, usize)> = Vec::new();
+
+  for heading in parse_headings(content.trim_start_matches('\u{feff}')) {
+    if heading.level <= PROJECT_LEVEL {
+      let mut description = String::new();
+      push_body(&mut description, &heading.body);
+      projects.push(ProjectState {
+        id: heading_id(&heading),
+        name: heading.title(),
+        description: description.trim().to_string(),
+        status: heading.properties.get("STATUS").cloned().unwrap_or_default(),
+        pinned: heading.properties.get("PINNED").is_some_and(|value| matches!(value.as_str(), "t" | "true" | "yes")),
+        tags: heading.tags.clone(),
+        ..Default::default()
+      });
+      current_note = None;
+      step_levels.clear();
+      continue;
+    }
+    let Some(project) = projects.last_mut() else {
+      continue;
+    };
+
+    if heading.level == ITEM_LEVEL {
+      current_note = None;
+      step_levels.clear();
+      match heading.step_done() {
+        Some(done) => {
+          project.steps.push(StepState { id: heading_id(&heading), text: heading.title(), done, tags: heading.tags.clone(), ..Default::default() });
+          step_levels.push((heading.level, project.steps.len() - 1));
+        }
+        None => {
+          let mut body = String::new();
+          push_body(&mut body, &heading.body);
+          project.notes.push(NoteState { id: heading_id(&heading), title: heading.title(), body, tags: heading.tags.clone(), ..Default::default() });
+          current_note = Some(project.notes.len() - 1);
+        }
+      }
+      continue;
+    }
+
+    if let Some(index) = current_note {
+      let body = &mut project.notes[index].body;
+      let mut lines = vec![format!("{} {}", "#".repeat((heading.level - ITEM_LEVEL).min(6)), heading.text)];
+      lines.extend(heading.body.iter().cloned());
+      push_body(body, &lines);
+    } else if let Some(done) = heading.step_done() {
+      while step_levels.last().is_some_and(|(level, _)| *level >= heading.level) {
+        step_levels.pop();
+      }
+      if step_levels.is_empty() {
+        continue;
+      }
+      let siblings = step_levels
+        .iter()
+        .fold(&mut project.steps, |steps, (_, index)| &mut steps[*index].children);
+      siblings.push(StepState { id: heading_id(&heading), text: heading.title(), done, tags: heading.tags.clone(), ..Default::default() });
+      step_levels.push((heading.level, siblings.len() - 1));
+    }
+  }
+
+  for project in &mut projects {
+    for note in &mut project.notes {
+      note.body = note.body.trim().to_string();
+    }
+  }
+  projects
+}
+
+fn merge_items<T>(
+  current: Vec<T>,
+  parsed: Vec<T>,
+  id: fn(&T) -> Option<&EntityId>,
+  update: fn(&mut T, T) -> bool,
+  updated: &mut usize,
+  added: &mut usize,
+) -> Vec<T> {
+  let mut remaining: Vec<Option<T>> = current.into_iter().map(Some).collect();
+  let mut merged = Vec::with_capacity(remaining.len());
+  for item in parsed {
+    let found = id(&item).and_then(|item_id| {
+      remaining
+        .iter()
+        .position(|existing| existing.as_ref().is_some_and(|existing| id(existing) == Some(item_id)))
+    });
+    match found.and_then(|index| remaining[index].take()) {
+      Some(mut existing) => {
+        if update(&mut existing, item) {
+          *updated += 1;
+        }
+        merged.push(existing);
+      }
+      None => {
+        merged.push(item);
+        *added += 1;
+      }
+    }
+  }
+  merged.extend(remaining.into_iter().flatten());
+  merged
+}
+
+fn update_note(note: &mut NoteState, parsed: NoteState) -> bool {
+  let changed = note.title != parsed.title || note.body != parsed.body || note.tags != parsed.tags;
+  note.title = parsed.title;
+  note.body = parsed.body;
+  note.tags = parsed.tags;
+  changed
+}
+
+fn update_step(step: &mut StepState, parsed: StepState) -> bool {
+  let mut changed = step.text != parsed.text || step.done != parsed.done || step.tags != parsed.tags;
+  step.text = parsed.text;
+  step.done = parsed.done;
+  step.tags = parsed.tags;
+  let (mut updated, mut added) = (0, 0);
+  let children = std::mem::take(&mut step.children);
+  step.children = merge_items(children, parsed.children, |step| step.id.as_ref(), update_step, &mut updated, &mut added);
+  changed |= updated + added > 0;
+  changed
+}
+
+fn assign_missing_ids(project: &mut ProjectState) {
+  project.id.get_or_insert_with(EntityId::generate);
+  for note in &mut project.notes {
+    note.id.get_or_insert_with(EntityId::generate);
+  }
+  step_tree::for_each_mut(&mut project.steps, &mut |step| {
+    step.id.get_or_insert_with(EntityId::generate);
+  });
+}
+
+pub(crate) fn apply_org(mut state: AppState, content: &str) -> Result<OrgImportResult, String> {
+  let parsed = parse_org(content);
+  if parsed.is_empty() {
+    return Err("org file has no project headings".to_string());
+  }
+
+  let mut updated = 0;
+  let mut added = 0;
+  for mut project in parsed {
+    let index = match &project.id {
+      Some(id) => state.projects.iter().position(|existing| existing.id.as_ref() == Some(id)),
+      None => state.projects.iter().position(|existing| existing.name.trim() == project.name),
+    };
+    assign_missing_ids(&mut project);
+    let Some(index) = index else {
+      added += 1 + project.notes.len() + step_tree::count(&project.steps);
+      state.projects.push(project);
+      continue;
+    };
+
+    let existing = &mut state.projects[index];
+    if existing.name != project.name
+      || existing.description != project.description
+      || existing.status != project.status
+      || existing.pinned != project.pinned
+      || existing.tags != project.tags
+    {
+      updated += 1;
+    }
+    existing.name = project.name;
+    existing.description = project.description;
+    existing.status = project.status;
+    existing.pinned = project.pinned;
+    existing.tags = project.tags;
+    let notes = std::mem::take(&mut existing.notes);
+    existing.notes = merge_items(notes, project.notes, |note| note.id.as_ref(), update_note, &mut updated, &mut added);
+    let steps = std::mem::take(&mut existing.steps);
+    existing.steps = merge_items(steps, project.steps, |step| step.id.as_ref(), update_step, &mut updated, &mut added);
+  }
+
+  Ok(OrgImportResult { state, updated, added })
+}
+
+#[tauri::command]
+pub(crate) fn export_org(app: tauri::AppHandle, state: AppState, project_id: Option<EntityId>) -> Result<String, String> {
+  let projects = state.select_projects(project_id.as_ref())?;
+  let (title, stem) = match projects.first() {
+    Some(project) if project_id.is_some() => (project.name.clone(), file_stem(&project.name, "project")),
+    _ => (doc_labels(&state.settings.language).workspace.to_string(), "workspace".to_string()),
+  };
+  let content = render_org(&projects, &title);
+  write_export_file(&app, &stem, ".org", content.as_bytes())
+}
+
+#[tauri::command]
+pub(crate) fn import_org(state: AppState, content: String) -> Result<OrgImportResult, String> {
+  apply_org(state, &content)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn sample() -> AppState {
+    serde_json::from_str(
+      r#"{"projects":[{"id":"p1","name":"Alpha","description":"desc\n* star line\n# hash","status":"В работе","pinned":true,"tags":["work"],
+        "notes":[{"id":7,"title":"N1","body":"intro\n\n## Sub\nmore\n```\n# code\n```\n* bullet"},{"id":8,"title":"N2","body":""}],
+        "steps":[{"id":"s1","text":"one","done":true,"children":[{"id":"s3","text":"nested"}]},{"id":"s2","text":"two"}]},
+        {"id":"p2","name":"Other"}]}"#,
+    )
+    .unwrap()
+  }
+
+  fn render(state: &AppState) -> String {
+    render_org(&state.select_projects(None).unwrap(), "T")
+  }
+
+  #[test]
+  fn round_trips_projects_and_escapes_headings_in_bodies() {
+    let state = sample();
+    let rendered = render(&state);
+    assert!(rendered.contains("\n,* star line\n") && rendered.contains("\n**** Sub\n"));
+    assert_eq!(serde_json::to_value(parse_org(&rendered)).unwrap(), serde_json::to_value(&state.projects).unwrap());
+  }
+
+  #[test]
+  fn round_trips_titles_that_start_with_keywords() {
+    let state: AppState = serde_json::from_str(
+      r###"{"projects":[{"id":"p1","name":"TODO list",
+        "notes":[{"id":"n1","title":"TODO later","body":"## DONE part"},{"id":"n2","title":"[#A] DONE","body":""}],
+        "steps":[{"id":"s1","text":"DONE twice","done":false},{"id":"s2","text":"TODO once","done":true}]}]}"###,
+    )
+    .unwrap();
+    let rendered = render(&state);
+    assert!(rendered.contains("* TODO list\n:PROPERTIES:\n:ID: p1\n:KIND: project\n"));
+    assert_eq!(serde_json::to_value(parse_org(&rendered)).unwrap(), serde_json::to_value(&state.projects).unwrap());
+  }
+
+  #[test]
+  fn applies_edits_by_id_and_appends_new_items_with_ids() {
+    let state = sample();
+    let edited = render(&state)
+      .replace("** TODO two", "** DONE two renamed")
+      .replace("** N2", "** TODO new step\n*** DONE sub\n** N2 :tag:")
+      + "* New project\n** Idea\ntext\n";
+    let result = apply_org(state, &edited).unwrap();
+    assert_eq!((result.updated, result.added), (2, 3));
+    let alpha = &result.state.projects[0];
+    assert_eq!(alpha.notes[1].tags, ["tag"]);
+    assert_eq!(alpha.steps.iter().map(|step| step.text.as_str()).collect::<Vec<_>>(), ["new step", "one", "two renamed"]);
+    assert!(alpha.steps[2].done);
+    assert_eq!(alpha.steps[1].children[0].id, Some(EntityId::parse("s3")));
+    let new_project = &result.state.projects[2];
+    assert_eq!(new_project.name, "New project");
+    assert_eq!(new_project.notes[0].body, "text");
+    assert!(new_project.id.is_some() && new_project.notes[0].id.is_some());
+    assert!(alpha.steps[0].id.is_some() && alpha.steps[0].children[0].id.is_some());
+  }
+
+  #[test]
+  fn matches_projects_without_ids_by_name() {
+    let result = apply_org(sample(), "* Other\n** TODO fresh\n").unwrap();
+    assert_eq!(result.state.projects.len(), 2);
+    assert_eq!(result.state.projects[1].steps[0].text, "fresh");
+    assert_eq!(result.state.projects[1].id, Some(EntityId::parse("p2")));
+  }
+
+  #[test]
+  fn rejects_files_without_project_headings() {
+    assert!(apply_org(sample(), "").is_err());
+    assert!(apply_org(sample(), "#+TITLE: x\nplain text\n").is_err());
+    assert!(parse_org("*not a heading\n**\n").is_empty());
+  }
+}
@@ -0,0 +1,320 @@
+use crate::labels::{DocLabels, doc_labels};
+use crate::{AppState, EntityId, ProjectState, file_stem, step_tree, write_export_file};
+use printpdf::path::PaintMode;
+use printpdf::{Color, IndirectFontRef, Mm, PdfDocument, PdfDocumentReference, PdfLayerReference, Rect, Rgb};
+
+const FONT_REGULAR: &[u8] = include_bytes!("../fonts/DejaVuSans.ttf");
+const FONT_BOLD: &[u8] = include_bytes!("../fonts/DejaVuSans-Bold.ttf");
+
+const PAGE_WIDTH: f32 = 210.0;
+const PAGE_HEIGHT: f32 = 297.0;
+const MARGIN: f32 = 20.0;
+const CONTENT_WIDTH: f32 = PAGE_WIDTH - MARGIN * 2.0;
+const PT_TO_MM: f32 = 0.352_778;
+const LINE_SPACING: f32 = 1.35;
+const STEP_INDENT: f32 = 6.0;
+const MAX_STEP_DEPTH: usize = 8;
+
+const TEXT_COLOR: (f32, f32, f32) = (0.08, 0.09, 0.14);
+const MUTED_COLOR: (f32, f32, f32) = (0.38, 0.44, 0.55);
+const ACCENT_COLOR: (f32, f32, f32) = (0.36, 0.66, 1.0);
+const TRACK_COLOR: (f32, f32, f32) = (0.87, 0.9, 0.95);
+
+struct PdfFont {
+  reference: IndirectFontRef,
+  face: ttf_parser::Face<'static>,
+}
+
+impl PdfFont {
+  fn load(doc: &PdfDocumentReference, data: &'static [u8]) -> Result<Self, String> {
+    let reference = doc.add_external_font(data).map_err(|err| err.to_string())?;
+    let face = ttf_parser::Face::parse(data, 0).map_err(|err| err.to_string())?;
+    Ok(Self { reference, face })
+  }
+
+  fn text_width(&self, text: &str, size: f32) -> f32 {
+    let units = f32::from(self.face.units_per_em());
+    let advance: f32 = text
+      .chars()
+      .filter_map(|ch| self.face.glyph_index(ch))
+      .map(|glyph| f32::from(self.face.glyph_hor_advance(glyph).unwrap_or(0)))
+      .sum();
+    advance / units * size * PT_TO_MM
+  }
+
+  fn wrap(&self, text: &str, size: f32, max_width: f32) -> Vec<String> {
+    let mut lines = Vec::new();
+    for paragraph in text.lines() {
+      let mut line = String::new();
+      for word in paragraph.split_whitespace() {
+        let candidate = if line.is_empty() { word.to_string() } else { format!("{line} {word}") };
+        if self.text_width(&candidate, size) <= max_width {
+          line = candidate;
+          continue;
+        }
+        if !line.is_empty() {
+          lines.push(std::mem::take(&mut line));
+        }
+        for ch in word.chars() {
+          line.push(ch);
+          if self.text_width(&line, size) > max_width && line.chars().count() > 1 {
+            line.pop();
+            lines.push(std::mem::take(&mut line));
+            line.push(ch);
+          }
+        }
+      }
+      lines.push(line);
+    }
+    lines
+  }
+}
+
+fn color((r, g, b): (f32, f32, f32)) -> Color {
+  Color::Rgb(Rgb::new(r, g, b, None))
+}
+
+fn line_height(size: f32) -> f32 {
+  size * LINE_SPACING * PT_TO_MM
+}
+
+struct PdfWriter {
+  doc: PdfDocumentReference,
+  layer: PdfLayerReference,
+  regular: PdfFont,
+  bold: PdfFont,
+  y: f32,
+}
+
+impl PdfWriter {
+  fn new(title: &str) -> Result<Self, String> {
+    let (doc, page, layer) = PdfDocument::new(title, Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "content");
+    let layer = doc.get_page(page).get_layer(layer);
+    let regular = PdfFont::load(&doc, FONT_REGULAR)?;
+    let bold = PdfFont::load(&doc, FONT_BOLD)?;
+    Ok(Self { doc, layer, regular, bold, y: PAGE_HEIGHT - MARGIN })
+  }
+
+  fn new_page(&mut self) {
+    let (page, layer) = self.doc.add_page(Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "content");
+    self.layer = self.doc.get_page(page).get_layer(layer);
+    self.y = PAGE_HEIGHT - MARGIN;
+  }
+
+  fn ensure(&mut self, height: f32) {
+    if self.y - height < MARGIN {
+      self.new_page();
+    }
+  }
+
+  fn space(&mut self, height: f32) {
+    self.y -= height;
+  }
+
+  fn text(&mut self, text: &str, size: f32, bold: bool, indent: f32, rgb: (f32, f32, f32)) {
+    let font = if bold { &self.bold } else { &self.regular };
+    let lines = font.wrap(text, size, CONTENT_WIDTH - indent);
+    let height = line_height(size);
+    self.layer.set_fill_color(color(rgb));
+    for line in lines {
+      if self.y - height < MARGIN {
+        self.new_page();
+        self.layer.set_fill_color(color(rgb));
+      }
+      self.y -= height;
+      if !line.is_empty() {
+        let font = if bold { &self.bold.reference } else { &self.regular.reference };
+        self.layer.use_text(line, size, Mm(MARGIN + indent), Mm(self.y + height * 0.22), font);
+      }
+    }
+  }
+
+  fn heading(&mut self, text: &str, size: f32) {
+    self.ensure(line_height(size) * 3.0);
+    self.space(line_height(size) * 0.4);
+    self.text(text, size, true, 0.0, TEXT_COLOR);
+    self.space(line_height(size) * 0.3);
+  }
+
+  fn rect(&self, x: f32, y: f32, width: f32, height: f32, mode: PaintMode, rgb: (f32, f32, f32)) {
+    self.layer.set_fill_color(color(rgb));
+    self.layer.set_outline_color(color(rgb));
+    self.layer.set_outline_thickness(0.8);
+    self.layer.add_rect(Rect::new(Mm(x), Mm(y), Mm(x + width), Mm(y + height)).with_mode(mode));
+  }
+
+  fn progress_bar(&mut self, done: usize, total: usize, labels: &DocLabels) {
+    let bar_height = 4.0;
+    self.ensure(bar_height + line_height(10.0) * 2.0);
+    self.y -= bar_height;
+    self.rect(MARGIN, self.y, CONTENT_WIDTH, bar_height, PaintMode::Fill, TRACK_COLOR);
+    if total > 0 && done > 0 {
+      let filled = CONTENT_WIDTH * done as f32 / total as f32;
+      self.rect(MARGIN, self.y, filled, bar_height, PaintMode::Fill, ACCENT_COLOR);
+    }
+    self.space(1.5);
+    let caption = format!("{}: {}", labels.progress, labels.progress_caption(done, total));
+    self.text(&caption, 10.0, false, 0.0, MUTED_COLOR);
+  }
+
+  fn checkbox_item(&mut self, text: &str, done: bool, depth: usize) {
+    let size = 11.0;
+    let box_size = 3.6;
+    let offset = depth.min(MAX_STEP_DEPTH) as f32 * STEP_INDENT;
+    let indent = offset + box_size + 3.0;
+    let first_line = line_height(size);
+    self.ensure(first_line);
+    let top = self.y;
+    self.rect(MARGIN + offset, top - first_line + 0.9, box_size, box_size, PaintMode::Stroke, MUTED_COLOR);
+    if done {
+      self.rect(MARGIN + offset + 0.8, top - first_line + 1.7, box_size - 1.6, box_size - 1.6, PaintMode::Fill, ACCENT_COLOR);
+    }
+    let rgb = if done { MUTED_COLOR } else { TEXT_COLOR };
+    self.text(text, size, false, indent, rgb);
+    self.space(1.2);
+  }
+
+  fn finish(self) -> Result<Vec<u8>, String> {
+    self.doc.save_to_bytes().map_err(|err| err.to_string())
+  }
+}
+
+fn write_title_page(writer: &mut PdfWriter, project: &ProjectState, labels: &DocLabels) {
+  writer.space(50.0);
+  writer.text(labels.app_title, 11.0, false, 0.0, ACCENT_COLOR);
+  writer.space(3.0);
+  writer.text(&project.name, 26.0, true, 0.0, TEXT_COLOR);
+  writer.space(4.0);
+
+  let status = project.status.trim();
+  let status = if status.is_empty() { labels.status_not_set } else { status };
+  writer.text(&format!("{}: {status}", labels.project_status), 12.0, false, 0.0, TEXT_COLOR);
+  if project.pinned {
+    writer.text(labels.pinned, 12.0, false, 0.0, MUTED_COLOR);
+  }
+  writer.space(6.0);
+
+  if !project.description.trim().is_empty() {
+    writer.text(project.description.trim(), 12.0, false, 0.0, TEXT_COLOR);
+    writer.space(6.0);
+  }
+
+  let (done, total) = project.step_progress();
+  writer.progress_bar(done, total, labels);
+  writer.space(4.0);
+  let generated = chrono::Local::now().format("%Y-%m-%d %H:%M");
+  writer.text(&format!("{}: {generated}", labels.generated), 10.0, false, 0.0, MUTED_COLOR);
+}
+
+fn write_notes(writer: &mut PdfWriter, project: &ProjectState, labels: &DocLabels) {
+  writer.heading(labels.project_notes, 18.0);
+  if project.notes.is_empty() {
+    writer.text(labels.no_notes, 11.0, false, 0.0, MUTED_COLOR);
+    return;
+  }
+  for note in &project.notes {
+    writer.ensure(line_height(14.0) + line_height(11.0) * 2.0);
+    writer.space(2.0);
+    writer.text(&note.title, 14.0, true, 0.0, TEXT_COLOR);
+    if !note.body.trim().is_empty() {
+      writer.space(1.0);
+      writer.text(note.body.trim_end(), 11.0, false, 0.0, TEXT_COLOR);
+    }
+    writer.space(4.0);
+  }
+}
+
+fn write_steps(writer: &mut PdfWriter, project: &ProjectState, labels: &DocLabels) {
+  writer.heading(labels.project_steps, 18.0);
+  if project.steps.is_empty() {
+    writer.text(labels.no_steps, 11.0, false, 0.0, MUTED_COLOR);
+    return;
+  }
+  for (depth, step) in step_tree::flatten(&project.steps) {
+    writer.checkbox_item(&step.text, step.done, depth);
+  }
+
+  let (done, total) = project.step_progress();
+  writer.space(4.0);
+  writer.ensure(line_height(14.0) * 2.0 + 12.0);
+  writer.text(labels.summary, 14.0, true, 0.0, TEXT_COLOR);
+  writer.space(1.0);
+  writer.progress_bar(done, total, labels);
+}
+
+pub(crate) fn render_project_pdf(project: &ProjectState, language: &str) -> Result<Vec<u8>, String> {
+  let labels = doc_labels(language);
+  let mut writer = PdfWriter::new(&project.name)?;
+  write_title_page(&mut writer, project, labels);
+  writer.new_page();
+  write_notes(&mut writer, project, labels);
+  writer.space(6.0);
+  write_steps(&mut writer, project, labels);
+  writer.finish()
+}
+
+#[tauri::command]
+pub(crate) fn export_pdf_report(app: tauri::AppHandle, state: AppState, project_id: EntityId) -> Result<String, String> {
+  let projects = state.select_projects(Some(&project_id))?;
+  let project = projects[0];
+  let bytes = render_project_pdf(project, &state.settings.language)?;
+  write_export_file(&app, &file_stem(&project.name, "project"), ".pdf", &bytes)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::StepState;
+
+  fn font() -> PdfFont {
+    PdfFont::load(&PdfDocument::empty("test"), FONT_REGULAR).unwrap()
+  }
+
+  fn page_count(bytes: &[u8]) -> usize {
+    String::from_utf8_lossy(bytes).matches("/Type/Page/").count()
+  }
+
+  #[test]
+  fn wraps_words_and_breaks_overlong_ones() {
+    let font = font();
+    let lines = font.wrap("Шаг номер один с довольно длинным текстом\n\nконец", 11.0, 40.0);
+    assert!(lines.len() > 3);
+    assert!(lines.iter().all(|line| font.text_width(line, 11.0) <= 40.0));
+    assert_eq!(lines.iter().filter(|line| line.is_empty()).count(), 1);
+    assert_eq!(lines.last().map(String::as_str), Some("конец"));
+    assert_eq!(lines.join(" ").split_whitespace().collect::<Vec<_>>().join(" "), "Шаг номер один с довольно длинным текстом конец");
+
+    let word = "Ж".repeat(60);
+    let broken = font.wrap(&word, 11.0, 30.0);
+    assert!(broken.len() > 1);
+    assert_eq!(broken.concat(), word);
+    assert!(broken.iter().all(|line| font.text_width(line, 11.0) <= 30.0));
+  }
+
+  #[test]
+  fn renders_a_pdf_and_adds_pages_for_long_projects() {
+    let mut project: ProjectState = serde_json::from_str(
+      r#"{"name":"Проект <А>","status":"В работе","pinned":true,"description":"Описание",
+        "notes":[{"title":"Заметка","body":"Текст"}],"steps":[{"text":"Шаг","done":true,"children":[{"text":"Вложенный"}]}]}"#,
+    )
+    .unwrap();
+    let short = render_project_pdf(&project, "ru").unwrap();
+    assert!(short.starts_with(b"%PDF-"));
+
+    project.description = "Длинное описание проекта ".repeat(30);
+    for index in 0..120 {
+      project.steps.push(StepState { text: format!("Шаг номер {index} с довольно длинным текстом, который переносится"), ..Default::default() });
+    }
+    let long = render_project_pdf(&project, "en").unwrap();
+    assert!(long.starts_with(b"%PDF-"));
+    assert!(long.len() > short.len());
+    let (short_pages, long_pages) = (page_count(&short), page_count(&long));
+    assert_eq!(short_pages, 2);
+    assert!(long_pages > short_pages + 2, "{long_pages} pages");
+  }
+
+  #[test]
+  fn renders_an_empty_project() {
+    assert!(render_project_pdf(&ProjectState::default(), "uk").unwrap().starts_with(b"%PDF-"));
+  }
+}
@@ -0,0 +1,318 @@
+use crate::markup::escape;
+use crate::{AppState, EntityId, ProjectState, file_stem, step_tree, wiki_links, write_export_file};
+use serde::Serialize;
+use std::f64::consts::PI;
+use std::fmt::Write as _;
+
+const LABEL_CHARS: usize = 28;
+const CHAR_WIDTH: f64 = 7.2;
+const NODE_HEIGHT: f64 = 34.0;
+const RING_RADIUS: f64 = 230.0;
+const RING_STEP: f64 = 80.0;
+const NODES_PER_RING: usize = 14;
+const MARGIN: f64 = 40.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum NodeKind {
+  Project,
+  Note,
+  OpenStep,
+  DoneStep,
+}
+
+impl NodeKind {
+  fn colors(self) -> (&'static str, &'static str) {
+    match self {
+      Self::Project => ("#1f3a5f", "#ffffff"),
+      Self::Note => ("#e8eef7", "#1f3a5f"),
+      Self::OpenStep => ("#fff4e0", "#7a4b00"),
+      Self::DoneStep => ("#e3f5e6", "#1e6b2e"),
+    }
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EdgeKind {
+  Contains,
+  NoteLink,
+}
+
+struct MapNode {
+  id: String,
+  label: String,
+  kind: NodeKind,
+}
+
+struct MapEdge {
+  from: usize,
+  to: usize,
+  kind: EdgeKind,
+}
+
+struct MapGraph {
+  nodes: Vec<MapNode>,
+  edges: Vec<MapEdge>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ProjectMap {
+  dot: String,
+  svg: String,
+  path: Option<String>,
+}
+
+fn short_label(text: &str, fallback: &str) -> String {
+  let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+  let text = if text.is_empty() { fallback.to_string() } else { text };
+  if text.chars().count() > LABEL_CHARS {
+    let cut: String = text.chars().take(LABEL_CHARS - 1).collect();
+    format!("{}…", cut.trim_end())
+  } else {
+    text
+  }
+}
+
+fn build_graph(project: &ProjectState) -> MapGraph {
+  let mut nodes = vec![MapNode { id: "project".to_string(), label: short_label(&project.name, "—"), kind: NodeKind::Project }];
+  let mut edges = Vec::new();
+
+  let first_note = nodes.len();
+  for (index, note) in project.notes.iter().enumerate() {
+    edges.push(MapEdge { from: 0, to: nodes.len(), kind: EdgeKind::Contains });
+    nodes.push(MapNode { id: format!("note_{}", index + 1), label: short_label(&note.title, "—"), kind: NodeKind::Note });
+  }
+  let mut parents: Vec<usize> = Vec::new();
+  for (index, (depth, step)) in step_tree::flatten(&project.steps).into_iter().enumerate() {
+    parents.truncate(depth);
+    let kind = if step.done { NodeKind::DoneStep } else { NodeKind::OpenStep };
+    edges.push(MapEdge { from: parents.last().copied().unwrap_or(0), to: nodes.len(), kind: EdgeKind::Contains });
+    parents.push(nodes.len());
+    nodes.push(MapNode { id: format!("step_{}", index + 1), label: short_label(&step.text, "—"), kind });
+  }
+
+  for (index, note) in project.notes.iter().enumerate() {
+    for link in wiki_links::body_links(&note.body) {
+      let title = link.target.to_lowercase();
+      let target = project.notes.iter().position(|candidate| candidate.title.trim().to_lowercase() == title);
+      if let Some(target) = target
+        && target != index
+        && !edges
+          .iter()
+          .any(|edge| edge.kind == EdgeKind::NoteLink && edge.from == first_note + index && edge.to == first_note + target)
+      {
+        edges.push(MapEdge { from: first_note + index, to: first_note + target, kind: EdgeKind::NoteLink });
+      }
+    }
+  }
+  MapGraph { nodes, edges }
+}
+
+fn dot_string(value: &str) -> String {
+  let mut out = String::with_capacity(value.len() + 2);
+  out.push('"');
+  for ch in value.chars() {
+    match ch {
+      '"' => out.push_str("\\\""),
+      '\\' => out.push_str("\\\\"),
+      '\n' => out.push_str("\\n"),
+      _ => out.push(ch),
+    }
+  }
+  out.push('"');
+  out
+}
+
+fn render_dot(graph: &MapGraph) -> String {
+  let mut out = String::from("digraph project {\n  layout=twopi;\n  root=project;\n  overlap=false;\n  splines=true;\n");
+  out.push_str("  node [fontname=\"DejaVu Sans\", fontsize=11, style=\"rounded,filled\", shape=box];\n");
+  out.push_str("  edge [color=\"#9aa5b1\", arrowsize=0.6];\n");
+  for node in &graph.nodes {
+    let (fill, text) = node.kind.colors();
+    let shape = if node.kind == NodeKind::Project { ", shape=ellipse, fontsize=14" } else { "" };
+    let _ = writeln!(
+      out,
+      "  {} [label={}, fillcolor=\"{fill}\", fontcolor=\"{text}\", color=\"{text}\"{shape}];",
+      node.id,
+      dot_string(&node.label)
+    );
+  }
+  for edge in &graph.edges {
+    let from = &graph.nodes[edge.from].id;
+    let to = &graph.nodes[edge.to].id;
+    match edge.kind {
+      EdgeKind::Contains => {
+        let _ = writeln!(out, "  {from} -> {to};");
+      }
+      EdgeKind::NoteLink => {
+        let _ = writeln!(out, "  {from} -> {to} [style=dashed, color=\"#4a6fa5\", constraint=false];");
+      }
+    }
+  }
+  out.push_str("}\n");
+  out
+}
+
+fn node_width(node: &MapNode) -> f64 {
+  let padding = if node.kind == NodeKind::Project { 36.0 } else { 20.0 };
+  node.label.chars().count() as f64 * CHAR_WIDTH + padding
+}
+
+fn layout(graph: &MapGraph) -> Vec<(f64, f64)> {
+  let children = graph.nodes.len() - 1;
+  let mut positions = vec![(0.0, 0.0)];
+  for index in 0..children {
+    let ring = if children > NODES_PER_RING { index % 2 } else { 0 };
+    let radius = RING_RADIUS + ring as f64 * RING_STEP;
+    let angle = -PI / 2.0 + 2.0 * PI * index as f64 / children as f64;
+    positions.push((radius * angle.cos(), radius * angle.sin()));
+  }
+  positions
+}
+
+fn render_svg(graph: &MapGraph) -> String {
+  let positions = layout(graph);
+  let (mut min_x, mut min_y, mut max_x, mut max_y) = (f64::MAX, f64::MAX, f64::MIN, f64::MIN);
+  for (node, (x, y)) in graph.nodes.iter().zip(&positions) {
+    let half = node_width(node) / 2.0;
+    min_x = min_x.min(x - half);
+    max_x = max_x.max(x + half);
+    min_y = min_y.min(y - NODE_HEIGHT / 2.0);
+    max_y = max_y.max(y + NODE_HEIGHT / 2.0);
+  }
+  let (offset_x, offset_y) = (MARGIN - min_x, MARGIN - min_y);
+  let width = max_x - min_x + MARGIN * 2.0;
+  let height = max_y - min_y + MARGIN * 2.0;
+  let point = |index: usize| (positions[index].0 + offset_x, positions[index].1 + offset_y);
+
+  let mut out = format!(
+    "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width:.0}\" height=\"{height:.0}\" viewBox=\"0 0 {width:.0} {height:.0}\" font-family=\"DejaVu Sans, Segoe UI, sans-serif\" font-size=\"12\">\n"
+  );
+  out.push_str("<defs><marker id=\"arrow\" viewBox=\"0 0 10 10\" refX=\"9\" refY=\"5\" markerWidth=\"7\" markerHeight=\"7\" orient=\"auto-start-reverse\"><path d=\"M0,0 L10,5 L0,10 z\" fill=\"#4a6fa5\"/></marker></defs>\n");
+  let _ = writeln!(out, "<rect width=\"{width:.0}\" height=\"{height:.0}\" fill=\"#ffffff\"/>");
+
+  for edge in &graph.edges {
+    let (x1, y1) = point(edge.from);
+    let (x2, y2) = point(edge.to);
+    match edge.kind {
+      EdgeKind::Contains => {
+        let _ = writeln!(out, "<line x1=\"{x1:.1}\" y1=\"{y1:.1}\" x2=\"{x2:.1}\" y2=\"{y2:.1}\" stroke=\"#9aa5b1\" stroke-width=\"1.2\"/>");
+      }
+      EdgeKind::NoteLink => {
+        let (cx, cy) = point(0);
+        let control = ((x1 + x2) / 2.0 * 0.6 + cx * 0.4, (y1 + y2) / 2.0 * 0.6 + cy * 0.4);
+        let (dx, dy) = (control.0 - x2, control.1 - y2);
+        let length = dx.hypot(dy).max(1.0);
+        let (x2, y2) = (x2 + dx / length * NODE_HEIGHT * 0.6, y2 + dy / length * NODE_HEIGHT * 0.6);
+        let _ = writeln!(
+          out,
+          "<path d=\"M{x1:.1},{y1:.1} Q{:.1},{:.1} {x2:.1},{y2:.1}\" fill=\"none\" stroke=\"#4a6fa5\" stroke-width=\"1.4\" stroke-dasharray=\"5 4\" marker-end=\"url(#arrow)\"/>",
+          control.0,
+          control.1
+        );
+      }
+    }
+  }
+
+  for (index, node) in graph.nodes.iter().enumerate() {
+    let (x, y) = point(index);
+    let (fill, text) = node.kind.colors();
+    let node_width = node_width(node);
+    let radius = if node.kind == NodeKind::Project { NODE_HEIGHT / 2.0 } else { 8.0 };
+    let weight = if node.kind == NodeKind::Project { " font-weight=\"bold\"" } else { "" };
+    let _ = writeln!(
+      out,
+      "<g class=\"{}\"><rect x=\"{:.1}\" y=\"{:.1}\" width=\"{node_width:.1}\" height=\"{NODE_HEIGHT}\" rx=\"{radius}\" fill=\"{fill}\" stroke=\"{text}\" stroke-width=\"1\"/><text x=\"{x:.1}\" y=\"{:.1}\" text-anchor=\"middle\" fill=\"{text}\"{weight}>{}</text></g>",
+      node.id,
+      x - node_width / 2.0,
+      y - NODE_HEIGHT / 2.0,
+      y + 4.0,
+      escape(&node.label)
+    );
+  }
+  out.push_str("</svg>\n");
+  out
+}
+
+pub(crate) fn render_project_map(project: &ProjectState) -> (String, String) {
+  let graph = build_graph(project);
+  (render_dot(&graph), render_svg(&graph))
+}
+
+#[tauri::command]
+pub(crate) fn project_map(app: tauri::AppHandle, state: AppState, project_id: EntityId, export: bool) -> Result<ProjectMap, String> {
+  let project = state.select_projects(Some(&project_id))?[0];
+  let (dot, svg) = render_project_map(project);
+  let path = if export {
+    let stem = format!("{}-map", file_stem(&project.name, "project"));
+    Some(write_export_file(&app, &stem, ".svg", svg.as_bytes())?)
+  } else {
+    None
+  };
+  Ok(ProjectMap { dot, svg, path })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn sample() -> ProjectState {
+    serde_json::from_str(
+      r#"{"id":1,"name":"Проект \"Альфа\" <&>","notes":[
+        {"id":2,"title":"Заметка","body":"см. [[вторая]], [[Вторая|ещё раз]], [[Заметка]] и [[Нет]]"},
+        {"id":3,"title":"Вторая","body":"`[[Заметка]]`"}],
+        "steps":[{"id":4,"text":"Очень длинный шаг который точно не поместится","done":true,"children":[{"text":"  "}]},{"text":"Шаг 2"}]}"#,
+    )
+    .unwrap()
+  }
+
+  #[test]
+  fn builds_nodes_for_notes_and_nested_steps() {
+    let graph = build_graph(&sample());
+    let nodes: Vec<(&str, &str, NodeKind)> = graph.nodes.iter().map(|node| (node.id.as_str(), node.label.as_str(), node.kind)).collect();
+    assert_eq!(
+      nodes,
+      [
+        ("project", "Проект \"Альфа\" <&>", NodeKind::Project),
+        ("note_1", "Заметка", NodeKind::Note),
+        ("note_2", "Вторая", NodeKind::Note),
+        ("step_1", "Очень длинный шаг который т…", NodeKind::DoneStep),
+        ("step_2", "—", NodeKind::OpenStep),
+        ("step_3", "Шаг 2", NodeKind::OpenStep),
+      ]
+    );
+    let edges: Vec<(usize, usize, EdgeKind)> = graph.edges.iter().map(|edge| (edge.from, edge.to, edge.kind)).collect();
+    assert_eq!(
+      edges,
+      [
+        (0, 1, EdgeKind::Contains),
+        (0, 2, EdgeKind::Contains),
+        (0, 3, EdgeKind::Contains),
+        (3, 4, EdgeKind::Contains),
+        (0, 5, EdgeKind::Contains),
+        (1, 2, EdgeKind::NoteLink),
+      ]
+    );
+  }
+
+  #[test]
+  fn renders_escaped_dot_and_well_formed_svg() {
+    let (dot, svg) = render_project_map(&sample());
+    assert!(dot.starts_with("digraph project {"));
+    assert!(dot.contains(r#"project [label="Проект \"Альфа\" <&>""#));
+    assert!(dot.contains("note_1 -> note_2 [style=dashed"));
+    let document = roxmltree::Document::parse(&svg).unwrap();
+    assert_eq!(document.root_element().tag_name().name(), "svg");
+    let labels: Vec<&str> = document.descendants().filter(|node| node.has_tag_name("text")).filter_map(|node| node.text()).collect();
+    assert_eq!(labels[0], "Проект \"Альфа\" <&>");
+    assert_eq!(document.descendants().filter(|node| node.has_tag_name("path") && node.attribute("marker-end").is_some()).count(), 1);
+  }
+
+  #[test]
+  fn maps_an_empty_project() {
+    let (dot, svg) = render_project_map(&ProjectState::default());
+    assert!(dot.contains("project [label=\"—\""));
+    assert!(roxmltree::Document::parse(&svg).is_ok());
+    assert!(!svg.contains("NaN"));
+  }
+}
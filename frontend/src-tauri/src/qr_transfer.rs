@@ -0,0 +1,251 @@
+use base64::Engine;
+use base64::engine::general_purpose::{STANDARD as BASE64, URL_SAFE_NO_PAD};
+use flate2::Compression;
+use flate2::read::DeflateDecoder;
+use flate2::write::DeflateEncoder;
+use image::{ImageEncoder, Luma};
+use qrcode::render::svg;
+use qrcode::{EcLevel, QrCode};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::io::{Read, Write};
+
+const PREFIX: &str = "PNS1";
+const CHUNK_LEN: usize = 900;
+const MAX_PARTS: usize = 64;
+const MIN_IMAGE_SIZE: u32 = 480;
+const MAX_DECODED_LEN: u64 = 16 * 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum QrImageFormat {
+  Png,
+  Svg,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct QrCodePart {
+  index: usize,
+  total: usize,
+  payload: String,
+  mime: String,
+  image: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct QrDecodeResult {
+  complete: bool,
+  received: usize,
+  total: usize,
+  missing: Vec<usize>,
+  content: Option<String>,
+}
+
+struct ScannedPart<'a> {
+  transfer: &'a str,
+  index: usize,
+  total: usize,
+  data: &'a str,
+}
+
+fn transfer_id(data: &str) -> String {
+  hex::encode(&Sha256::digest(data.as_bytes())[..4])
+}
+
+fn split_payloads(content: &str) -> Result<Vec<String>, String> {
+  let mut encoder = DeflateEncoder::new(Vec::new(), Compression::best());
+  encoder.write_all(content.as_bytes()).map_err(|err| err.to_string())?;
+  let compressed = encoder.finish().map_err(|err| err.to_string())?;
+  let data = URL_SAFE_NO_PAD.encode(compressed);
+  let id = transfer_id(&data);
+
+  let chunks: Vec<&str> = data
+    .as_bytes()
+    .chunks(CHUNK_LEN)
+    .map(|chunk| std::str::from_utf8(chunk).unwrap_or_default())
+    .collect();
+  if chunks.len() > MAX_PARTS {
+    return Err(format!("project is too large for QR transfer ({} codes needed)", chunks.len()));
+  }
+  let total = chunks.len();
+  Ok(chunks
+    .iter()
+    .enumerate()
+    .map(|(index, chunk)| format!("{PREFIX}:{id}:{}/{total}:{chunk}", index + 1))
+    .collect())
+}
+
+fn render(payload: &str, format: QrImageFormat) -> Result<(String, String), String> {
+  let code = QrCode::with_error_correction_level(payload.as_bytes(), EcLevel::M).map_err(|err| err.to_string())?;
+  match format {
+    QrImageFormat::Svg => {
+      let image = code
+        .render::<svg::Color>()
+        .min_dimensions(MIN_IMAGE_SIZE, MIN_IMAGE_SIZE)
+        .quiet_zone(true)
+        .build();
+      Ok(("image/svg+xml".to_string(), BASE64.encode(image)))
+    }
+    QrImageFormat::Png => {
+      let image = code
+        .render::<Luma<u8>>()
+        .min_dimensions(MIN_IMAGE_SIZE, MIN_IMAGE_SIZE)
+        .quiet_zone(true)
+        .build();
+      let mut png = Vec::new();
+      image::codecs::png::PngEncoder::new(&mut png)
+        .write_image(image.as_raw(), image.width(), image.height(), image::ExtendedColorType::L8)
+        .map_err(|err| err.to_string())?;
+      Ok(("image/png".to_string(), BASE64.encode(png)))
+    }
+  }
+}
+
+pub(crate) fn encode_qr_parts(content: &str, format: QrImageFormat) -> Result<Vec<QrCodePart>, String> {
+  let payloads = split_payloads(content)?;
+  let total = payloads.len();
+  payloads
+    .into_iter()
+    .enumerate()
+    .map(|(index, payload)| {
+      let (mime, image) = render(&payload, format)?;
+      Ok(QrCodePart { index: index + 1, total, payload, mime, image })
+    })
+    .collect()
+}
+
+fn parse_part(text: &str) -> Result<ScannedPart<'_>, String> {
+  let invalid = || "not a Project Notes Studio QR code".to_string();
+  let mut fields = text.trim().splitn(4, ':');
+  if fields.next() != Some(PREFIX) {
+    return Err(invalid());
+  }
+  let transfer = fields.next().ok_or_else(invalid)?;
+  let (index, total) = fields.next().and_then(|position| position.split_once('/')).ok_or_else(invalid)?;
+  let data = fields.next().ok_or_else(invalid)?;
+  let index: usize = index.parse().map_err(|_| invalid())?;
+  let total: usize = total.parse().map_err(|_| invalid())?;
+  if index == 0 || index > total || total > MAX_PARTS {
+    return Err(invalid());
+  }
+  Ok(ScannedPart { transfer, index, total, data })
+}
+
+pub(crate) fn decode_qr_parts(parts: &[String]) -> Result<QrDecodeResult, String> {
+  let mut transfer: Option<(&str, usize)> = None;
+  let mut received: BTreeMap<usize, &str> = BTreeMap::new();
+  for text in parts.iter().filter(|text| !text.trim().is_empty()) {
+    let part = parse_part(text)?;
+    match transfer {
+      Some((id, total)) if id != part.transfer || total != part.total => {
+        return Err("QR codes belong to different transfers".to_string());
+      }
+      _ => transfer = Some((part.transfer, part.total)),
+    }
+    received.insert(part.index, part.data);
+  }
+  let Some((id, total)) = transfer else {
+    return Err("no QR codes scanned".to_string());
+  };
+
+  let missing: Vec<usize> = (1..=total).filter(|index| !received.contains_key(index)).collect();
+  if !missing.is_empty() {
+    return Ok(QrDecodeResult { complete: false, received: received.len(), total, missing, content: None });
+  }
+
+  let data: String = received.values().copied().collect();
+  if transfer_id(&data) != id {
+    return Err("QR transfer is damaged, scan the codes again".to_string());
+  }
+  let compressed = URL_SAFE_NO_PAD.decode(data).map_err(|_| "QR transfer is damaged".to_string())?;
+  let mut decoded = Vec::new();
+  DeflateDecoder::new(compressed.as_slice())
+    .take(MAX_DECODED_LEN + 1)
+    .read_to_end(&mut decoded)
+    .map_err(|_| "QR transfer is damaged".to_string())?;
+  if decoded.len() as u64 > MAX_DECODED_LEN {
+    return Err("QR transfer is too large".to_string());
+  }
+  let content = String::from_utf8(decoded).map_err(|_| "QR transfer is damaged".to_string())?;
+  Ok(QrDecodeResult { complete: true, received: total, total, missing, content: Some(content) })
+}
+
+#[tauri::command]
+pub(crate) fn encode_project_qr(content: String, format: QrImageFormat) -> Result<Vec<QrCodePart>, String> {
+  encode_qr_parts(&content, format)
+}
+
+#[tauri::command]
+pub(crate) fn decode_project_qr(parts: Vec<String>) -> Result<QrDecodeResult, String> {
+  decode_qr_parts(&parts)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn noisy_content(items: usize) -> String {
+    (0..items).map(|index| format!("{{\"step\":\"{}\"}},", hex::encode(Sha256::digest(index.to_string())))).collect()
+  }
+
+  #[test]
+  fn splits_into_chunks_and_reassembles_in_any_order() {
+    let content = noisy_content(40);
+    let payloads = split_payloads(&content).unwrap();
+    assert!(payloads.len() > 1);
+    assert!(payloads.iter().all(|payload| payload.len() <= CHUNK_LEN + 32 && payload.starts_with("PNS1:")));
+    let mut scanned = payloads.clone();
+    scanned.reverse();
+    scanned.push(payloads[0].clone());
+    scanned.push("  ".to_string());
+    let result = decode_qr_parts(&scanned).unwrap();
+    assert!(result.complete);
+    assert_eq!(result.content.as_deref(), Some(content.as_str()));
+  }
+
+  #[test]
+  fn reports_missing_parts() {
+    let payloads = split_payloads(&noisy_content(40)).unwrap();
+    let result = decode_qr_parts(&payloads[1..]).unwrap();
+    assert!(!result.complete && result.content.is_none());
+    assert_eq!((result.received, result.missing.as_slice()), (payloads.len() - 1, [1].as_slice()));
+  }
+
+  #[test]
+  fn rejects_foreign_mixed_and_damaged_codes() {
+    let first = split_payloads(&noisy_content(40)).unwrap();
+    let second = split_payloads(&noisy_content(41)).unwrap();
+    assert!(decode_qr_parts(&[first[0].clone(), second[1].clone()]).is_err_and(|err| err.contains("different transfers")));
+    for text in ["https://example.com", "PNS1:abcd:0/2:x", "PNS1:abcd:3/2:x", "PNS1:abcd:1/65:x", "PNS1:abcd:1-2:x", "PNS1:abcd"] {
+      assert!(decode_qr_parts(&[text.to_string()]).is_err(), "{text}");
+    }
+    assert!(decode_qr_parts(&[]).is_err());
+    let mut damaged = first.clone();
+    damaged[0].push('x');
+    assert!(decode_qr_parts(&damaged).is_err_and(|err| err.contains("damaged")));
+  }
+
+  #[test]
+  fn refuses_projects_that_need_too_many_codes() {
+    assert!(split_payloads(&noisy_content(1500)).is_err_and(|err| err.contains("too large")));
+  }
+
+  #[test]
+  fn rejects_transfers_that_expand_past_the_limit() {
+    let payloads = split_payloads(&"a".repeat(MAX_DECODED_LEN as usize + 1)).unwrap();
+    assert!(decode_qr_parts(&payloads).is_err_and(|err| err.contains("too large")));
+  }
+
+  #[test]
+  fn renders_png_and_svg_codes() {
+    let parts = encode_qr_parts("{\"a\":1}", QrImageFormat::Png).unwrap();
+    assert_eq!((parts.len(), parts[0].index, parts[0].total, parts[0].mime.as_str()), (1, 1, 1, "image/png"));
+    let png = image::load_from_memory(&BASE64.decode(&parts[0].image).unwrap()).unwrap();
+    assert!(png.width() >= MIN_IMAGE_SIZE);
+    let svg = encode_qr_parts("{\"a\":1}", QrImageFormat::Svg).unwrap();
+    assert!(String::from_utf8(BASE64.decode(&svg[0].image).unwrap()).unwrap().contains("<svg"));
+  }
+}
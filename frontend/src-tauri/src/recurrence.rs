@@ -0,0 +1,517 @@
+use crate::step_tree::{find_path, for_each_mut, list_at, step_at_mut, sync_parents};
+use crate::time_zone::{Zone, resolve_time};
+use crate::{AppState, CompletionState, EntityId, StepState};
+use chrono::{DateTime, Datelike, Duration, Months, NaiveDate, NaiveDateTime, NaiveTime, Utc, Weekday};
+use serde::Serialize;
+
+const WEEKDAYS: [(&str, Weekday); 7] = [
+  ("MO", Weekday::Mon),
+  ("TU", Weekday::Tue),
+  ("WE", Weekday::Wed),
+  ("TH", Weekday::Thu),
+  ("FR", Weekday::Fri),
+  ("SA", Weekday::Sat),
+  ("SU", Weekday::Sun),
+];
+const SEARCH_LIMIT: u32 = 1000;
+const MAX_INTERVAL: u32 = 366;
+const PREVIEW_COUNT: usize = 3;
+const DATE_FORMAT: &str = "%Y-%m-%d";
+const DATE_TIME_FORMAT: &str = "%Y-%m-%dT%H:%M";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Frequency {
+  Daily,
+  Weekly,
+  Monthly,
+  Yearly,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Rule {
+  frequency: Frequency,
+  interval: u32,
+  by_day: Vec<(Option<i32>, Weekday)>,
+  by_month_day: Vec<i32>,
+  count: Option<usize>,
+  until: Option<NaiveDate>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Due {
+  at: NaiveDateTime,
+  has_time: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CompletionResult {
+  state: AppState,
+  next_due_at: Option<String>,
+}
+
+fn parse_number<T: std::str::FromStr>(key: &str, value: &str) -> Result<T, String> {
+  value.parse().map_err(|_| format!("invalid {key} value: {value}"))
+}
+
+fn parse_weekday(value: &str) -> Result<(Option<i32>, Weekday), String> {
+  let split = value.char_indices().rev().nth(1).map_or(0, |(index, _)| index);
+  let (ordinal, code) = value.split_at(split);
+  let weekday = WEEKDAYS
+    .iter()
+    .find(|(name, _)| *name == code)
+    .map(|(_, weekday)| *weekday)
+    .ok_or_else(|| format!("invalid BYDAY value: {value}"))?;
+  let ordinal = match ordinal.trim_start_matches('+') {
+    "" => None,
+    number => {
+      let number: i32 = parse_number("BYDAY", number)?;
+      if number == 0 || number.abs() > 5 {
+        return Err(format!("invalid BYDAY value: {value}"));
+      }
+      Some(number)
+    }
+  };
+  Ok((ordinal, weekday))
+}
+
+fn parse_until(value: &str) -> Result<NaiveDate, String> {
+  value
+    .get(..8)
+    .and_then(|date| NaiveDate::parse_from_str(date, "%Y%m%d").ok())
+    .ok_or_else(|| format!("invalid UNTIL value: {value}"))
+}
+
+fn parse_rule(text: &str) -> Result<Rule, String> {
+  let text = text.trim();
+  let text = text.get(..6).filter(|prefix| prefix.eq_ignore_ascii_case("RRULE:")).map_or(text, |_| &text[6..]);
+  let mut frequency = None;
+  let mut rule = Rule { frequency: Frequency::Daily, interval: 1, by_day: Vec::new(), by_month_day: Vec::new(), count: None, until: None };
+  for part in text.split(';').map(str::trim).filter(|part| !part.is_empty()) {
+    let (key, value) = part.split_once('=').ok_or_else(|| format!("invalid recurrence part: {part}"))?;
+    let key = key.trim().to_ascii_uppercase();
+    let value = value.trim().to_ascii_uppercase();
+    match key.as_str() {
+      "FREQ" => {
+        frequency = Some(match value.as_str() {
+          "DAILY" => Frequency::Daily,
+          "WEEKLY" => Frequency::Weekly,
+          "MONTHLY" => Frequency::Monthly,
+          "YEARLY" => Frequency::Yearly,
+          _ => return Err(format!("unsupported frequency: {value}")),
+        })
+      }
+      "INTERVAL" => {
+        rule.interval = parse_number(&key, &value)?;
+        if rule.interval == 0 || rule.interval > MAX_INTERVAL {
+          return Err(format!("INTERVAL must be between 1 and {MAX_INTERVAL}"));
+        }
+      }
+      "BYDAY" => rule.by_day = value.split(',').map(|day| parse_weekday(day.trim())).collect::<Result<_, _>>()?,
+      "BYMONTHDAY" => {
+        rule.by_month_day = value.split(',').map(|day| parse_number::<i32>(&key, day.trim())).collect::<Result<_, _>>()?;
+        if rule.by_month_day.iter().any(|day| *day == 0 || day.abs() > 31) {
+          return Err(format!("invalid BYMONTHDAY value: {value}"));
+        }
+      }
+      "COUNT" => rule.count = Some(parse_number(&key, &value)?),
+      "UNTIL" => rule.until = Some(parse_until(&value)?),
+      "WKST" if value == "MO" => {}
+      _ => return Err(format!("unsupported recurrence part: {part}")),
+    }
+  }
+  rule.frequency = frequency.ok_or_else(|| "FREQ is required".to_string())?;
+  let ordinals = rule.by_day.iter().any(|(ordinal, _)| ordinal.is_some());
+  match rule.frequency {
+    Frequency::Daily | Frequency::Weekly if ordinals || !rule.by_month_day.is_empty() => {
+      Err("numbered weekdays and BYMONTHDAY need FREQ=MONTHLY".to_string())
+    }
+    Frequency::Yearly if !rule.by_day.is_empty() || !rule.by_month_day.is_empty() => {
+      Err("BYDAY and BYMONTHDAY are not supported with FREQ=YEARLY".to_string())
+    }
+    _ => Ok(rule),
+  }
+}
+
+pub(crate) fn is_supported(rule: &str) -> bool {
+  parse_rule(rule).is_ok()
+}
+
+fn month_length(year: i32, month: u32) -> u32 {
+  let first = NaiveDate::from_ymd_opt(year, month, 1).expect("valid month");
+  let next = first + Months::new(1);
+  (next - first).num_days() as u32
+}
+
+fn nth_weekday(year: i32, month: u32, ordinal: i32, weekday: Weekday) -> Option<NaiveDate> {
+  let days = month_days_with(year, month, weekday);
+  let index = if ordinal > 0 { ordinal - 1 } else { days.len() as i32 + ordinal };
+  usize::try_from(index).ok().and_then(|index| days.get(index).copied())
+}
+
+fn month_days_with(year: i32, month: u32, weekday: Weekday) -> Vec<NaiveDate> {
+  (1..=month_length(year, month))
+    .filter_map(|day| NaiveDate::from_ymd_opt(year, month, day))
+    .filter(|date| date.weekday() == weekday)
+    .collect()
+}
+
+fn month_candidates(rule: &Rule, year: i32, month: u32, anchor_day: u32) -> Vec<NaiveDate> {
+  let length = month_length(year, month);
+  let by_month_day: Vec<NaiveDate> = rule
+    .by_month_day
+    .iter()
+    .filter_map(|day| {
+      let day = if *day > 0 { *day } else { length as i32 + 1 + day };
+      u32::try_from(day).ok().and_then(|day| NaiveDate::from_ymd_opt(year, month, day))
+    })
+    .collect();
+  let by_day: Vec<NaiveDate> = rule
+    .by_day
+    .iter()
+    .flat_map(|(ordinal, weekday)| match ordinal {
+      Some(ordinal) => nth_weekday(year, month, *ordinal, *weekday).into_iter().collect(),
+      None => month_days_with(year, month, *weekday),
+    })
+    .collect();
+  let mut dates = match (rule.by_month_day.is_empty(), rule.by_day.is_empty()) {
+    (true, true) => NaiveDate::from_ymd_opt(year, month, anchor_day.min(length)).into_iter().collect(),
+    (false, true) => by_month_day,
+    (true, false) => by_day,
+    (false, false) => by_month_day.into_iter().filter(|date| by_day.contains(date)).collect(),
+  };
+  dates.sort();
+  dates
+}
+
+fn matches_weekday(rule: &Rule, date: NaiveDate) -> bool {
+  rule.by_day.is_empty() || rule.by_day.iter().any(|(_, weekday)| *weekday == date.weekday())
+}
+
+fn next_date(rule: &Rule, anchor: NaiveDate) -> Option<NaiveDate> {
+  match rule.frequency {
+    Frequency::Daily => (1..=SEARCH_LIMIT)
+      .filter_map(|step| anchor.checked_add_signed(Duration::days(i64::from(step * rule.interval))))
+      .find(|date| matches_weekday(rule, *date)),
+    Frequency::Weekly if rule.by_day.is_empty() => anchor.checked_add_signed(Duration::weeks(i64::from(rule.interval))),
+    Frequency::Weekly => {
+      let week_start = anchor.week(Weekday::Mon).first_day();
+      (1..=7 * rule.interval * 2)
+        .filter_map(|offset| anchor.checked_add_signed(Duration::days(i64::from(offset))))
+        .find(|date| {
+          let weeks = (date.week(Weekday::Mon).first_day() - week_start).num_weeks();
+          weeks % i64::from(rule.interval) == 0 && matches_weekday(rule, *date)
+        })
+    }
+    Frequency::Monthly => {
+      let first = anchor.with_day(1)?;
+      (0..SEARCH_LIMIT).step_by(rule.interval as usize).find_map(|offset| {
+        let month = first.checked_add_months(Months::new(offset))?;
+        month_candidates(rule, month.year(), month.month(), anchor.day())
+          .into_iter()
+          .find(|date| *date > anchor)
+      })
+    }
+    Frequency::Yearly => {
+      let year = anchor.year() + i32::try_from(rule.interval).ok()?;
+      let day = anchor.day().min(month_length(year, anchor.month()));
+      NaiveDate::from_ymd_opt(year, anchor.month(), day)
+    }
+  }
+}
+
+fn parse_due(value: &str, zone: &Zone) -> Option<Due> {
+  let value = value.trim();
+  if let Ok(date) = NaiveDate::parse_from_str(value, DATE_FORMAT) {
+    return Some(Due { at: date.and_time(NaiveTime::MIN), has_time: false });
+  }
+  if let Ok(at) = NaiveDateTime::parse_from_str(value, DATE_TIME_FORMAT) {
+    return Some(Due { at, has_time: true });
+  }
+  let at = resolve_time(value, zone).map(|value| zone.local(&value))?;
+  Some(Due { at, has_time: true })
+}
+
+fn format_due(due: Due) -> String {
+  due.at.format(if due.has_time { DATE_TIME_FORMAT } else { DATE_FORMAT }).to_string()
+}
+
+fn next_due(rule: &Rule, due: Option<Due>, today: NaiveDate, completed: usize) -> Option<Due> {
+  if rule.count.is_some_and(|count| completed >= count) {
+    return None;
+  }
+  let base = due.unwrap_or(Due { at: today.and_time(NaiveTime::MIN), has_time: false });
+  let mut anchor = base.at.date();
+  for _ in 0..SEARCH_LIMIT {
+    let date = next_date(rule, anchor)?;
+    if rule.until.is_some_and(|until| date > until) {
+      return None;
+    }
+    if date >= today {
+      return Some(Due { at: date.and_time(base.at.time()), has_time: base.has_time });
+    }
+    anchor = date;
+  }
+  None
+}
+
+fn preview(rule: &Rule, due: Option<Due>, today: NaiveDate) -> Vec<String> {
+  let mut dates = Vec::new();
+  let mut current = due;
+  let mut completed = 0;
+  if let Some(due) = due
+    && due.at.date() >= today
+  {
+    dates.push(format_due(due));
+    completed += 1;
+  }
+  while dates.len() < PREVIEW_COUNT {
+    let Some(next) = next_due(rule, current, today, completed) else {
+      break;
+    };
+    dates.push(format_due(next));
+    current = Some(next);
+    completed += 1;
+  }
+  dates
+}
+
+fn fresh_copy(step: &StepState) -> StepState {
+  StepState {
+    id: Some(EntityId::generate()),
+    done: false,
+    time_entries: Vec::new(),
+    completions: Vec::new(),
+    children: step.children.iter().map(fresh_copy).collect(),
+    ..step.clone()
+  }
+}
+
+fn shift_reminder(step: &StepState, due: Option<Due>, next: Due, zone: &Zone) -> Option<String> {
+  let remind = parse_due(step.remind_at.as_deref()?, zone)?;
+  let offset = remind.at - due?.at;
+  Some((next.at + offset).format(DATE_TIME_FORMAT).to_string())
+}
+
+fn complete(mut state: AppState, project_id: &EntityId, step_id: &EntityId, now: DateTime<Utc>) -> Result<CompletionResult, String> {
+  let zone = Zone::from_settings(&state.settings.time_zone);
+  let today = zone.local(&now).date();
+  let auto_complete = state.settings.auto_complete_steps;
+  let project = state
+    .projects
+    .iter_mut()
+    .find(|project| project.id.as_ref() == Some(project_id))
+    .ok_or_else(|| "project not found".to_string())?;
+  let path = find_path(&project.steps, step_id).ok_or_else(|| "step not found".to_string())?;
+  let step = step_at_mut(&mut project.steps, &path);
+  let rule = step
+    .recurrence
+    .as_deref()
+    .filter(|rule| !rule.trim().is_empty())
+    .ok_or_else(|| "step does not repeat".to_string())
+    .and_then(parse_rule)?;
+
+  let due = step.due_at.as_deref().and_then(|value| parse_due(value, &zone));
+  let mut completions = std::mem::take(&mut step.completions);
+  completions.push(CompletionState { due_at: step.due_at.clone(), completed_at: now.to_rfc3339() });
+  step.done = true;
+  if auto_complete {
+    for_each_mut(&mut step.children, &mut |child| child.done = true);
+  }
+
+  let next = next_due(&rule, due, today, completions.len());
+  let next_due_at = next.map(format_due);
+  match next {
+    Some(next) => {
+      let mut occurrence = fresh_copy(step);
+      occurrence.due_at = Some(format_due(next));
+      occurrence.remind_at = shift_reminder(step, due, next, &zone);
+      occurrence.completions = completions;
+      step.recurrence = None;
+      let (index, parent_path) = path.split_last().expect("step path is never empty");
+      list_at(&mut project.steps, parent_path).insert(index + 1, occurrence);
+    }
+    None => step.completions = completions,
+  }
+  if auto_complete {
+    sync_parents(&mut project.steps);
+  }
+  Ok(CompletionResult { state, next_due_at })
+}
+
+#[tauri::command]
+pub(crate) fn complete_recurring_step(state: AppState, project_id: EntityId, step_id: EntityId) -> Result<CompletionResult, String> {
+  complete(state, &project_id, &step_id, Utc::now())
+}
+
+#[tauri::command]
+pub(crate) fn preview_recurrence(rule: String, due_at: Option<String>, time_zone: String) -> Result<Vec<String>, String> {
+  let rule = parse_rule(&rule)?;
+  let zone = Zone::from_settings(&time_zone);
+  let due = due_at.as_deref().and_then(|value| parse_due(value, &zone));
+  Ok(preview(&rule, due, zone.local(&Utc::now()).date()))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn date(value: &str) -> NaiveDate {
+    NaiveDate::parse_from_str(value, DATE_FORMAT).unwrap()
+  }
+
+  fn next(rule: &str, anchor: &str) -> String {
+    next_date(&parse_rule(rule).unwrap(), date(anchor)).unwrap().to_string()
+  }
+
+  fn utc(value: &str) -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339(value).unwrap().with_timezone(&Utc)
+  }
+
+  #[test]
+  fn parses_supported_rules() {
+    let rule = parse_rule(" rrule:freq=monthly;interval=2;byday=-1fr,+2MO;count=4;until=20271231T000000Z;wkst=mo; ").unwrap();
+    assert_eq!(rule.frequency, Frequency::Monthly);
+    assert_eq!(rule.interval, 2);
+    assert_eq!(rule.by_day, vec![(Some(-1), Weekday::Fri), (Some(2), Weekday::Mon)]);
+    assert_eq!(rule.count, Some(4));
+    assert_eq!(rule.until, Some(date("2027-12-31")));
+    assert_eq!(parse_rule("FREQ=MONTHLY;BYMONTHDAY=1,-1").unwrap().by_month_day, vec![1, -1]);
+  }
+
+  #[test]
+  fn rejects_malformed_rules_without_panicking() {
+    for bad in [
+      "",
+      "FREQ",
+      "FREQ=HOURLY",
+      "INTERVAL=2",
+      "FREQ=DAILY;INTERVAL=0",
+      "FREQ=DAILY;INTERVAL=367",
+      "FREQ=DAILY;INTERVAL=-1",
+      "FREQ=DAILY;BYHOUR=3",
+      "FREQ=WEEKLY;BYDAY=1MO",
+      "FREQ=WEEKLY;BYMONTHDAY=1",
+      "FREQ=YEARLY;BYDAY=MO",
+      "FREQ=MONTHLY;BYDAY=XX",
+      "FREQ=MONTHLY;BYDAY=6MO",
+      "FREQ=MONTHLY;BYDAY=0MO",
+      "FREQ=MONTHLY;BYDAY=M",
+      "FREQ=MONTHLY;BYDAY=",
+      "FREQ=MONTHLY;BYDAY=1Пн",
+      "FREQ=MONTHLY;BYDAY=Пн",
+      "FREQ=MONTHLY;BYDAY=1MÖ",
+      "FREQ=WEEKLY;BYDAY=🙂",
+      "FREQ=MONTHLY;BYMONTHDAY=32",
+      "FREQ=MONTHLY;BYMONTHDAY=0",
+      "FREQ=DAILY;UNTIL=2026",
+      "FREQ=DAILY;UNTIL=2026-10-1",
+      "FREQ=DAILY;WKST=SU",
+      "FREQ=DAILY;COUNT=many",
+    ] {
+      assert!(parse_rule(bad).is_err(), "{bad}");
+    }
+    assert!(preview_recurrence("FREQ=MONTHLY;BYDAY=1Пн".into(), None, "UTC".into()).is_err());
+  }
+
+  #[test]
+  fn finds_next_dates() {
+    assert_eq!(next("FREQ=DAILY", "2026-10-19"), "2026-10-20");
+    assert_eq!(next("FREQ=DAILY;INTERVAL=3", "2026-12-30"), "2027-01-02");
+    assert_eq!(next("FREQ=DAILY;BYDAY=MO,TU,WE,TH,FR", "2026-10-23"), "2026-10-26");
+    assert_eq!(next("FREQ=WEEKLY", "2026-10-19"), "2026-10-26");
+    assert_eq!(next("RRULE:FREQ=WEEKLY;BYDAY=MO,TH", "2026-10-19"), "2026-10-22");
+    assert_eq!(next("FREQ=WEEKLY;BYDAY=MO,TH", "2026-10-22"), "2026-10-26");
+    assert_eq!(next("FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,TH", "2026-10-22"), "2026-11-02");
+    assert_eq!(next("FREQ=MONTHLY;BYDAY=-1FR", "2026-10-19"), "2026-10-30");
+    assert_eq!(next("FREQ=MONTHLY;BYDAY=1MO", "2026-10-19"), "2026-11-02");
+    assert_eq!(next("FREQ=MONTHLY;BYDAY=5FR", "2026-10-31"), "2027-01-29");
+    assert_eq!(next("FREQ=MONTHLY;INTERVAL=3;BYMONTHDAY=1", "2026-10-01"), "2027-01-01");
+  }
+
+  #[test]
+  fn handles_month_and_year_ends() {
+    assert_eq!(next("FREQ=MONTHLY", "2026-01-31"), "2026-02-28");
+    assert_eq!(next("FREQ=MONTHLY", "2028-01-31"), "2028-02-29");
+    assert_eq!(next("FREQ=MONTHLY", "2026-02-28"), "2026-03-28");
+    assert_eq!(next("FREQ=MONTHLY", "2026-12-31"), "2027-01-31");
+    assert_eq!(next("FREQ=MONTHLY;BYMONTHDAY=-1", "2026-01-31"), "2026-02-28");
+    assert_eq!(next("FREQ=MONTHLY;BYMONTHDAY=-1", "2026-02-28"), "2026-03-31");
+    assert_eq!(next("FREQ=MONTHLY;BYMONTHDAY=31", "2026-01-31"), "2026-03-31");
+    assert_eq!(next("FREQ=MONTHLY;BYMONTHDAY=30", "2027-01-30"), "2027-03-30");
+    assert_eq!(next("FREQ=MONTHLY;BYMONTHDAY=29", "2028-01-29"), "2028-02-29");
+    assert_eq!(next("FREQ=YEARLY", "2024-02-29"), "2025-02-28");
+    assert_eq!(next("FREQ=YEARLY;INTERVAL=4", "2024-02-29"), "2028-02-29");
+    assert_eq!(next("FREQ=WEEKLY;BYDAY=FR", "2026-12-28"), "2027-01-01");
+  }
+
+  #[test]
+  fn keeps_wall_clock_time_across_dst_changes() {
+    let berlin = Zone::from_settings("Europe/Berlin");
+    let rule = parse_rule("FREQ=WEEKLY").unwrap();
+    let due = parse_due("2026-03-23T09:00", &berlin);
+    let next = next_due(&rule, due, date("2026-03-24"), 1).unwrap();
+    assert_eq!(format_due(next), "2026-03-30T09:00");
+    let due = parse_due("2026-10-19T09:00", &berlin);
+    let next = next_due(&rule, due, date("2026-10-20"), 1).unwrap();
+    assert_eq!(format_due(next), "2026-10-26T09:00");
+
+    let daily = parse_rule("FREQ=DAILY").unwrap();
+    let gap = parse_due("2026-03-29T02:30", &berlin).expect("time inside the spring-forward gap still parses");
+    assert_eq!(format_due(next_due(&daily, Some(gap), date("2026-03-29"), 1).unwrap()), "2026-03-30T02:30");
+    let repeated = parse_due("2026-10-25T02:30", &berlin).unwrap();
+    assert_eq!(format_due(next_due(&daily, Some(repeated), date("2026-10-25"), 1).unwrap()), "2026-10-26T02:30");
+
+    let offset = parse_due("2026-03-28T23:30:00Z", &berlin).unwrap();
+    assert_eq!(format_due(offset), "2026-03-29T00:30");
+    let preview = preview(&daily, Some(offset), date("2026-03-29"));
+    assert_eq!(preview, vec!["2026-03-29T00:30", "2026-03-30T00:30", "2026-03-31T00:30"]);
+  }
+
+  #[test]
+  fn respects_count_until_and_catch_up() {
+    let rule = parse_rule("FREQ=DAILY;UNTIL=20261021").unwrap();
+    let due = parse_due("2026-10-19", &Zone::from_settings("UTC"));
+    assert_eq!(preview(&rule, due, date("2026-10-19")), vec!["2026-10-19", "2026-10-20", "2026-10-21"]);
+    assert_eq!(next_due(&rule, due, date("2026-10-22"), 0), None);
+    let weekly = parse_rule("FREQ=WEEKLY;BYDAY=MO").unwrap();
+    let overdue = parse_due("2026-09-07", &Zone::from_settings("UTC"));
+    assert_eq!(next_due(&weekly, overdue, date("2026-10-20"), 0).map(format_due).as_deref(), Some("2026-10-26"));
+    let counted = parse_rule("FREQ=DAILY;COUNT=2").unwrap();
+    assert_eq!(next_due(&counted, due, date("2026-10-19"), 2), None);
+    let monthly = parse_rule("FREQ=MONTHLY;BYMONTHDAY=1").unwrap();
+    assert_eq!(preview(&monthly, None, date("2026-10-19")), vec!["2026-11-01", "2026-12-01", "2027-01-01"]);
+  }
+
+  #[test]
+  fn completing_spawns_the_next_occurrence() {
+    let state: AppState = serde_json::from_str(
+      r#"{"projects":[{"id":1,"name":"Ops","steps":[
+        {"id":"a","text":"Backups","dueAt":"2026-10-12T10:00","remindAt":"2026-10-12T09:30","recurrence":"FREQ=WEEKLY;BYDAY=MO;COUNT=3",
+         "children":[{"id":"c","text":"Verify","done":true}]},
+        {"id":"b","text":"Other"}]}],"settings":{"timeZone":"UTC","autoCompleteSteps":true}}"#,
+    )
+    .unwrap();
+    let now = utc("2026-10-19T12:00:00Z");
+    let project = EntityId::Num(1);
+    let result = complete(state, &project, &EntityId::parse("a"), now).unwrap();
+    assert_eq!(result.next_due_at.as_deref(), Some("2026-10-19T10:00"));
+    let steps = &result.state.projects[0].steps;
+    assert_eq!(steps.len(), 3);
+    assert!(steps[0].done && steps[0].recurrence.is_none() && steps[0].completions.is_empty());
+    assert!(!steps[1].done);
+    assert_eq!(steps[1].remind_at.as_deref(), Some("2026-10-19T09:30"));
+    assert_eq!(steps[1].completions.len(), 1);
+    assert!(!steps[1].children[0].done);
+    assert_ne!(steps[1].children[0].id, steps[0].children[0].id);
+
+    let id = steps[1].id.clone().unwrap();
+    let result = complete(result.state, &project, &id, now).unwrap();
+    let id = result.state.projects[0].steps[2].id.clone().unwrap();
+    let result = complete(result.state, &project, &id, now).unwrap();
+    assert_eq!(result.next_due_at, None);
+    assert_eq!(result.state.projects[0].steps[2].completions.len(), 3);
+    assert!(complete(result.state.clone(), &project, &EntityId::parse("b"), now).is_err());
+    assert!(complete(result.state, &project, &EntityId::parse("missing"), now).is_err());
+  }
+}
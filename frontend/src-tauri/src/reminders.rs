@@ -0,0 +1,313 @@
+use crate::labels::{AppLabels, app_labels};
+use crate::time_zone::{Zone, resolve_time};
+use crate::{AppState, EntityId, data_dir, read_state_file, step_tree};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{Emitter, Manager};
+use tauri_plugin_notification::NotificationExt;
+
+const LOG_FILE: &str = "reminders.json";
+const DUE_EVENT: &str = "reminders-due";
+const CHECK_INTERVAL: Duration = Duration::from_secs(30);
+const STARTUP_DELAY: Duration = Duration::from_secs(5);
+const MAX_SEPARATE_NOTIFICATIONS: usize = 3;
+const MAX_SNOOZE_MINUTES: u32 = 7 * 24 * 60;
+
+#[derive(Default)]
+pub(crate) struct ReminderScheduler {
+  lock: Mutex<()>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct ReminderLog {
+  fired: BTreeMap<String, String>,
+  snoozed: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DueReminder {
+  key: String,
+  project_id: Option<EntityId>,
+  step_id: Option<EntityId>,
+  project: String,
+  text: String,
+  due_at: Option<String>,
+  remind_at: String,
+  missed: bool,
+}
+
+struct Candidate {
+  trigger: String,
+  at: DateTime<Utc>,
+  done: bool,
+  reminder: DueReminder,
+}
+
+fn trigger<'a>(due_at: Option<&'a String>, remind_at: Option<&'a String>) -> Option<&'a str> {
+  remind_at
+    .map(|value| value.trim())
+    .filter(|value| !value.is_empty())
+    .or_else(|| due_at.map(|value| value.trim()).filter(|value| !value.is_empty()))
+}
+
+fn candidates(state: &AppState, zone: &Zone) -> Vec<Candidate> {
+  let mut result = Vec::new();
+  for project in &state.projects {
+    let Some(project_id) = &project.id else {
+      continue;
+    };
+    let mut push = |key: String, step_id: Option<EntityId>, text: &str, done: bool, due_at: Option<&String>, remind_at: Option<&String>| {
+      let Some(spec) = trigger(due_at, remind_at) else {
+        return;
+      };
+      let Some(at) = resolve_time(spec, zone) else {
+        return;
+      };
+      let due_at = due_at.and_then(|value| resolve_time(value, zone)).map(|value| zone.format(&value));
+      result.push(Candidate {
+        trigger: spec.to_string(),
+        at,
+        done,
+        reminder: DueReminder {
+          key,
+          project_id: Some(project_id.clone()),
+          step_id,
+          project: project.name.trim().to_string(),
+          text: text.trim().to_string(),
+          due_at,
+          remind_at: at.to_rfc3339(),
+          missed: false,
+        },
+      });
+    };
+
+    push(
+      format!("project:{project_id}"),
+      None,
+      &project.name,
+      false,
+      project.due_at.as_ref(),
+      project.remind_at.as_ref(),
+    );
+    for (_, step) in step_tree::flatten(&project.steps) {
+      let Some(step_id) = &step.id else {
+        continue;
+      };
+      push(
+        format!("step:{project_id}:{step_id}"),
+        Some(step_id.clone()),
+        &step.text,
+        step.done,
+        step.due_at.as_ref(),
+        step.remind_at.as_ref(),
+      );
+    }
+  }
+  result
+}
+
+fn collect_due(state: &AppState, log: &mut ReminderLog, now: DateTime<Utc>) -> Vec<DueReminder> {
+  let zone = Zone::from_settings(&state.settings.time_zone);
+  let candidates = candidates(state, &zone);
+  let late_after = chrono::Duration::from_std(CHECK_INTERVAL * 2).unwrap_or_default();
+  let mut due = Vec::new();
+
+  for candidate in &candidates {
+    let key = &candidate.reminder.key;
+    if candidate.done {
+      log.snoozed.remove(key);
+      continue;
+    }
+    if log.fired.get(key) == Some(&candidate.trigger) {
+      let until = log.snoozed.get(key).and_then(|value| DateTime::parse_from_rfc3339(value).ok());
+      if until.is_some_and(|until| until <= now) {
+        log.snoozed.remove(key);
+        due.push(candidate.reminder.clone());
+      }
+    } else if candidate.at <= now {
+      log.fired.insert(key.clone(), candidate.trigger.clone());
+      log.snoozed.remove(key);
+      let mut reminder = candidate.reminder.clone();
+      reminder.missed = now - candidate.at > late_after;
+      due.push(reminder);
+    }
+  }
+
+  let known: BTreeSet<&String> = candidates.iter().map(|candidate| &candidate.reminder.key).collect();
+  log.fired.retain(|key, _| known.contains(key));
+  log.snoozed.retain(|key, _| known.contains(key));
+  due
+}
+
+fn read_log(app: &tauri::AppHandle) -> Result<ReminderLog, String> {
+  let path = data_dir(app)?.join(LOG_FILE);
+  if !path.exists() {
+    return Ok(ReminderLog::default());
+  }
+  let content = fs::read_to_string(path).map_err(|err| err.to_string())?;
+  Ok(serde_json::from_str(&content).unwrap_or_default())
+}
+
+fn write_log(app: &tauri::AppHandle, log: &ReminderLog) -> Result<(), String> {
+  let serialized = serde_json::to_string_pretty(log).map_err(|err| err.to_string())?;
+  fs::write(data_dir(app)?.join(LOG_FILE), serialized).map_err(|err| err.to_string())
+}
+
+fn group_title(due: &[DueReminder], labels: &AppLabels) -> String {
+  let template = if due.iter().all(|item| item.missed) { labels.missed_reminders } else { labels.due_reminders };
+  template.replace("{count}", &due.len().to_string())
+}
+
+fn notify(app: &tauri::AppHandle, due: &[DueReminder], language: &str) {
+  let labels = app_labels(language);
+  let show = |title: &str, body: &str| {
+    if let Err(err) = app.notification().builder().title(title).body(body).show() {
+      log::warn!("failed to show reminder notification: {err}");
+    }
+  };
+
+  if due.len() > MAX_SEPARATE_NOTIFICATIONS {
+    let mut lines: Vec<String> = due.iter().take(MAX_SEPARATE_NOTIFICATIONS).map(|item| item.text.clone()).collect();
+    lines.push("…".to_string());
+    show(&group_title(due, labels), &lines.join("\n"));
+    return;
+  }
+  for item in due {
+    let mut body = if item.step_id.is_some() { item.text.clone() } else { String::new() };
+    if let Some(due_at) = &item.due_at {
+      if !body.is_empty() {
+        body.push('\n');
+      }
+      body.push_str(&format!("{}: {due_at}", labels.due_date));
+    }
+    show(&item.project, &body);
+  }
+}
+
+fn check_due(app: &tauri::AppHandle) -> Result<(), String> {
+  let scheduler = app.state::<ReminderScheduler>();
+  let _guard = scheduler.lock.lock().map_err(|err| err.to_string())?;
+  let Some(state) = read_state_file(app)? else {
+    return Ok(());
+  };
+  let mut log = read_log(app)?;
+  let before = serde_json::to_string(&log).map_err(|err| err.to_string())?;
+  let due = collect_due(&state, &mut log, Utc::now());
+  if serde_json::to_string(&log).map_err(|err| err.to_string())? != before {
+    write_log(app, &log)?;
+  }
+  if due.is_empty() {
+    return Ok(());
+  }
+
+  notify(app, &due, &state.settings.language);
+  app.emit(DUE_EVENT, due).map_err(|err| err.to_string())
+}
+
+pub(crate) fn start(app: tauri::AppHandle) {
+  std::thread::spawn(move || {
+    std::thread::sleep(STARTUP_DELAY);
+    loop {
+      if let Err(err) = check_due(&app) {
+        log::warn!("failed to check reminders: {err}");
+      }
+      std::thread::sleep(CHECK_INTERVAL);
+    }
+  });
+}
+
+#[tauri::command]
+pub(crate) fn snooze_reminder(
+  app: tauri::AppHandle,
+  scheduler: tauri::State<'_, ReminderScheduler>,
+  key: String,
+  minutes: u32,
+) -> Result<String, String> {
+  let _guard = scheduler.lock.lock().map_err(|err| err.to_string())?;
+  let mut log = read_log(&app)?;
+  if !log.fired.contains_key(&key) {
+    return Err("reminder not found".to_string());
+  }
+  let until = Utc::now() + chrono::Duration::minutes(i64::from(minutes.clamp(1, MAX_SNOOZE_MINUTES)));
+  let until = until.to_rfc3339();
+  log.snoozed.insert(key, until.clone());
+  write_log(&app, &log)?;
+  Ok(until)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn at(value: &str) -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339(value).unwrap().with_timezone(&Utc)
+  }
+
+  fn state() -> AppState {
+    serde_json::from_str(
+      r#"{"projects":[{"id":"p1","name":"Проект","dueAt":"2026-01-10","steps":[
+        {"id":"s1","text":"Шаг 1","remindAt":"2026-01-05T10:00"},
+        {"id":"s2","text":"Шаг 2","dueAt":"2026-01-06T12:00:00+00:00","done":true},
+        {"id":"s3","text":"Шаг 3","dueAt":"2026-02-01T08:00"}]}],
+        "settings":{"timeZone":"Europe/Kyiv","language":"ru"}}"#,
+    )
+    .unwrap()
+  }
+
+  fn keys(due: &[DueReminder]) -> Vec<(&str, bool)> {
+    due.iter().map(|item| (item.key.as_str(), item.missed)).collect()
+  }
+
+  #[test]
+  fn fires_once_in_the_configured_zone() {
+    let state = state();
+    let mut log = ReminderLog::default();
+    assert!(collect_due(&state, &mut log, at("2026-01-05T07:59:00Z")).is_empty());
+    let due = collect_due(&state, &mut log, at("2026-01-05T08:00:00Z"));
+    assert_eq!(keys(&due), [("step:p1:s1", false)]);
+    assert!(collect_due(&state, &mut log, at("2026-01-05T08:01:00Z")).is_empty());
+  }
+
+  #[test]
+  fn catches_up_and_marks_late_reminders_as_missed() {
+    let state = state();
+    let mut log = ReminderLog::default();
+    let due = collect_due(&state, &mut log, at("2026-03-01T00:00:00Z"));
+    assert_eq!(keys(&due), [("project:p1", true), ("step:p1:s1", true), ("step:p1:s3", true)]);
+    assert_eq!(due[0].due_at.as_deref(), Some("2026-01-10 09:00"));
+  }
+
+  #[test]
+  fn snoozes_and_forgets_done_or_removed_items() {
+    let mut state = state();
+    let mut log = ReminderLog::default();
+    let now = at("2026-01-05T08:00:00Z");
+    collect_due(&state, &mut log, now);
+    log.snoozed.insert("step:p1:s1".into(), "2026-01-05T08:10:00+00:00".into());
+    assert!(collect_due(&state, &mut log, at("2026-01-05T08:05:00Z")).is_empty());
+    assert_eq!(collect_due(&state, &mut log, at("2026-01-05T08:11:00Z")).len(), 1);
+    assert!(log.snoozed.is_empty());
+
+    state.projects[0].steps[0].remind_at = Some("2026-01-05T11:00".into());
+    assert_eq!(keys(&collect_due(&state, &mut log, at("2026-01-05T09:00:00Z"))), [("step:p1:s1", false)]);
+
+    state.projects[0].steps.remove(0);
+    collect_due(&state, &mut log, at("2026-01-05T09:01:00Z"));
+    assert!(!log.fired.contains_key("step:p1:s1"));
+  }
+
+  #[test]
+  fn uses_the_missed_title_only_for_caught_up_reminders() {
+    let labels = app_labels("en");
+    let mut due = collect_due(&state(), &mut ReminderLog::default(), at("2026-03-01T00:00:00Z"));
+    assert_eq!(group_title(&due, labels), "Missed reminders: 3");
+    due[1].missed = false;
+    assert_eq!(group_title(&due, labels), "Reminders: 3");
+  }
+}
@@ -0,0 +1,288 @@
+use crate::labels::{DocLabels, doc_labels};
+use crate::markup::escape;
+use crate::{AppState, EntityId, ProjectState, StepState, file_stem, markdown, step_tree, write_export_file};
+use std::fmt::Write;
+
+const REPORT_CSS: &str = r#"
+:root {
+  --bg-main: #07090f;
+  --bg-card: rgba(15, 18, 28, 0.93);
+  --text: #eef3ff;
+  --muted: #9eabc2;
+  --line: rgba(139, 157, 197, 0.24);
+  --accent: #5ca8ff;
+  --accent-2: #495fff;
+}
+* { box-sizing: border-box; }
+body {
+  margin: 0;
+  font-family: 'Manrope', 'Segoe UI', 'Helvetica Neue', Arial, sans-serif;
+  color: var(--text);
+  background: radial-gradient(circle at 8% -12%, #1e274a 0%, transparent 35%),
+    radial-gradient(circle at 95% 120%, #0f2740 0%, transparent 45%), var(--bg-main);
+  background-attachment: fixed;
+  line-height: 1.5;
+}
+.report { max-width: 960px; margin: 0 auto; padding: 32px 20px 48px; }
+.report-head { margin-bottom: 24px; }
+.eyebrow { margin: 0; color: var(--accent); font-size: 13px; letter-spacing: 0.08em; text-transform: uppercase; }
+h1 { margin: 6px 0; font-size: 30px; }
+h2 { margin: 0 0 8px; font-size: 22px; }
+h3 { margin: 22px 0 10px; font-size: 15px; color: var(--muted); text-transform: uppercase; letter-spacing: 0.06em; }
+.meta { margin: 0; color: var(--muted); font-size: 13px; }
+.card {
+  background: var(--bg-card);
+  border: 1px solid var(--line);
+  border-radius: 18px;
+  padding: 20px 22px;
+  margin-bottom: 18px;
+  box-shadow: 0 24px 68px rgba(0, 0, 0, 0.45);
+}
+.badges { display: flex; flex-wrap: wrap; gap: 8px; margin-bottom: 10px; }
+.badge { border: 1px solid var(--line); border-radius: 999px; padding: 2px 10px; font-size: 12px; color: var(--muted); }
+.badge.accent { color: var(--text); border-color: var(--accent); }
+.description { white-space: pre-wrap; margin: 0 0 12px; }
+.progress { display: grid; gap: 6px; }
+.progress-track { height: 10px; border-radius: 999px; background: rgba(139, 157, 197, 0.16); overflow: hidden; }
+.progress-fill { height: 100%; border-radius: inherit; background: linear-gradient(90deg, var(--accent-2), var(--accent)); }
+.progress-caption { font-size: 13px; color: var(--muted); }
+.steps { list-style: none; margin: 0; padding: 0; display: grid; gap: 6px; }
+.steps .steps { margin: 6px 0 0 28px; }
+.step-row { display: flex; gap: 10px; align-items: flex-start; }
+.step-count { margin-left: auto; font-size: 12px; color: var(--muted); white-space: nowrap; }
+.check {
+  flex: none;
+  width: 18px;
+  height: 18px;
+  margin-top: 3px;
+  border-radius: 5px;
+  border: 1px solid var(--line);
+  font-size: 12px;
+  line-height: 16px;
+  text-align: center;
+}
+.steps li.done > .step-row .check { background: var(--accent); border-color: var(--accent); color: var(--bg-main); }
+.steps li.done > .step-row .step-text { color: var(--muted); text-decoration: line-through; }
+.note { border-top: 1px solid var(--line); padding: 8px 0; }
+.note summary { cursor: pointer; font-weight: 600; }
+.note-body { margin: 8px 0 0; color: var(--muted); }
+.note-body pre { overflow-x: auto; padding: 10px; border-radius: 8px; background: #2b303b; }
+.note-body td, .note-body th { border: 1px solid var(--line); }
+.empty { color: var(--muted); margin: 0; }
+table { width: 100%; border-collapse: collapse; }
+th, td { text-align: left; padding: 8px 6px; border-bottom: 1px solid var(--line); vertical-align: middle; }
+th { color: var(--muted); font-weight: 500; font-size: 13px; }
+td .progress-track { min-width: 120px; }
+a { color: var(--accent); }
+@media print {
+  body { background: #fff; color: #111; }
+  .card { box-shadow: none; break-inside: avoid; }
+}
+"#;
+
+pub(crate) fn progress_percent(done: usize, total: usize) -> usize {
+  (done * 100).checked_div(total).unwrap_or(0)
+}
+
+fn write_progress(out: &mut String, project: &ProjectState, labels: &DocLabels) {
+  let (done, total) = project.step_progress();
+  let _ = write!(
+    out,
+    r#"<div class="progress"><div class="progress-track"><div class="progress-fill" style="width: {}%"></div></div><span class="progress-caption">{}: {}</span></div>"#,
+    progress_percent(done, total),
+    escape(labels.progress),
+    escape(&labels.progress_caption(done, total)),
+  );
+}
+
+fn write_summary(out: &mut String, projects: &[&ProjectState], labels: &DocLabels) {
+  let _ = write!(
+    out,
+    r#"<section class="card"><h2>{}</h2><table><thead><tr><th>{}</th><th>{}</th><th>{}</th></tr></thead><tbody>"#,
+    escape(labels.projects),
+    escape(labels.projects),
+    escape(labels.project_status),
+    escape(labels.progress),
+  );
+  for (index, project) in projects.iter().enumerate() {
+    let (done, total) = project.step_progress();
+    let status = if project.status.trim().is_empty() { labels.status_not_set } else { project.status.trim() };
+    let _ = write!(
+      out,
+      r##"<tr><td><a href="#project-{index}">{}</a></td><td>{}</td><td><div class="progress-track"><div class="progress-fill" style="width: {}%"></div></div><span class="progress-caption">{done}/{total}</span></td></tr>"##,
+      escape(&project.name),
+      escape(status),
+      progress_percent(done, total),
+    );
+  }
+  out.push_str("</tbody></table></section>");
+}
+
+fn write_steps(out: &mut String, steps: &[StepState]) {
+  out.push_str(r#"<ul class="steps">"#);
+  for step in steps {
+    let (class, mark) = if step.done { (" class=\"done\"", "&#10003;") } else { ("", "") };
+    let _ = write!(
+      out,
+      r#"<li{class}><div class="step-row"><span class="check">{mark}</span><span class="step-text">{}</span>"#,
+      escape(&step.text)
+    );
+    if !step.children.is_empty() {
+      let (done, total) = step.progress();
+      let _ = write!(out, r#"<span class="step-count">{done}/{total}</span>"#);
+    }
+    out.push_str("</div>");
+    if !step.children.is_empty() {
+      write_steps(out, &step.children);
+    }
+    out.push_str("</li>");
+  }
+  out.push_str("</ul>");
+}
+
+fn write_project(out: &mut String, index: usize, project: &ProjectState, labels: &DocLabels) {
+  let _ = write!(out, r#"<section class="card" id="project-{index}"><h2>{}</h2><div class="badges">"#, escape(&project.name));
+  let status = project.status.trim();
+  if status.is_empty() {
+    let _ = write!(out, r#"<span class="badge">{}: {}</span>"#, escape(labels.project_status), escape(labels.status_not_set));
+  } else {
+    let _ = write!(out, r#"<span class="badge accent">{}: {}</span>"#, escape(labels.project_status), escape(status));
+  }
+  if project.pinned {
+    let _ = write!(out, r#"<span class="badge">{}</span>"#, escape(labels.pinned));
+  }
+  let _ = write!(
+    out,
+    r#"<span class="badge">{} {}</span><span class="badge">{} {}</span></div>"#,
+    project.notes.len(),
+    escape(labels.note_count),
+    step_tree::count(&project.steps),
+    escape(labels.step_count),
+  );
+
+  if !project.description.trim().is_empty() {
+    let _ = write!(out, r#"<p class="description">{}</p>"#, escape(project.description.trim()));
+  }
+  write_progress(out, project, labels);
+
+  let _ = write!(out, "<h3>{}</h3>", escape(labels.project_steps));
+  if project.steps.is_empty() {
+    let _ = write!(out, r#"<p class="empty">{}</p>"#, escape(labels.no_steps));
+  } else {
+    write_steps(out, &project.steps);
+  }
+
+  let _ = write!(out, "<h3>{}</h3>", escape(labels.project_notes));
+  if project.notes.is_empty() {
+    let _ = write!(out, r#"<p class="empty">{}</p>"#, escape(labels.no_notes));
+  } else {
+    for note in &project.notes {
+      let _ = write!(out, r#"<details class="note"><summary>{}</summary>"#, escape(&note.title));
+      if !note.body.trim().is_empty() {
+        let _ = write!(out, r#"<div class="note-body">{}</div>"#, markdown::render(&note.body));
+      }
+      out.push_str("</details>");
+    }
+  }
+  out.push_str("</section>");
+}
+
+pub(crate) fn render_report(projects: &[&ProjectState], workspace: bool, language: &str) -> String {
+  let labels = doc_labels(language);
+  let title = match (workspace, projects.first()) {
+    (false, Some(project)) => project.name.as_str(),
+    _ => labels.workspace,
+  };
+  let generated = chrono::Local::now().format("%Y-%m-%d %H:%M");
+
+  let mut out = String::new();
+  let _ = write!(
+    out,
+    r#"<!DOCTYPE html><html lang="{}"><head><meta charset="utf-8"><meta name="viewport" content="width=device-width, initial-scale=1"><title>{}</title><style>{REPORT_CSS}{}</style></head><body><main class="report">"#,
+    escape(language),
+    escape(title),
+    markdown::highlight_css(),
+  );
+  let _ = write!(
+    out,
+    r#"<header class="report-head"><p class="eyebrow">{}</p><h1>{}</h1><p class="meta">{}: {generated}</p></header>"#,
+    escape(labels.app_title),
+    escape(title),
+    escape(labels.generated),
+  );
+  if workspace {
+    write_summary(&mut out, projects, labels);
+  }
+  for (index, project) in projects.iter().enumerate() {
+    write_project(&mut out, index, project, labels);
+  }
+  out.push_str("</main></body></html>\n");
+  out
+}
+
+#[tauri::command]
+pub(crate) fn export_html_report(
+  app: tauri::AppHandle,
+  state: AppState,
+  project_id: Option<EntityId>,
+) -> Result<String, String> {
+  let projects = state.select_projects(project_id.as_ref())?;
+  let workspace = project_id.is_none();
+  let html = render_report(&projects, workspace, &state.settings.language);
+  let stem = match projects.first() {
+    Some(project) if !workspace => file_stem(&project.name, "project"),
+    _ => "workspace".to_string(),
+  };
+  write_export_file(&app, &format!("{stem}.report"), ".html", html.as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn sample() -> AppState {
+    serde_json::from_str(
+      r#"{"projects":[{"id":"p1","name":"Релиз <1.0>","description":"Описание\nвторая строка","status":"В работе","pinned":true,
+        "notes":[{"id":"n1","title":"Заметка & план","body":"Тело **жирно**\n\n<script>alert(1)</script>"},{"id":"n2","title":"Пустая"}],
+        "steps":[{"id":"s1","text":"tag","done":true},{"id":"s2","text":"build","children":[{"text":"a <b>","done":true},{"text":"c"}]}]},
+        {"id":2,"name":"Second"}]}"#,
+    )
+    .unwrap()
+  }
+
+  #[test]
+  fn renders_a_workspace_summary_and_project_cards() {
+    let state = sample();
+    let labels = doc_labels("uk");
+    let html = render_report(&state.select_projects(None).unwrap(), true, "uk");
+    assert!(html.starts_with("<!DOCTYPE html><html lang=\"uk\">"));
+    assert!(html.contains(&format!("<title>{}</title>", escape(labels.workspace))));
+    assert!(html.contains(r##"<a href="#project-0">Релиз &lt;1.0&gt;</a>"##));
+    assert!(html.contains(r##"<a href="#project-1">Second</a>"##));
+    assert!(html.contains("style=\"width: 66%\"></div></div><span class=\"progress-caption\">2/3</span>"));
+    assert!(html.contains("<p class=\"description\">Описание\nвторая строка</p>"));
+    assert!(html.contains("<span class=\"step-text\">a &lt;b&gt;</span>"));
+    assert!(html.contains("<span class=\"step-count\">1/2</span>"));
+    assert!(html.contains("<summary>Заметка &amp; план</summary><div class=\"note-body\"><p>Тело <strong>жирно</strong></p>"));
+    assert!(!html.contains("<script>") && !html.contains("alert(1)"));
+    assert!(html.contains(&format!("<p class=\"empty\">{}</p>", escape(labels.no_steps))));
+    assert!(html.contains(&format!("<p class=\"empty\">{}</p>", escape(labels.no_notes))));
+  }
+
+  #[test]
+  fn single_project_reports_skip_the_summary() {
+    let state = sample();
+    let html = render_report(&state.select_projects(Some(&EntityId::Num(2))).unwrap(), false, "en");
+    assert!(html.contains("<title>Second</title>") && html.contains("<h1>Second</h1>"));
+    assert!(!html.contains("<table>"));
+    assert_eq!(html.matches("<section class=\"card\"").count(), 1);
+    assert!(html.contains("style=\"width: 0%\""));
+  }
+
+  #[test]
+  fn computes_progress_percentages() {
+    assert_eq!(progress_percent(0, 0), 0);
+    assert_eq!(progress_percent(1, 3), 33);
+    assert_eq!(progress_percent(3, 3), 100);
+  }
+}
@@ -0,0 +1,475 @@
+use crate::{AppState, EntityId, NoteState, data_dir};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use similar::{Algorithm, ChangeTag, TextDiff};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::Manager;
+
+pub(crate) const REVISIONS_DIR: &str = "revisions";
+const THROTTLE_SECONDS: i64 = 5 * 60;
+const MAX_REVISIONS: usize = 200;
+const SHRINK_GUARD_CHARS: usize = 200;
+const REWRITE_SIMILARITY: f32 = 0.6;
+
+type NoteKey = (EntityId, EntityId);
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+pub(crate) struct Revision {
+  id: u64,
+  created_at: String,
+  updated_at: String,
+  title: String,
+  body: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct RevisionLog {
+  project_id: Option<EntityId>,
+  note_id: Option<EntityId>,
+  revisions: Vec<Revision>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RevisionSummary {
+  id: u64,
+  created_at: String,
+  updated_at: String,
+  title: String,
+  chars: usize,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum DiffMode {
+  Line,
+  Word,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DiffChunk {
+  tag: &'static str,
+  text: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RevisionDiff {
+  from: u64,
+  to: u64,
+  title: Vec<DiffChunk>,
+  body: Vec<DiffChunk>,
+  insertions: usize,
+  deletions: usize,
+}
+
+struct TrackedNote {
+  fingerprint: u64,
+  open_revision: Option<u64>,
+}
+
+#[derive(Default)]
+pub(crate) struct RevisionTracker(Mutex<HashMap<NoteKey, TrackedNote>>);
+
+fn fingerprint(note: &NoteState) -> u64 {
+  let mut hasher = DefaultHasher::new();
+  note.title.hash(&mut hasher);
+  note.body.hash(&mut hasher);
+  hasher.finish()
+}
+
+fn log_path(root: &Path, project_id: &EntityId, note_id: &EntityId) -> PathBuf {
+  let hash = hex::encode(Sha256::digest(format!("{project_id}/{note_id}")));
+  root.join(REVISIONS_DIR).join(format!("{hash}.json"))
+}
+
+fn read_log(path: &Path) -> Result<RevisionLog, String> {
+  if !path.exists() {
+    return Ok(RevisionLog::default());
+  }
+  let content = fs::read_to_string(path).map_err(|err| err.to_string())?;
+  serde_json::from_str(&content).map_err(|err| err.to_string())
+}
+
+fn write_log(path: &Path, log: &RevisionLog) -> Result<(), String> {
+  let parent = path.parent().expect("revision log has a directory");
+  fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+  let content = serde_json::to_string_pretty(log).map_err(|err| err.to_string())?;
+  let partial = path.with_extension("json.part");
+  fs::write(&partial, content).map_err(|err| err.to_string())?;
+  fs::rename(&partial, path).map_err(|err| err.to_string())
+}
+
+fn seconds_since(timestamp: &str, now: DateTime<Utc>) -> Option<i64> {
+  let created = DateTime::parse_from_rfc3339(timestamp).ok()?;
+  Some((now - created.with_timezone(&Utc)).num_seconds())
+}
+
+fn drastic_shrink(previous: &str, next: &str) -> bool {
+  let previous = previous.chars().count();
+  previous > SHRINK_GUARD_CHARS && next.chars().count() * 2 < previous
+}
+
+fn rewritten(head: &Revision, note: &NoteState) -> bool {
+  let previous = format!("{}\n{}", head.title, head.body);
+  let next = format!("{}\n{}", note.title, note.body);
+  TextDiff::from_words(&previous, &next).ratio() < REWRITE_SIMILARITY
+}
+
+fn append_or_update(log: &mut RevisionLog, note: &NoteState, open_revision: &mut Option<u64>, now: DateTime<Utc>) -> bool {
+  let timestamp = now.to_rfc3339();
+  if let Some(head) = log.revisions.last_mut() {
+    if head.title == note.title && head.body == note.body {
+      return false;
+    }
+    let within_throttle = seconds_since(&head.created_at, now).is_some_and(|age| age < THROTTLE_SECONDS);
+    let keeps_head = !drastic_shrink(&head.body, &note.body) && !rewritten(head, note);
+    if *open_revision == Some(head.id) && within_throttle && keeps_head {
+      head.title = note.title.clone();
+      head.body = note.body.clone();
+      head.updated_at = timestamp;
+      return true;
+    }
+  }
+  let id = log.revisions.last().map(|head| head.id + 1).unwrap_or(1);
+  log.revisions.push(Revision {
+    id,
+    created_at: timestamp.clone(),
+    updated_at: timestamp,
+    title: note.title.clone(),
+    body: note.body.clone(),
+  });
+  if log.revisions.len() > MAX_REVISIONS {
+    let excess = log.revisions.len() - MAX_REVISIONS;
+    log.revisions.drain(..excess);
+  }
+  *open_revision = Some(id);
+  true
+}
+
+fn record_notes(
+  root: &Path,
+  tracked: &mut HashMap<NoteKey, TrackedNote>,
+  state: &AppState,
+  now: DateTime<Utc>,
+) -> Result<usize, String> {
+  let mut written = 0;
+  for project in &state.projects {
+    let Some(project_id) = &project.id else {
+      continue;
+    };
+    for note in &project.notes {
+      let Some(note_id) = &note.id else {
+        continue;
+      };
+      let note_key = (project_id.clone(), note_id.clone());
+      let note_fingerprint = fingerprint(note);
+      let seen = tracked.get(&note_key);
+      if seen.is_some_and(|entry| entry.fingerprint == note_fingerprint) {
+        continue;
+      }
+      let first_sight = seen.is_none();
+      let mut open_revision = seen.and_then(|entry| entry.open_revision);
+      let path = log_path(root, project_id, note_id);
+      let mut log = read_log(&path)?;
+      if append_or_update(&mut log, note, &mut open_revision, now) {
+        log.project_id = Some(project_id.clone());
+        log.note_id = Some(note_id.clone());
+        write_log(&path, &log)?;
+        written += 1;
+      }
+      if first_sight {
+        open_revision = None;
+      }
+      tracked.insert(note_key, TrackedNote { fingerprint: note_fingerprint, open_revision });
+    }
+  }
+  Ok(written)
+}
+
+pub(crate) fn record(app: &tauri::AppHandle, state: &AppState) -> Result<usize, String> {
+  let root = data_dir(app)?;
+  let tracker = app.state::<RevisionTracker>();
+  let mut tracked = tracker.0.lock().map_err(|err| err.to_string())?;
+  record_notes(&root, &mut tracked, state, Utc::now())
+}
+
+pub(crate) fn forget_sessions(app: &tauri::AppHandle) -> Result<(), String> {
+  let tracker = app.state::<RevisionTracker>();
+  tracker.0.lock().map_err(|err| err.to_string())?.clear();
+  Ok(())
+}
+
+fn find(log: &RevisionLog, revision_id: u64) -> Result<&Revision, String> {
+  log
+    .revisions
+    .iter()
+    .find(|revision| revision.id == revision_id)
+    .ok_or_else(|| "revision not found".to_string())
+}
+
+fn summaries(log: &RevisionLog) -> Vec<RevisionSummary> {
+  log
+    .revisions
+    .iter()
+    .rev()
+    .map(|revision| RevisionSummary {
+      id: revision.id,
+      created_at: revision.created_at.clone(),
+      updated_at: revision.updated_at.clone(),
+      title: revision.title.clone(),
+      chars: revision.body.chars().count(),
+    })
+    .collect()
+}
+
+fn push_chunk(chunks: &mut Vec<DiffChunk>, tag: &'static str, text: &str) {
+  match chunks.last_mut() {
+    Some(last) if last.tag == tag => last.text.push_str(text),
+    _ => chunks.push(DiffChunk { tag, text: text.to_string() }),
+  }
+}
+
+fn diff_text(old: &str, new: &str, mode: DiffMode, insertions: &mut usize, deletions: &mut usize) -> Vec<DiffChunk> {
+  let mut config = TextDiff::configure();
+  config.algorithm(Algorithm::Patience);
+  let diff = match mode {
+    DiffMode::Line => config.diff_lines(old, new),
+    DiffMode::Word => config.diff_words(old, new),
+  };
+  let mut chunks = Vec::new();
+  for change in diff.iter_all_changes() {
+    let tag = match change.tag() {
+      ChangeTag::Equal => "equal",
+      ChangeTag::Insert => {
+        *insertions += 1;
+        "insert"
+      }
+      ChangeTag::Delete => {
+        *deletions += 1;
+        "delete"
+      }
+    };
+    push_chunk(&mut chunks, tag, change.value());
+  }
+  chunks
+}
+
+fn diff(log: &RevisionLog, from: u64, to: u64, mode: DiffMode) -> Result<RevisionDiff, String> {
+  let old = find(log, from)?;
+  let new = find(log, to)?;
+  let (mut insertions, mut deletions) = (0, 0);
+  let title = diff_text(&old.title, &new.title, DiffMode::Word, &mut insertions, &mut deletions);
+  let body = diff_text(&old.body, &new.body, mode, &mut insertions, &mut deletions);
+  Ok(RevisionDiff { from, to, insertions, deletions, title, body })
+}
+
+fn restore(mut state: AppState, project_id: &EntityId, note_id: &EntityId, revision: &Revision) -> Result<AppState, String> {
+  let project = state
+    .projects
+    .iter_mut()
+    .find(|project| project.id.as_ref() == Some(project_id))
+    .ok_or_else(|| "project not found".to_string())?;
+  match project.notes.iter_mut().find(|note| note.id.as_ref() == Some(note_id)) {
+    Some(note) => {
+      note.title = revision.title.clone();
+      note.body = revision.body.clone();
+    }
+    None => project.notes.push(NoteState {
+      id: Some(note_id.clone()),
+      title: revision.title.clone(),
+      body: revision.body.clone(),
+      ..NoteState::default()
+    }),
+  }
+  Ok(state)
+}
+
+fn note_log(app: &tauri::AppHandle, project_id: &EntityId, note_id: &EntityId) -> Result<RevisionLog, String> {
+  read_log(&log_path(&data_dir(app)?, project_id, note_id))
+}
+
+#[tauri::command]
+pub(crate) fn note_revisions(
+  app: tauri::AppHandle,
+  project_id: EntityId,
+  note_id: EntityId,
+) -> Result<Vec<RevisionSummary>, String> {
+  Ok(summaries(&note_log(&app, &project_id, &note_id)?))
+}
+
+#[tauri::command]
+pub(crate) fn note_revision(
+  app: tauri::AppHandle,
+  project_id: EntityId,
+  note_id: EntityId,
+  revision_id: u64,
+) -> Result<Revision, String> {
+  find(&note_log(&app, &project_id, &note_id)?, revision_id).cloned()
+}
+
+#[tauri::command]
+pub(crate) fn diff_revisions(
+  app: tauri::AppHandle,
+  project_id: EntityId,
+  note_id: EntityId,
+  from: u64,
+  to: u64,
+  mode: DiffMode,
+) -> Result<RevisionDiff, String> {
+  diff(&note_log(&app, &project_id, &note_id)?, from, to, mode)
+}
+
+#[tauri::command]
+pub(crate) fn restore_revision(
+  app: tauri::AppHandle,
+  state: AppState,
+  project_id: EntityId,
+  note_id: EntityId,
+  revision_id: u64,
+) -> Result<AppState, String> {
+  record(&app, &state)?;
+  let revision = find(&note_log(&app, &project_id, &note_id)?, revision_id)?.clone();
+  let tracker = app.state::<RevisionTracker>();
+  if let Some(entry) = tracker.0.lock().map_err(|err| err.to_string())?.get_mut(&(project_id.clone(), note_id.clone())) {
+    entry.open_revision = None;
+  }
+  restore(state, &project_id, &note_id, &revision)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::ProjectState;
+  use crate::test_support::TempDir;
+  use chrono::Duration;
+
+  fn state(title: &str, body: &str) -> AppState {
+    let mut state = AppState::default();
+    state.projects.push(ProjectState {
+      id: Some(EntityId::Num(1)),
+      notes: vec![NoteState { id: Some(EntityId::Num(7)), title: title.into(), body: body.into(), ..NoteState::default() }],
+      ..ProjectState::default()
+    });
+    state
+  }
+
+  fn bodies(root: &Path) -> Vec<String> {
+    let log = read_log(&log_path(root, &EntityId::Num(1), &EntityId::Num(7))).unwrap();
+    log.revisions.into_iter().map(|revision| revision.body).collect()
+  }
+
+  fn at(seconds: i64) -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339("2026-10-19T12:00:00Z").unwrap().with_timezone(&Utc) + Duration::seconds(seconds)
+  }
+
+  #[test]
+  fn throttles_small_edits_into_the_open_revision() {
+    let dir = TempDir::new("revisions", "throttle");
+    let mut tracked = HashMap::new();
+    let base = "Meeting notes about the quarterly roadmap and hiring plan";
+    assert_eq!(record_notes(&dir.0, &mut tracked, &state("Plan", base), at(0)).unwrap(), 1);
+    assert_eq!(record_notes(&dir.0, &mut tracked, &state("Plan", base), at(1)).unwrap(), 0);
+    let typo = format!("{base}.");
+    record_notes(&dir.0, &mut tracked, &state("Plan", &typo), at(10)).unwrap();
+    let more = format!("{base}. Budget");
+    record_notes(&dir.0, &mut tracked, &state("Plan", &more), at(60)).unwrap();
+    assert_eq!(bodies(&dir.0), vec![base.to_string(), more.clone()]);
+    let later = format!("{more} approved");
+    record_notes(&dir.0, &mut tracked, &state("Plan", &later), at(60 + THROTTLE_SECONDS)).unwrap();
+    assert_eq!(bodies(&dir.0), vec![base.to_string(), more, later]);
+  }
+
+  #[test]
+  fn snapshots_rewrites_inside_the_throttle_window() {
+    let dir = TempDir::new("revisions", "rewrite");
+    let mut tracked = HashMap::new();
+    record_notes(&dir.0, &mut tracked, &state("Plan", "seed"), at(0)).unwrap();
+    record_notes(&dir.0, &mut tracked, &state("Plan", "buy milk and bread"), at(5)).unwrap();
+    record_notes(&dir.0, &mut tracked, &state("Plan", "call the plumber today"), at(10)).unwrap();
+    record_notes(&dir.0, &mut tracked, &state("Plan", "call the plumber today!"), at(15)).unwrap();
+    assert_eq!(bodies(&dir.0), vec!["seed", "buy milk and bread", "call the plumber today!"]);
+
+    let first = "alpha beta gamma delta ".repeat(20);
+    let second = "one two three four five ".repeat(19);
+    record_notes(&dir.0, &mut tracked, &state("Plan", &first), at(100)).unwrap();
+    record_notes(&dir.0, &mut tracked, &state("Plan", &second), at(110)).unwrap();
+    let all = bodies(&dir.0);
+    assert_eq!(&all[all.len() - 2..], [first, second]);
+    record_notes(&dir.0, &mut tracked, &state("Renamed entirely", &all[all.len() - 1]), at(120)).unwrap();
+    assert_eq!(bodies(&dir.0).len(), all.len());
+  }
+
+  #[test]
+  fn shrink_guard_keeps_the_longer_text() {
+    let dir = TempDir::new("revisions", "shrink");
+    let mut tracked = HashMap::new();
+    let long = "word ".repeat(100);
+    record_notes(&dir.0, &mut tracked, &state("Plan", "seed"), at(0)).unwrap();
+    record_notes(&dir.0, &mut tracked, &state("Plan", &long), at(5)).unwrap();
+    let cut = "word ".repeat(45);
+    record_notes(&dir.0, &mut tracked, &state("Plan", &cut), at(10)).unwrap();
+    assert_eq!(bodies(&dir.0), vec!["seed".to_string(), long, cut]);
+    assert!(drastic_shrink(&"x".repeat(201), &"x".repeat(100)));
+    assert!(!drastic_shrink(&"x".repeat(201), &"x".repeat(101)));
+    assert!(!drastic_shrink(&"x".repeat(150), ""));
+  }
+
+  #[test]
+  fn first_sight_seals_the_previous_session() {
+    let dir = TempDir::new("revisions", "session");
+    let mut tracked = HashMap::new();
+    record_notes(&dir.0, &mut tracked, &state("Plan", "draft one"), at(0)).unwrap();
+    let mut restarted = HashMap::new();
+    record_notes(&dir.0, &mut restarted, &state("Plan", "draft one two"), at(5)).unwrap();
+    record_notes(&dir.0, &mut restarted, &state("Plan", "draft one two three"), at(10)).unwrap();
+    assert_eq!(bodies(&dir.0), vec!["draft one", "draft one two", "draft one two three"]);
+  }
+
+  #[test]
+  fn trims_old_revisions() {
+    let mut log = RevisionLog::default();
+    let mut open = None;
+    for index in 0..MAX_REVISIONS + 5 {
+      let note = NoteState { body: format!("v{index}"), ..NoteState::default() };
+      append_or_update(&mut log, &note, &mut open, at(index as i64 * (THROTTLE_SECONDS + 1)));
+    }
+    assert_eq!(log.revisions.len(), MAX_REVISIONS);
+    assert_eq!(log.revisions[0].id, 6);
+  }
+
+  #[test]
+  fn diffs_and_restores() {
+    let mut log = RevisionLog::default();
+    for (id, body) in [(1, "one\ntwo\n"), (2, "one\nthree\n"), (3, "one\nthree\nfour\nfive\n")] {
+      log.revisions.push(Revision { id, title: "Plan".into(), body: body.into(), ..Revision::default() });
+    }
+    let lines = diff(&log, 1, 2, DiffMode::Line).unwrap();
+    assert_eq!((lines.insertions, lines.deletions), (1, 1));
+    assert_eq!(lines.body[0], DiffChunk { tag: "equal", text: "one\n".into() });
+    let added = diff(&log, 1, 3, DiffMode::Line).unwrap();
+    assert_eq!((added.insertions, added.deletions), (3, 1));
+    assert_eq!(added.body[2], DiffChunk { tag: "insert", text: "three\nfour\nfive\n".into() });
+    let words = diff(&log, 1, 2, DiffMode::Word).unwrap();
+    assert!(words.body.iter().any(|chunk| chunk.tag == "insert" && chunk.text.contains("three")));
+    assert!(diff(&log, 1, 9, DiffMode::Line).is_err());
+    assert_eq!(summaries(&log)[0].id, 3);
+
+    let restored = restore(state("Plan", "new"), &EntityId::Num(1), &EntityId::Num(7), &log.revisions[0]).unwrap();
+    assert_eq!(restored.projects[0].notes[0].body, "one\ntwo\n");
+    let mut empty = state("Plan", "");
+    empty.projects[0].notes.clear();
+    let restored = restore(empty, &EntityId::Num(1), &EntityId::Num(7), &log.revisions[1]).unwrap();
+    assert_eq!(restored.projects[0].notes[0].id, Some(EntityId::Num(7)));
+    assert!(restore(state("Plan", ""), &EntityId::Num(2), &EntityId::Num(7), &log.revisions[0]).is_err());
+  }
+}
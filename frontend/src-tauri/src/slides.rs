@@ -0,0 +1,228 @@
+use crate::labels::{DocLabels, doc_labels};
+use crate::markup::escape;
+use crate::report::progress_percent;
+use crate::{AppState, EntityId, ProjectState, file_stem, markdown, step_tree, write_export_file};
+use std::fmt::Write;
+
+const SLIDES_CSS: &str = r#"
+:root {
+  --bg: #07090f;
+  --card: #0f121c;
+  --text: #eef3ff;
+  --muted: #9eabc2;
+  --line: rgba(139, 157, 197, 0.24);
+  --accent: #5ca8ff;
+  --accent-2: #495fff;
+}
+* { box-sizing: border-box; }
+html, body { height: 100%; margin: 0; }
+body {
+  font-family: 'Manrope', 'Segoe UI', 'Helvetica Neue', Arial, sans-serif;
+  color: var(--text);
+  background: var(--bg);
+  overflow: hidden;
+}
+.slide {
+  position: absolute;
+  inset: 0;
+  display: none;
+  flex-direction: column;
+  justify-content: center;
+  padding: 6vh 8vw;
+  background: radial-gradient(circle at 8% -12%, #1e274a 0%, transparent 40%), var(--bg);
+}
+.slide.active { display: flex; }
+.slide.title { align-items: flex-start; }
+.eyebrow { margin: 0 0 1vh; color: var(--accent); font-size: 2vh; letter-spacing: 0.08em; text-transform: uppercase; }
+h1 { margin: 0 0 3vh; font-size: 7vh; line-height: 1.1; }
+h2 { margin: 0 0 3vh; font-size: 5vh; line-height: 1.15; }
+p { margin: 0 0 1.6vh; font-size: 3vh; line-height: 1.45; max-width: 70em; }
+.muted { color: var(--muted); }
+.badges { display: flex; flex-wrap: wrap; gap: 1.2vh; margin-bottom: 3vh; }
+.badge { border: 1px solid var(--line); border-radius: 999px; padding: 0.6vh 1.8vh; font-size: 2.2vh; color: var(--muted); }
+.badge.accent { color: var(--text); border-color: var(--accent); }
+.progress { width: min(60vw, 900px); display: grid; gap: 1.2vh; }
+.progress-track { height: 1.6vh; border-radius: 999px; background: rgba(139, 157, 197, 0.16); overflow: hidden; }
+.progress-fill { height: 100%; border-radius: inherit; background: linear-gradient(90deg, var(--accent-2), var(--accent)); }
+.progress-caption { font-size: 2.4vh; color: var(--muted); }
+.note-body { overflow: auto; max-height: 70vh; }
+.note-body pre { font-size: 2vh; padding: 1.6vh; border-radius: 1vh; background: #2b303b; }
+.steps { list-style: none; margin: 0; padding: 0; display: grid; gap: 1.4vh; overflow: auto; max-height: 70vh; }
+.steps li { font-size: 3vh; display: flex; gap: 1.6vh; align-items: baseline; }
+.steps li::before { content: ''; flex: none; width: 2.4vh; height: 2.4vh; border: 2px solid var(--accent); border-radius: 0.6vh; }
+.counter { position: fixed; right: 3vw; bottom: 3vh; color: var(--muted); font-size: 1.8vh; }
+.hint { position: fixed; left: 3vw; bottom: 3vh; color: var(--muted); font-size: 1.6vh; opacity: 0.7; }
+@media print {
+  body { overflow: visible; }
+  .slide { position: relative; display: flex; height: 100vh; page-break-after: always; }
+  .counter, .hint { display: none; }
+}
+"#;
+
+const SLIDES_JS: &str = r#"
+(function () {
+  var slides = Array.prototype.slice.call(document.querySelectorAll('.slide'));
+  var counter = document.querySelector('.counter');
+  var current = 0;
+  function show(index) {
+    current = Math.max(0, Math.min(slides.length - 1, index));
+    slides.forEach(function (slide, i) { slide.classList.toggle('active', i === current); });
+    counter.textContent = (current + 1) + ' / ' + slides.length;
+    if (location.hash !== '#' + (current + 1)) { history.replaceState(null, '', '#' + (current + 1)); }
+  }
+  document.addEventListener('keydown', function (event) {
+    if (event.altKey || event.ctrlKey || event.metaKey) { return; }
+    switch (event.key) {
+      case 'ArrowRight': case 'ArrowDown': case 'PageDown': case ' ': case 'Enter': show(current + 1); break;
+      case 'ArrowLeft': case 'ArrowUp': case 'PageUp': case 'Backspace': show(current - 1); break;
+      case 'Home': show(0); break;
+      case 'End': show(slides.length - 1); break;
+      case 'f': case 'F':
+        if (document.fullscreenElement) { document.exitFullscreen(); } else if (document.documentElement.requestFullscreen) { document.documentElement.requestFullscreen(); }
+        break;
+      default: return;
+    }
+    event.preventDefault();
+  });
+  document.addEventListener('click', function (event) {
+    if (event.target.closest('a')) { return; }
+    show(event.clientX < window.innerWidth / 3 ? current - 1 : current + 1);
+  });
+  window.addEventListener('hashchange', function () { show(parseInt(location.hash.slice(1), 10) - 1 || 0); });
+  show(parseInt(location.hash.slice(1), 10) - 1 || 0);
+})();
+"#;
+
+fn write_title_slide(out: &mut String, project: &ProjectState, labels: &DocLabels, generated: &str) {
+  let _ = write!(
+    out,
+    r#"<section class="slide title"><p class="eyebrow">{}</p><h1>{}</h1><div class="badges">"#,
+    escape(labels.app_title),
+    escape(project.name.trim()),
+  );
+  let status = project.status.trim();
+  if status.is_empty() {
+    let _ = write!(out, r#"<span class="badge">{}: {}</span>"#, escape(labels.project_status), escape(labels.status_not_set));
+  } else {
+    let _ = write!(out, r#"<span class="badge accent">{}: {}</span>"#, escape(labels.project_status), escape(status));
+  }
+  if project.pinned {
+    let _ = write!(out, r#"<span class="badge">{}</span>"#, escape(labels.pinned));
+  }
+  let _ = write!(
+    out,
+    r#"<span class="badge">{} {}</span><span class="badge">{} {}</span></div>"#,
+    project.notes.len(),
+    escape(labels.note_count),
+    step_tree::count(&project.steps),
+    escape(labels.step_count),
+  );
+  let (done, total) = project.step_progress();
+  let _ = write!(
+    out,
+    r#"<div class="progress"><div class="progress-track"><div class="progress-fill" style="width: {}%"></div></div><span class="progress-caption">{}: {}</span></div><p class="muted" style="margin-top: 3vh">{}: {generated}</p></section>"#,
+    progress_percent(done, total),
+    escape(labels.progress),
+    escape(&labels.progress_caption(done, total)),
+    escape(labels.generated),
+  );
+}
+
+fn write_note_slide(out: &mut String, title: &str, body: &str) {
+  let _ = write!(out, r#"<section class="slide"><h2>{}</h2><div class="note-body">"#, escape(title.trim()));
+  out.push_str(&markdown::render(body));
+  out.push_str("</div></section>");
+}
+
+fn write_open_steps_slide(out: &mut String, project: &ProjectState, labels: &DocLabels) {
+  let _ = write!(out, r#"<section class="slide"><h2>{}</h2>"#, escape(labels.open_steps));
+  let mut open = project.steps.clone();
+  step_tree::retain_open(&mut open);
+  if open.is_empty() {
+    let message = if project.steps.is_empty() { labels.no_steps } else { labels.no_open_steps };
+    let _ = write!(out, r#"<p class="muted">{}</p>"#, escape(message));
+  } else {
+    out.push_str(r#"<ul class="steps">"#);
+    for (depth, step) in step_tree::flatten(&open) {
+      let _ = write!(out, r#"<li style="margin-left: {}vh">{}</li>"#, depth * 4, escape(step.text.trim()));
+    }
+    out.push_str("</ul>");
+  }
+  out.push_str("</section>");
+}
+
+pub(crate) fn render_slides(project: &ProjectState, language: &str) -> String {
+  let labels = doc_labels(language);
+  let generated = chrono::Local::now().format("%Y-%m-%d %H:%M").to_string();
+
+  let mut out = String::new();
+  let _ = write!(
+    out,
+    r#"<!DOCTYPE html><html lang="{}"><head><meta charset="utf-8"><meta name="viewport" content="width=device-width, initial-scale=1"><title>{}</title><style>{SLIDES_CSS}{}</style></head><body>"#,
+    escape(language),
+    escape(project.name.trim()),
+    markdown::highlight_css(),
+  );
+  write_title_slide(&mut out, project, labels, &generated);
+  for note in &project.notes {
+    write_note_slide(&mut out, &note.title, &note.body);
+  }
+  write_open_steps_slide(&mut out, project, labels);
+  let _ = write!(
+    out,
+    r#"<div class="hint">← → · F</div><div class="counter"></div><script>{SLIDES_JS}</script></body></html>"#
+  );
+  out
+}
+
+#[tauri::command]
+pub(crate) fn export_slides(app: tauri::AppHandle, state: AppState, project_id: EntityId) -> Result<String, String> {
+  let project = state.select_projects(Some(&project_id))?[0];
+  let html = render_slides(project, &state.settings.language);
+  let stem = format!("{}-slides", file_stem(&project.name, "project"));
+  write_export_file(&app, &stem, ".html", html.as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn sample() -> ProjectState {
+    serde_json::from_str(
+      r#"{"id":1,"name":"Проект <b>","status":"Активен","pinned":true,
+        "notes":[{"id":2,"title":"Заметка & план","body":"Первая **строка**\n\n<script>alert(1)</script>"}],
+        "steps":[{"id":4,"text":"Готово","done":true},{"text":"Шаг 2","children":[{"text":"Вложенный <i>"},{"text":"Закрыт","done":true}]}]}"#,
+    )
+    .unwrap()
+  }
+
+  #[test]
+  fn renders_title_note_and_open_step_slides() {
+    let html = render_slides(&sample(), "en");
+    let labels = doc_labels("en");
+    assert!(html.starts_with("<!DOCTYPE html><html lang=\"en\">"));
+    assert!(html.contains("<title>Проект &lt;b&gt;</title>"));
+    assert_eq!(html.matches("<section class=\"slide").count(), 3);
+    assert!(html.contains("<h1>Проект &lt;b&gt;</h1>"));
+    assert!(html.contains(&format!("<span class=\"badge accent\">{}: Активен</span>", labels.project_status)));
+    assert!(html.contains("style=\"width: 66%\""));
+    assert!(html.contains("<h2>Заметка &amp; план</h2>"));
+    assert!(html.contains("<strong>строка</strong>") && !html.contains("alert(1)"));
+    assert!(html.contains("<li style=\"margin-left: 0vh\">Шаг 2</li><li style=\"margin-left: 4vh\">Вложенный &lt;i&gt;</li></ul>"));
+    assert!(!html.contains("Закрыт") && !html.contains(">Готово<"));
+  }
+
+  #[test]
+  fn explains_missing_or_finished_steps() {
+    let labels = doc_labels("ru");
+    let empty = render_slides(&ProjectState { name: "Пусто".into(), ..Default::default() }, "ru");
+    assert!(empty.contains(&format!("<p class=\"muted\">{}</p>", escape(labels.no_steps))));
+    assert!(empty.contains(&format!("{}: {}", labels.project_status, escape(labels.status_not_set))));
+
+    let mut finished = sample();
+    step_tree::for_each_mut(&mut finished.steps, &mut |step| step.done = true);
+    let html = render_slides(&finished, "ru");
+    assert!(html.contains(&format!("<p class=\"muted\">{}</p>", escape(labels.no_open_steps))));
+    assert!(html.contains("style=\"width: 100%\""));
+  }
+}
@@ -0,0 +1,245 @@
+use crate::{EntityId, ProjectState, StepState};
+
+pub(crate) fn leaf_progress(steps: &[StepState]) -> (usize, usize) {
+  steps.iter().fold((0, 0), |(done, total), step| {
+    let (step_done, step_total) = step.progress();
+    (done + step_done, total + step_total)
+  })
+}
+
+pub(crate) fn flatten(steps: &[StepState]) -> Vec<(usize, &StepState)> {
+  fn walk<'a>(steps: &'a [StepState], depth: usize, out: &mut Vec<(usize, &'a StepState)>) {
+    for step in steps {
+      out.push((depth, step));
+      walk(&step.children, depth + 1, out);
+    }
+  }
+  let mut out = Vec::new();
+  walk(steps, 0, &mut out);
+  out
+}
+
+pub(crate) fn count(steps: &[StepState]) -> usize {
+  steps.iter().map(|step| 1 + count(&step.children)).sum()
+}
+
+pub(crate) fn retain_open(steps: &mut Vec<StepState>) {
+  steps.retain(|step| !step.done);
+  for step in steps {
+    retain_open(&mut step.children);
+  }
+}
+
+pub(crate) fn for_each_mut(steps: &mut [StepState], f: &mut impl FnMut(&mut StepState)) {
+  for step in steps {
+    f(step);
+    for_each_mut(&mut step.children, f);
+  }
+}
+
+pub(crate) fn sync_parents(steps: &mut [StepState]) {
+  for step in steps {
+    if step.children.is_empty() {
+      continue;
+    }
+    sync_parents(&mut step.children);
+    step.done = step.children.iter().all(|child| child.done);
+  }
+}
+
+pub(crate) fn find_path(steps: &[StepState], id: &EntityId) -> Option<Vec<usize>> {
+  for (index, step) in steps.iter().enumerate() {
+    if step.id.as_ref() == Some(id) {
+      return Some(vec![index]);
+    }
+    if let Some(mut path) = find_path(&step.children, id) {
+      path.insert(0, index);
+      return Some(path);
+    }
+  }
+  None
+}
+
+pub(crate) fn list_at<'a>(steps: &'a mut Vec<StepState>, parent_path: &[usize]) -> &'a mut Vec<StepState> {
+  parent_path.iter().fold(steps, |list, index| &mut list[*index].children)
+}
+
+pub(crate) fn step_at_mut<'a>(steps: &'a mut [StepState], path: &[usize]) -> &'a mut StepState {
+  let (first, rest) = path.split_first().expect("step path is never empty");
+  rest.iter().fold(&mut steps[*first], |step, index| &mut step.children[*index])
+}
+
+fn step_path(project: &ProjectState, id: &EntityId) -> Result<Vec<usize>, String> {
+  find_path(&project.steps, id).ok_or_else(|| "step not found".to_string())
+}
+
+fn indent(project: &mut ProjectState, id: &EntityId) -> Result<(), String> {
+  let path = step_path(project, id)?;
+  let (index, parent_path) = path.split_last().expect("step path is never empty");
+  if *index == 0 {
+    return Err("step has no previous sibling to nest under".to_string());
+  }
+  let siblings = list_at(&mut project.steps, parent_path);
+  let step = siblings.remove(*index);
+  siblings[index - 1].children.push(step);
+  Ok(())
+}
+
+fn outdent(project: &mut ProjectState, id: &EntityId) -> Result<(), String> {
+  let path = step_path(project, id)?;
+  if path.len() < 2 {
+    return Err("step is already at the top level".to_string());
+  }
+  let (index, parent_path) = path.split_last().expect("step path is never empty");
+  let step = list_at(&mut project.steps, parent_path).remove(*index);
+  let (parent_index, grandparent_path) = parent_path.split_last().expect("nested step has a parent");
+  list_at(&mut project.steps, grandparent_path).insert(parent_index + 1, step);
+  Ok(())
+}
+
+fn move_subtree(project: &mut ProjectState, id: &EntityId, parent_id: Option<&EntityId>, index: usize) -> Result<(), String> {
+  let path = step_path(project, id)?;
+  if let Some(parent_id) = parent_id {
+    let parent_path = step_path(project, parent_id)?;
+    if parent_path.starts_with(&path) {
+      return Err("a step cannot be moved into its own sub-steps".to_string());
+    }
+  }
+  let (step_index, old_parent_path) = path.split_last().expect("step path is never empty");
+  let step = list_at(&mut project.steps, old_parent_path).remove(*step_index);
+  let target = match parent_id {
+    Some(parent_id) => {
+      let parent_path = step_path(project, parent_id)?;
+      &mut step_at_mut(&mut project.steps, &parent_path).children
+    }
+    None => &mut project.steps,
+  };
+  let index = index.min(target.len());
+  target.insert(index, step);
+  Ok(())
+}
+
+fn finish(mut project: ProjectState, auto_complete: bool) -> ProjectState {
+  if auto_complete {
+    sync_parents(&mut project.steps);
+  }
+  project
+}
+
+#[tauri::command]
+pub(crate) fn indent_step(mut project: ProjectState, step_id: EntityId, auto_complete: bool) -> Result<ProjectState, String> {
+  indent(&mut project, &step_id)?;
+  Ok(finish(project, auto_complete))
+}
+
+#[tauri::command]
+pub(crate) fn outdent_step(mut project: ProjectState, step_id: EntityId, auto_complete: bool) -> Result<ProjectState, String> {
+  outdent(&mut project, &step_id)?;
+  Ok(finish(project, auto_complete))
+}
+
+#[tauri::command]
+pub(crate) fn move_step(
+  mut project: ProjectState,
+  step_id: EntityId,
+  parent_id: Option<EntityId>,
+  index: usize,
+  auto_complete: bool,
+) -> Result<ProjectState, String> {
+  move_subtree(&mut project, &step_id, parent_id.as_ref(), index)?;
+  Ok(finish(project, auto_complete))
+}
+
+#[tauri::command]
+pub(crate) fn set_step_done(mut project: ProjectState, step_id: EntityId, done: bool, auto_complete: bool) -> Result<ProjectState, String> {
+  let path = step_path(&project, &step_id)?;
+  let step = step_at_mut(&mut project.steps, &path);
+  step.done = done;
+  if auto_complete {
+    for_each_mut(&mut step.children, &mut |child| child.done = done);
+  }
+  Ok(finish(project, auto_complete))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn sample() -> ProjectState {
+    serde_json::from_str(
+      r#"{"id":"p","name":"P","steps":[
+        {"id":"a","text":"A","children":[{"id":"a1","text":"A1","done":true},{"id":"a2","text":"A2","children":[{"id":"a2x","text":"A2x"}]}]},
+        {"id":"b","text":"B"},{"id":"c","text":"C","done":true}]}"#,
+    )
+    .unwrap()
+  }
+
+  fn id(value: &str) -> EntityId {
+    EntityId::Str(value.to_string())
+  }
+
+  fn shape(steps: &[StepState]) -> String {
+    steps
+      .iter()
+      .map(|step| {
+        let done = if step.done { "+" } else { "" };
+        if step.children.is_empty() {
+          format!("{}{done}", step.text)
+        } else {
+          format!("{}{done}({})", step.text, shape(&step.children))
+        }
+      })
+      .collect::<Vec<_>>()
+      .join(",")
+  }
+
+  #[test]
+  fn walks_nested_steps() {
+    let project = sample();
+    assert_eq!(project.step_progress(), (2, 4));
+    assert_eq!(count(&project.steps), 6);
+    let flat: Vec<(usize, &str)> = flatten(&project.steps).into_iter().map(|(depth, step)| (depth, step.text.as_str())).collect();
+    assert_eq!(flat, [(0, "A"), (1, "A1"), (1, "A2"), (2, "A2x"), (0, "B"), (0, "C")]);
+    assert_eq!(find_path(&project.steps, &id("a2x")), Some(vec![0, 1, 0]));
+    assert_eq!(find_path(&project.steps, &id("zz")), None);
+
+    let mut open = project.steps.clone();
+    retain_open(&mut open);
+    assert_eq!(shape(&open), "A(A2(A2x)),B");
+  }
+
+  #[test]
+  fn indents_and_outdents_steps() {
+    let project = sample();
+    assert_eq!(shape(&indent_step(project.clone(), id("b"), false).unwrap().steps), "A(A1+,A2(A2x),B),C+");
+    assert_eq!(shape(&outdent_step(project.clone(), id("a2x"), false).unwrap().steps), "A(A1+,A2,A2x),B,C+");
+    assert_eq!(shape(&outdent_step(project.clone(), id("a2"), false).unwrap().steps), "A(A1+),A2(A2x),B,C+");
+    assert!(indent_step(project.clone(), id("a"), false).is_err_and(|err| err.contains("no previous sibling")));
+    assert!(outdent_step(project.clone(), id("b"), false).is_err_and(|err| err.contains("top level")));
+    assert!(indent_step(project, id("zz"), false).is_err_and(|err| err == "step not found"));
+  }
+
+  #[test]
+  fn moves_subtrees_but_not_into_themselves() {
+    let project = sample();
+    assert_eq!(shape(&move_step(project.clone(), id("c"), Some(id("a2")), 0, false).unwrap().steps), "A(A1+,A2(C+,A2x)),B");
+    assert_eq!(shape(&move_step(project.clone(), id("a2"), None, 99, false).unwrap().steps), "A(A1+),B,C+,A2(A2x)");
+    assert!(move_step(project.clone(), id("a"), Some(id("a2x")), 0, false).is_err());
+    assert!(move_step(project.clone(), id("a"), Some(id("a")), 0, false).is_err());
+    assert!(move_step(project, id("a"), Some(id("zz")), 0, false).is_err_and(|err| err == "step not found"));
+  }
+
+  #[test]
+  fn completes_parents_and_children_when_asked() {
+    let project = sample();
+    let done = set_step_done(project.clone(), id("a2x"), true, true).unwrap();
+    assert_eq!(shape(&done.steps), "A+(A1+,A2+(A2x+)),B,C+");
+    assert_eq!(shape(&set_step_done(done, id("a1"), false, true).unwrap().steps), "A(A1,A2+(A2x+)),B,C+");
+    assert_eq!(shape(&set_step_done(project.clone(), id("a"), true, true).unwrap().steps), "A+(A1+,A2+(A2x+)),B,C+");
+
+    let manual = set_step_done(project.clone(), id("a2x"), true, false).unwrap();
+    assert_eq!(shape(&manual.steps), "A(A1+,A2(A2x+)),B,C+");
+    assert_eq!(manual.step_progress(), (3, 4));
+    assert_eq!(shape(&indent_step(project, id("c"), true).unwrap().steps), "A(A1+,A2(A2x)),B+(C+)");
+  }
+}
@@ -0,0 +1,349 @@
+use crate::{AppState, EntityId, StepState, file_stem, step_tree, write_export_file};
+use serde::Serialize;
+use std::collections::HashMap;
+
+pub(crate) const UTF8_BOM: &str = "\u{feff}";
+const HEADER: [&str; 7] = ["project_id", "project", "step_id", "parent_id", "order", "text", "done"];
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CsvUnmatchedRow {
+  line: u64,
+  text: String,
+  reason: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CsvImportResult {
+  state: AppState,
+  updated: usize,
+  appended: usize,
+  unmatched: Vec<CsvUnmatchedRow>,
+}
+
+struct Columns {
+  project_id: Option<usize>,
+  project: Option<usize>,
+  step_id: Option<usize>,
+  parent_id: Option<usize>,
+  order: Option<usize>,
+  text: usize,
+  done: Option<usize>,
+}
+
+impl Columns {
+  fn from_header(header: &csv::StringRecord) -> Result<Self, String> {
+    let find = |name: &str| {
+      header
+        .iter()
+        .position(|value| value.trim().trim_start_matches(UTF8_BOM).eq_ignore_ascii_case(name))
+    };
+    Ok(Self {
+      project_id: find("project_id"),
+      project: find("project"),
+      step_id: find("step_id"),
+      parent_id: find("parent_id"),
+      order: find("order"),
+      text: find("text").ok_or_else(|| "csv is missing the \"text\" column".to_string())?,
+      done: find("done"),
+    })
+  }
+}
+
+fn field(record: &csv::StringRecord, column: Option<usize>) -> Option<&str> {
+  column
+    .and_then(|index| record.get(index))
+    .map(str::trim)
+    .filter(|value| !value.is_empty())
+}
+
+fn is_formula(value: &str) -> bool {
+  value.trim_start_matches('\'').starts_with(['=', '+', '-', '@'])
+}
+
+fn escape_cell(value: &str) -> String {
+  if is_formula(value) { format!("'{value}") } else { value.to_string() }
+}
+
+fn unescape_cell(value: &str) -> &str {
+  match value.strip_prefix('\'') {
+    Some(rest) if is_formula(rest) => rest,
+    _ => value,
+  }
+}
+
+fn parse_done(value: &str) -> bool {
+  matches!(
+    value.to_lowercase().as_str(),
+    "true" | "1" | "yes" | "y" | "x" | "+" | "да" | "так" | "done"
+  )
+}
+
+fn detect_delimiter(content: &str) -> u8 {
+  let header = content.lines().next().unwrap_or_default();
+  if header.matches(';').count() > header.matches(',').count() { b';' } else { b',' }
+}
+
+fn write_steps(
+  writer: &mut csv::Writer<Vec<u8>>,
+  project_key: &str,
+  project_name: &str,
+  parent_key: &str,
+  steps: &[StepState],
+) -> Result<(), String> {
+  for (index, step) in steps.iter().enumerate() {
+    let step_key = step.id.as_ref().map(ToString::to_string).unwrap_or_default();
+    let order = (index + 1).to_string();
+    let text = escape_cell(&step.text);
+    writer
+      .write_record([
+        project_key,
+        project_name,
+        step_key.as_str(),
+        parent_key,
+        order.as_str(),
+        text.as_str(),
+        if step.done { "true" } else { "false" },
+      ])
+      .map_err(|err| err.to_string())?;
+    write_steps(writer, project_key, project_name, &step_key, &step.children)?;
+  }
+  Ok(())
+}
+
+fn sort_level(steps: &mut Vec<StepState>, path: &mut Vec<usize>, orders: &HashMap<Vec<usize>, f64>) {
+  for (index, step) in steps.iter_mut().enumerate() {
+    path.push(index);
+    sort_level(&mut step.children, path, orders);
+    path.pop();
+  }
+  let mut keyed: Vec<(f64, StepState)> = std::mem::take(steps)
+    .into_iter()
+    .enumerate()
+    .map(|(index, step)| {
+      path.push(index);
+      let order = orders.get(path).copied().unwrap_or((index + 1) as f64);
+      path.pop();
+      (order, step)
+    })
+    .collect();
+  keyed.sort_by(|a, b| a.0.total_cmp(&b.0));
+  *steps = keyed.into_iter().map(|(_, step)| step).collect();
+}
+
+pub(crate) fn render_steps_csv(state: &AppState, project_id: Option<&EntityId>) -> Result<String, String> {
+  let projects = state.select_projects(project_id)?;
+  let mut writer = csv::Writer::from_writer(Vec::new());
+  writer.write_record(HEADER).map_err(|err| err.to_string())?;
+  for project in projects {
+    let project_key = project.id.as_ref().map(ToString::to_string).unwrap_or_default();
+    write_steps(&mut writer, &project_key, &escape_cell(&project.name), "", &project.steps)?;
+  }
+  let bytes = writer.into_inner().map_err(|err| err.to_string())?;
+  let body = String::from_utf8(bytes).map_err(|err| err.to_string())?;
+  Ok(format!("{UTF8_BOM}{body}"))
+}
+
+pub(crate) fn apply_steps_csv(mut state: AppState, content: &str) -> Result<CsvImportResult, String> {
+  let content = content.trim_start_matches(UTF8_BOM);
+  let mut reader = csv::ReaderBuilder::new()
+    .delimiter(detect_delimiter(content))
+    .flexible(true)
+    .from_reader(content.as_bytes());
+  let columns = Columns::from_header(reader.headers().map_err(|err| err.to_string())?)?;
+
+  let mut updated = 0;
+  let mut appended = 0;
+  let mut unmatched = Vec::new();
+  let mut orders: HashMap<usize, HashMap<Vec<usize>, f64>> = HashMap::new();
+
+  for record in reader.records() {
+    let record = record.map_err(|err| err.to_string())?;
+    let line = record.position().map(|position| position.line()).unwrap_or_default();
+    let text = field(&record, Some(columns.text)).map(unescape_cell).unwrap_or_default().to_string();
+    let step_id = field(&record, columns.step_id).map(EntityId::parse);
+    let parent_id = field(&record, columns.parent_id).map(EntityId::parse);
+    if text.is_empty() && step_id.is_none() {
+      continue;
+    }
+
+    let project_id = field(&record, columns.project_id).map(EntityId::parse);
+    let project_name = field(&record, columns.project).map(unescape_cell);
+    let order = field(&record, columns.order).and_then(|value| value.replace(',', ".").parse::<f64>().ok());
+    let done = field(&record, columns.done).map(parse_done);
+    let mut unmatched_row = |reason: &str| {
+      unmatched.push(CsvUnmatchedRow { line, text: text.clone(), reason: reason.to_string() });
+    };
+
+    let project_index = match (&project_id, project_name) {
+      (Some(id), _) => state.projects.iter().position(|project| project.id.as_ref() == Some(id)),
+      (None, Some(name)) => state.projects.iter().position(|project| project.name.trim() == name),
+      (None, None) => None,
+    };
+
+    if let Some(step_id) = step_id {
+      let found = state.projects.iter().enumerate().find_map(|(p_index, project)| {
+        if project_index.is_some_and(|index| index != p_index) {
+          return None;
+        }
+        step_tree::find_path(&project.steps, &step_id).map(|path| (p_index, path))
+      });
+      let Some((p_index, path)) = found else {
+        unmatched_row("step id not found");
+        continue;
+      };
+      let step = step_tree::step_at_mut(&mut state.projects[p_index].steps, &path);
+      if !text.is_empty() {
+        step.text = text.clone();
+      }
+      if let Some(done) = done {
+        step.done = done;
+      }
+      if let Some(order) = order {
+        orders.entry(p_index).or_default().insert(path, order);
+      }
+      updated += 1;
+      continue;
+    }
+
+    let Some(p_index) = project_index else {
+      unmatched_row("project not found");
+      continue;
+    };
+    let project = &mut state.projects[p_index];
+    let (mut path, steps) = match &parent_id {
+      Some(parent_id) => match step_tree::find_path(&project.steps, parent_id) {
+        Some(path) => {
+          let parent = step_tree::step_at_mut(&mut project.steps, &path);
+          (path, &mut parent.children)
+        }
+        None => {
+          unmatched_row("parent step not found");
+          continue;
+        }
+      },
+      None => (Vec::new(), &mut project.steps),
+    };
+    steps.push(StepState {
+      id: Some(EntityId::generate()),
+      text: text.clone(),
+      done: done.unwrap_or(false),
+      ..Default::default()
+    });
+    path.push(steps.len() - 1);
+    orders.entry(p_index).or_default().insert(path, order.unwrap_or(f64::INFINITY));
+    appended += 1;
+  }
+
+  for (p_index, project_orders) in &orders {
+    sort_level(&mut state.projects[*p_index].steps, &mut Vec::new(), project_orders);
+  }
+
+  Ok(CsvImportResult { state, updated, appended, unmatched })
+}
+
+#[tauri::command]
+pub(crate) fn export_steps_csv(app: tauri::AppHandle, state: AppState, project_id: Option<EntityId>) -> Result<String, String> {
+  let content = render_steps_csv(&state, project_id.as_ref())?;
+  let stem = match project_id.as_ref().and_then(|id| state.select_projects(Some(id)).ok()) {
+    Some(projects) => file_stem(&projects[0].name, "project"),
+    None => "workspace".to_string(),
+  };
+  write_export_file(&app, &format!("{stem}.steps"), ".csv", content.as_bytes())
+}
+
+#[tauri::command]
+pub(crate) fn import_steps_csv(state: AppState, content: String) -> Result<CsvImportResult, String> {
+  apply_steps_csv(state, &content)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn sample() -> AppState {
+    serde_json::from_str(
+      r#"{"projects":[
+        {"id":"p1","name":"Релиз","steps":[
+          {"id":"s1","text":"Собрать, \"подписать\"","done":true,"children":[{"id":"s3","text":"Child"}]},
+          {"id":"s2","text":"Опубликовать"}]},
+        {"id":2,"name":"Second"}]}"#,
+    )
+    .unwrap()
+  }
+
+  #[test]
+  fn exports_with_bom_and_nested_rows() {
+    let csv = render_steps_csv(&sample(), None).unwrap();
+    assert!(csv.starts_with(UTF8_BOM));
+    let lines: Vec<&str> = csv.trim_start_matches(UTF8_BOM).lines().collect();
+    assert_eq!(lines[0], HEADER.join(","));
+    assert_eq!(lines[1], r#"p1,Релиз,s1,,1,"Собрать, ""подписать""",true"#);
+    assert_eq!(lines[2], "p1,Релиз,s3,s1,1,Child,false");
+    assert_eq!(lines[3], "p1,Релиз,s2,,2,Опубликовать,false");
+    assert_eq!(lines.len(), 4);
+    let single = render_steps_csv(&sample(), Some(&EntityId::Num(2))).unwrap();
+    assert_eq!(single.trim_start_matches(UTF8_BOM).lines().count(), 1);
+    assert!(render_steps_csv(&sample(), Some(&EntityId::Num(9))).is_err());
+  }
+
+  #[test]
+  fn round_trip_leaves_state_unchanged() {
+    let state = sample();
+    let csv = render_steps_csv(&state, None).unwrap();
+    let result = apply_steps_csv(state.clone(), &csv).unwrap();
+    assert_eq!((result.updated, result.appended, result.unmatched.len()), (3, 0, 0));
+    assert_eq!(serde_json::to_value(&result.state.projects).unwrap(), serde_json::to_value(&state.projects).unwrap());
+  }
+
+  #[test]
+  fn escapes_formula_cells_and_restores_them_on_import() {
+    let mut state = sample();
+    state.projects[0].name = "=Release".into();
+    state.projects[0].steps[0].text = "+1 review".into();
+    state.projects[0].steps[1].text = "'@team".into();
+    state.projects[0].steps[0].children[0].text = "'quoted".into();
+    let csv = render_steps_csv(&state, Some(&EntityId::parse("p1"))).unwrap();
+    let lines: Vec<&str> = csv.trim_start_matches(UTF8_BOM).lines().collect();
+    assert_eq!(lines[1], "p1,'=Release,s1,,1,'+1 review,true");
+    assert_eq!(lines[2], "p1,'=Release,s3,s1,1,'quoted,false");
+    assert_eq!(lines[3], "p1,'=Release,s2,,2,''@team,false");
+    let result = apply_steps_csv(state.clone(), &csv).unwrap();
+    assert_eq!(serde_json::to_value(&result.state.projects).unwrap(), serde_json::to_value(&state.projects).unwrap());
+    let appended = apply_steps_csv(state, "project,text
+'=Release,'-draft
+").unwrap();
+    assert_eq!(appended.state.projects[0].steps[2].text, "-draft");
+  }
+
+  #[test]
+  fn updates_appends_and_reorders() {
+    let csv = "\u{feff}project_id;project;step_id;parent_id;order;text;done\n\
+      p1;;s2;;0;Опубликовать сейчас;да\n\
+      ;Релиз;;;;Новый шаг;\n\
+      p1;;;s1;0,5;Sub first;x\n\
+      ;;;;;;\n";
+    let result = apply_steps_csv(sample(), csv).unwrap();
+    assert_eq!((result.updated, result.appended), (1, 2));
+    let steps = &result.state.projects[0].steps;
+    assert_eq!(steps.iter().map(|step| step.text.as_str()).collect::<Vec<_>>(), ["Опубликовать сейчас", "Собрать, \"подписать\"", "Новый шаг"]);
+    assert!(steps[0].done);
+    assert!(steps[2].id.is_some(), "appended steps need ids");
+    assert_eq!(steps[1].children[0].text, "Sub first");
+    assert!(steps[1].children[0].done && steps[1].children[0].id.is_some());
+    assert_ne!(steps[2].id, steps[1].children[0].id);
+  }
+
+  #[test]
+  fn reports_unmatched_and_malformed_rows() {
+    let csv = "step_id,project,parent_id,text\nmissing,,,Ghost\n,Nowhere,,Orphan\n,Релиз,nope,Lost child\n";
+    let result = apply_steps_csv(sample(), csv).unwrap();
+    let reasons: Vec<_> = result.unmatched.iter().map(|row| (row.line, row.reason.as_str())).collect();
+    assert_eq!(reasons, [(2, "step id not found"), (3, "project not found"), (4, "parent step not found")]);
+    assert_eq!(result.appended + result.updated, 0);
+    assert!(apply_steps_csv(sample(), "project,done\nRelease,true\n").is_err());
+    assert!(apply_steps_csv(sample(), "").is_err());
+    assert!(apply_steps_csv(sample(), "text\n\"unterminated\n").is_ok_and(|result| result.unmatched.len() == 1));
+  }
+}
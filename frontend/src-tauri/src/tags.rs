@@ -0,0 +1,249 @@
+use crate::{AppState, EntityId, StepState, step_tree};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum TaggedKind {
+  Project,
+  Note,
+  Step,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TagCount {
+  tag: String,
+  projects: usize,
+  notes: usize,
+  steps: usize,
+  total: usize,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub(crate) struct TagQuery {
+  all: Vec<String>,
+  any: Vec<String>,
+  none: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TaggedEntity {
+  kind: TaggedKind,
+  project_id: Option<EntityId>,
+  project: String,
+  id: Option<EntityId>,
+  title: String,
+  tags: Vec<String>,
+}
+
+pub(crate) fn clean_tag(value: &str) -> String {
+  value.trim().trim_start_matches('#').split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn tag_key(value: &str) -> String {
+  clean_tag(value).to_lowercase()
+}
+
+pub(crate) fn clean_tags<'a>(values: impl IntoIterator<Item = &'a str>) -> Vec<String> {
+  let mut result: Vec<String> = Vec::new();
+  for value in values {
+    let tag = clean_tag(value);
+    if !tag.is_empty() && !result.iter().any(|existing| existing.to_lowercase() == tag.to_lowercase()) {
+      result.push(tag);
+    }
+  }
+  result
+}
+
+fn for_each_tag_list(state: &mut AppState, f: &mut impl FnMut(&mut Vec<String>)) {
+  for project in &mut state.projects {
+    f(&mut project.tags);
+    for note in &mut project.notes {
+      f(&mut note.tags);
+    }
+    step_tree::for_each_mut(&mut project.steps, &mut |step: &mut StepState| f(&mut step.tags));
+  }
+}
+
+fn entities(state: &AppState) -> Vec<TaggedEntity> {
+  let mut result = Vec::new();
+  for project in &state.projects {
+    let mut push = |kind, id: &Option<EntityId>, title: &str, tags: &[String]| {
+      result.push(TaggedEntity {
+        kind,
+        project_id: project.id.clone(),
+        project: project.name.trim().to_string(),
+        id: id.clone(),
+        title: title.trim().to_string(),
+        tags: tags.to_vec(),
+      });
+    };
+    push(TaggedKind::Project, &project.id, &project.name, &project.tags);
+    for note in &project.notes {
+      push(TaggedKind::Note, &note.id, &note.title, &note.tags);
+    }
+    for (_, step) in step_tree::flatten(&project.steps) {
+      push(TaggedKind::Step, &step.id, &step.text, &step.tags);
+    }
+  }
+  result
+}
+
+fn build_index(state: &AppState) -> Vec<TagCount> {
+  let mut index: BTreeMap<String, TagCount> = BTreeMap::new();
+  for entity in entities(state) {
+    for tag in clean_tags(entity.tags.iter().map(String::as_str)) {
+      let count = index.entry(tag.to_lowercase()).or_insert_with(|| TagCount { tag: tag.clone(), ..Default::default() });
+      match entity.kind {
+        TaggedKind::Project => count.projects += 1,
+        TaggedKind::Note => count.notes += 1,
+        TaggedKind::Step => count.steps += 1,
+      }
+      count.total += 1;
+    }
+  }
+  index.into_values().collect()
+}
+
+fn merge_into(state: &mut AppState, sources: &[String], target: &str) -> Result<(), String> {
+  let target = clean_tag(target);
+  if target.is_empty() {
+    return Err("tag name is empty".to_string());
+  }
+  let sources: Vec<String> = sources.iter().map(|source| tag_key(source)).filter(|key| !key.is_empty()).collect();
+  for_each_tag_list(state, &mut |tags| {
+    if !tags.iter().any(|tag| sources.contains(&tag_key(tag))) {
+      return;
+    }
+    let renamed: Vec<String> = tags
+      .iter()
+      .map(|tag| if sources.contains(&tag_key(tag)) { target.clone() } else { tag.clone() })
+      .collect();
+    *tags = clean_tags(renamed.iter().map(String::as_str));
+  });
+  Ok(())
+}
+
+fn remove(state: &mut AppState, tag: &str) {
+  let key = tag_key(tag);
+  for_each_tag_list(state, &mut |tags| tags.retain(|existing| tag_key(existing) != key));
+}
+
+fn query(state: &AppState, query: &TagQuery) -> Vec<TaggedEntity> {
+  let keys = |values: &[String]| -> Vec<String> { values.iter().map(|value| tag_key(value)).filter(|key| !key.is_empty()).collect() };
+  let (all, any, none) = (keys(&query.all), keys(&query.any), keys(&query.none));
+  if all.is_empty() && any.is_empty() {
+    return Vec::new();
+  }
+  entities(state)
+    .into_iter()
+    .filter(|entity| {
+      let tags: Vec<String> = entity.tags.iter().map(|tag| tag_key(tag)).collect();
+      all.iter().all(|key| tags.contains(key))
+        && (any.is_empty() || any.iter().any(|key| tags.contains(key)))
+        && !none.iter().any(|key| tags.contains(key))
+    })
+    .collect()
+}
+
+#[tauri::command]
+pub(crate) fn tag_index(state: AppState) -> Vec<TagCount> {
+  build_index(&state)
+}
+
+#[tauri::command]
+pub(crate) fn rename_tag(mut state: AppState, from: String, to: String) -> Result<AppState, String> {
+  merge_into(&mut state, &[from], &to)?;
+  Ok(state)
+}
+
+#[tauri::command]
+pub(crate) fn merge_tags(mut state: AppState, tags: Vec<String>, into: String) -> Result<AppState, String> {
+  merge_into(&mut state, &tags, &into)?;
+  Ok(state)
+}
+
+#[tauri::command]
+pub(crate) fn delete_tag(mut state: AppState, tag: String) -> AppState {
+  remove(&mut state, &tag);
+  state
+}
+
+#[tauri::command]
+pub(crate) fn query_tags(state: AppState, query: TagQuery) -> Vec<TaggedEntity> {
+  self::query(&state, &query)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn sample() -> AppState {
+    serde_json::from_str(
+      r##"{"projects":[{"id":"p","name":"P","tags":["Work","urgent"],
+        "notes":[{"id":"n","title":"N","tags":["work","idea"]}],
+        "steps":[{"id":"s","text":"S","tags":["urgent"],"children":[{"id":"c","text":"C","tags":["Idea","Work"]}]}]}]}"##,
+    )
+    .unwrap()
+  }
+
+  fn titles(state: &AppState, all: &[&str], any: &[&str], none: &[&str]) -> Vec<String> {
+    let owned = |values: &[&str]| values.iter().map(|value| value.to_string()).collect();
+    let query = TagQuery { all: owned(all), any: owned(any), none: owned(none) };
+    self::query(state, &query).into_iter().map(|entity| entity.title).collect()
+  }
+
+  #[test]
+  fn cleans_and_deduplicates_tags() {
+    assert_eq!(clean_tag("  #Deep   work "), "Deep work");
+    assert_eq!(clean_tags(["Idea", "#idea", " ", "#", "Ёлка", "ёлка"]), ["Idea", "Ёлка"]);
+  }
+
+  #[test]
+  fn counts_tags_case_insensitively() {
+    let index = serde_json::to_value(build_index(&sample())).unwrap();
+    assert_eq!(
+      index,
+      serde_json::json!([
+        { "tag": "idea", "projects": 0, "notes": 1, "steps": 1, "total": 2 },
+        { "tag": "urgent", "projects": 1, "notes": 0, "steps": 1, "total": 2 },
+        { "tag": "Work", "projects": 1, "notes": 1, "steps": 1, "total": 3 },
+      ])
+    );
+  }
+
+  #[test]
+  fn queries_with_all_any_and_none() {
+    let state = sample();
+    assert_eq!(titles(&state, &["work"], &[], &[]), ["P", "N", "C"]);
+    assert_eq!(titles(&state, &["WORK", "#idea"], &[], &[]), ["N", "C"]);
+    assert_eq!(titles(&state, &[], &["urgent", "idea"], &["work"]), ["S"]);
+    assert!(titles(&state, &[], &[], &["work"]).is_empty());
+    assert!(titles(&state, &[" "], &["#"], &[]).is_empty());
+  }
+
+  #[test]
+  fn renames_merges_and_deletes_tags_everywhere() {
+    let state = sample();
+    let merged = merge_tags(state.clone(), vec!["idea".into(), "URGENT".into()], "Later".into()).unwrap();
+    assert_eq!(merged.projects[0].tags, ["Work", "Later"]);
+    assert_eq!(merged.projects[0].steps[0].children[0].tags, ["Later", "Work"]);
+
+    let renamed = rename_tag(state.clone(), "work".into(), "idea".into()).unwrap();
+    assert_eq!(renamed.projects[0].notes[0].tags, ["idea"]);
+    assert_eq!(renamed.projects[0].steps[0].children[0].tags, ["Idea"]);
+
+    let deleted = delete_tag(state.clone(), "#Work".into());
+    assert_eq!(deleted.projects[0].tags, ["urgent"]);
+    assert!(build_index(&deleted).iter().all(|count| count.tag != "Work"));
+  }
+
+  #[test]
+  fn rejects_blank_target_names() {
+    assert!(rename_tag(sample(), "work".into(), " # ".into()).is_err_and(|err| err == "tag name is empty"));
+    assert!(merge_tags(sample(), vec!["work".into()], String::new()).is_err());
+  }
+}
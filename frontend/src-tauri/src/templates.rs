@@ -0,0 +1,412 @@
+use crate::time_zone::Zone;
+use crate::{EntityId, ProjectState, StepState, data_dir, step_tree, write_export_file};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::Path;
+
+pub(crate) const LIBRARY_FILE: &str = "templates.json";
+const LIBRARY_FORMAT: &str = "project-notes-studio-templates";
+const LIBRARY_VERSION: u32 = 1;
+const BUILT_IN: [&str; 2] = ["date", "year"];
+const MAX_PLACEHOLDER_CHARS: usize = 40;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct ProjectTemplate {
+  id: String,
+  name: String,
+  created_at: String,
+  project: ProjectState,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TemplateLibrary {
+  format: String,
+  version: u32,
+  #[serde(default)]
+  templates: Vec<ProjectTemplate>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TemplateSummary {
+  id: String,
+  name: String,
+  description: String,
+  created_at: String,
+  notes: usize,
+  steps: usize,
+  placeholders: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TemplateImportResult {
+  added: usize,
+  replaced: usize,
+}
+
+impl Default for TemplateLibrary {
+  fn default() -> Self {
+    TemplateLibrary { format: LIBRARY_FORMAT.to_string(), version: LIBRARY_VERSION, templates: Vec::new() }
+  }
+}
+
+fn read_library(root: &Path) -> Result<TemplateLibrary, String> {
+  let path = root.join(LIBRARY_FILE);
+  if !path.exists() {
+    return Ok(TemplateLibrary::default());
+  }
+  parse_library(&fs::read_to_string(path).map_err(|err| err.to_string())?)
+}
+
+fn write_library(root: &Path, library: &TemplateLibrary) -> Result<(), String> {
+  let content = serde_json::to_string_pretty(library).map_err(|err| err.to_string())?;
+  fs::write(root.join(LIBRARY_FILE), content).map_err(|err| err.to_string())
+}
+
+fn parse_library(content: &str) -> Result<TemplateLibrary, String> {
+  let library: TemplateLibrary = serde_json::from_str(content).map_err(|err| err.to_string())?;
+  if library.format != LIBRARY_FORMAT {
+    return Err("file is not a template library".to_string());
+  }
+  if library.version > LIBRARY_VERSION {
+    return Err("template library was created by a newer version".to_string());
+  }
+  Ok(library)
+}
+
+fn for_each_text(project: &mut ProjectState, f: &mut impl FnMut(&mut String)) {
+  fn steps(steps: &mut [StepState], f: &mut impl FnMut(&mut String)) {
+    step_tree::for_each_mut(steps, &mut |step| {
+      f(&mut step.text);
+      step.tags.iter_mut().for_each(&mut *f);
+    });
+  }
+  f(&mut project.name);
+  f(&mut project.description);
+  project.tags.iter_mut().for_each(&mut *f);
+  for note in &mut project.notes {
+    f(&mut note.title);
+    f(&mut note.body);
+    note.tags.iter_mut().for_each(&mut *f);
+    steps(&mut note.steps, f);
+  }
+  steps(&mut project.steps, f);
+}
+
+fn placeholder_at(text: &str, start: usize) -> Option<(usize, &str)> {
+  let inner_start = start + 2;
+  let end = inner_start + text[inner_start..].find("}}")?;
+  let name = text[inner_start..end].trim();
+  let valid = !name.is_empty()
+    && name.chars().count() <= MAX_PLACEHOLDER_CHARS
+    && !name.contains(['{', '}', '\n']);
+  valid.then_some((end + 2, name))
+}
+
+fn replace_placeholders(text: &str, values: &BTreeMap<String, String>) -> String {
+  let mut out = String::with_capacity(text.len());
+  let mut offset = 0;
+  while let Some(found) = text[offset..].find("{{") {
+    let start = offset + found;
+    out.push_str(&text[offset..start]);
+    match placeholder_at(text, start) {
+      Some((end, name)) => {
+        match values.get(&name.to_lowercase()) {
+          Some(value) => out.push_str(value),
+          None => out.push_str(&text[start..end]),
+        }
+        offset = end;
+      }
+      None => {
+        out.push_str("{{");
+        offset = start + 2;
+      }
+    }
+  }
+  out.push_str(&text[offset..]);
+  out
+}
+
+fn placeholders(project: &ProjectState) -> Vec<String> {
+  let mut project = project.clone();
+  let mut names = BTreeSet::new();
+  for_each_text(&mut project, &mut |text| {
+    let mut offset = 0;
+    while let Some(found) = text[offset..].find("{{") {
+      let start = offset + found;
+      match placeholder_at(text, start) {
+        Some((end, name)) => {
+          let name = name.to_lowercase();
+          if !BUILT_IN.contains(&name.as_str()) {
+            names.insert(name);
+          }
+          offset = end;
+        }
+        None => offset = start + 2,
+      }
+    }
+  });
+  names.into_iter().collect()
+}
+
+fn sanitize_steps(steps: &mut [StepState]) {
+  step_tree::for_each_mut(steps, &mut |step| {
+    step.id = None;
+    step.done = false;
+    step.due_at = None;
+    step.remind_at = None;
+    step.time_entries.clear();
+    step.completions.clear();
+  });
+}
+
+fn sanitize(mut project: ProjectState) -> ProjectState {
+  project.id = None;
+  project.status.clear();
+  project.pinned = false;
+  project.due_at = None;
+  project.remind_at = None;
+  for note in &mut project.notes {
+    note.id = None;
+    note.attachments.clear();
+    sanitize_steps(&mut note.steps);
+  }
+  sanitize_steps(&mut project.steps);
+  project
+}
+
+fn assign_ids(project: &mut ProjectState) {
+  fn steps(steps: &mut [StepState]) {
+    step_tree::for_each_mut(steps, &mut |step| step.id = Some(EntityId::generate()));
+  }
+  project.id = Some(EntityId::generate());
+  for note in &mut project.notes {
+    note.id = Some(EntityId::generate());
+    steps(&mut note.steps);
+  }
+  steps(&mut project.steps);
+}
+
+fn instantiate(template: &ProjectTemplate, values: &BTreeMap<String, String>, zone: &Zone) -> ProjectState {
+  let today = zone.local(&Utc::now()).date();
+  let mut filled: BTreeMap<String, String> = BTreeMap::from([
+    ("date".to_string(), today.format("%Y-%m-%d").to_string()),
+    ("year".to_string(), today.format("%Y").to_string()),
+  ]);
+  for (name, value) in values {
+    let value = value.trim();
+    if !value.is_empty() {
+      filled.insert(name.trim().to_lowercase(), value.to_string());
+    }
+  }
+  let mut project = template.project.clone();
+  for_each_text(&mut project, &mut |text| *text = replace_placeholders(text, &filled));
+  assign_ids(&mut project);
+  project
+}
+
+fn summary(template: &ProjectTemplate) -> TemplateSummary {
+  let project = &template.project;
+  TemplateSummary {
+    id: template.id.clone(),
+    name: template.name.clone(),
+    description: project.description.clone(),
+    created_at: template.created_at.clone(),
+    notes: project.notes.len(),
+    steps: step_tree::count(&project.steps),
+    placeholders: placeholders(project),
+  }
+}
+
+fn upsert(library: &mut TemplateLibrary, template: ProjectTemplate) -> bool {
+  let existing = library.templates.iter().position(|entry| {
+    entry.id == template.id || entry.name.trim().to_lowercase() == template.name.trim().to_lowercase()
+  });
+  match existing {
+    Some(index) => {
+      let id = library.templates[index].id.clone();
+      library.templates[index] = ProjectTemplate { id, ..template };
+      true
+    }
+    None => {
+      library.templates.push(template);
+      false
+    }
+  }
+}
+
+fn merge(library: &mut TemplateLibrary, imported: TemplateLibrary) -> TemplateImportResult {
+  let mut result = TemplateImportResult::default();
+  for template in imported.templates {
+    let name = template.name.trim().to_string();
+    if name.is_empty() {
+      continue;
+    }
+    let template = ProjectTemplate {
+      id: if template.id.trim().is_empty() { EntityId::generate().to_string() } else { template.id },
+      name,
+      project: sanitize(template.project),
+      ..template
+    };
+    if upsert(library, template) {
+      result.replaced += 1;
+    } else {
+      result.added += 1;
+    }
+  }
+  library.templates.sort_by_key(|template| template.name.to_lowercase());
+  result
+}
+
+pub(crate) fn merge_library_file(root: &Path, content: &str) -> Result<TemplateImportResult, String> {
+  let imported = parse_library(content.trim_start_matches('\u{feff}'))?;
+  let mut library = read_library(root)?;
+  let result = merge(&mut library, imported);
+  write_library(root, &library)?;
+  Ok(result)
+}
+
+fn template_by_id<'a>(library: &'a TemplateLibrary, template_id: &str) -> Result<&'a ProjectTemplate, String> {
+  library
+    .templates
+    .iter()
+    .find(|template| template.id == template_id)
+    .ok_or_else(|| "template not found".to_string())
+}
+
+#[tauri::command]
+pub(crate) fn project_templates(app: tauri::AppHandle) -> Result<Vec<TemplateSummary>, String> {
+  Ok(read_library(&data_dir(&app)?)?.templates.iter().map(summary).collect())
+}
+
+#[tauri::command]
+pub(crate) fn save_project_template(app: tauri::AppHandle, project: ProjectState, name: String) -> Result<TemplateSummary, String> {
+  let name = name.trim();
+  if name.is_empty() {
+    return Err("template name is empty".to_string());
+  }
+  let root = data_dir(&app)?;
+  let mut library = read_library(&root)?;
+  let template = ProjectTemplate {
+    id: EntityId::generate().to_string(),
+    name: name.to_string(),
+    created_at: Utc::now().to_rfc3339(),
+    project: sanitize(project),
+  };
+  upsert(&mut library, template);
+  library.templates.sort_by_key(|template| template.name.to_lowercase());
+  write_library(&root, &library)?;
+  let saved = library
+    .templates
+    .iter()
+    .find(|template| template.name == name)
+    .expect("saved template is in the library");
+  Ok(summary(saved))
+}
+
+#[tauri::command]
+pub(crate) fn delete_project_template(app: tauri::AppHandle, template_id: String) -> Result<(), String> {
+  let root = data_dir(&app)?;
+  let mut library = read_library(&root)?;
+  let before = library.templates.len();
+  library.templates.retain(|template| template.id != template_id);
+  if library.templates.len() == before {
+    return Err("template not found".to_string());
+  }
+  write_library(&root, &library)
+}
+
+#[tauri::command]
+pub(crate) fn create_project_from_template(
+  app: tauri::AppHandle,
+  template_id: String,
+  values: BTreeMap<String, String>,
+  time_zone: String,
+) -> Result<ProjectState, String> {
+  let library = read_library(&data_dir(&app)?)?;
+  let template = template_by_id(&library, &template_id)?;
+  Ok(instantiate(template, &values, &Zone::from_settings(&time_zone)))
+}
+
+#[tauri::command]
+pub(crate) fn export_template_library(app: tauri::AppHandle) -> Result<String, String> {
+  let library = read_library(&data_dir(&app)?)?;
+  if library.templates.is_empty() {
+    return Err("template library is empty".to_string());
+  }
+  let content = serde_json::to_string_pretty(&library).map_err(|err| err.to_string())?;
+  write_export_file(&app, "project-templates", ".json", content.as_bytes())
+}
+
+#[tauri::command]
+pub(crate) fn import_template_library(app: tauri::AppHandle, content: String) -> Result<TemplateImportResult, String> {
+  merge_library_file(&data_dir(&app)?, &content)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn template() -> ProjectTemplate {
+    let project: ProjectState = serde_json::from_str(
+      r#"{"id":7,"name":"Site for {{ Client }}","status":"active","pinned":true,"dueAt":"2026-11-01","tags":["{{client}}"],
+        "notes":[{"id":1,"title":"Kickoff {{date}}","body":"Meet {{client}} in {{year}}, keep {{unknown}} and {{ }} and {{x",
+          "attachments":[{"name":"a"}]}],
+        "steps":[{"id":"s","text":"Brief","done":true,"timeEntries":[{"startedAt":"2026-10-01T10:00:00Z"}],
+          "children":[{"id":"c","text":"Call {{contact}}","done":true}]}]}"#,
+    )
+    .unwrap();
+    ProjectTemplate { id: "t1".into(), name: "Site".into(), created_at: String::new(), project: sanitize(project) }
+  }
+
+  #[test]
+  fn sanitizes_saved_projects() {
+    let text = serde_json::to_string(&template().project).unwrap();
+    assert!(!text.contains("\"id\":7") && !text.contains("\"id\":\"s\""), "{text}");
+    assert!(!text.contains("\"done\":true") && !text.contains("startedAt") && !text.contains("\"a\""), "{text}");
+    let summary = summary(&template());
+    assert_eq!(summary.placeholders, ["client", "contact", "unknown"]);
+    assert_eq!((summary.notes, summary.steps), (1, 2));
+  }
+
+  #[test]
+  fn fills_placeholders_and_keeps_malformed_ones() {
+    let values = BTreeMap::from([("CLIENT".to_string(), "Acme".to_string()), ("contact".to_string(), " ".to_string())]);
+    let made = instantiate(&template(), &values, &Zone::from_settings("UTC"));
+    assert_eq!(made.name, "Site for Acme");
+    assert_eq!(made.tags, ["Acme"]);
+    let year = Utc::now().format("%Y").to_string();
+    assert_eq!(made.notes[0].body, format!("Meet Acme in {year}, keep {{{{unknown}}}} and {{{{ }}}} and {{{{x"));
+    assert_eq!(made.steps[0].children[0].text, "Call {{contact}}");
+    assert!(made.id.is_some() && made.notes[0].id.is_some() && made.steps[0].children[0].id.is_some());
+    assert!(!made.pinned && made.status.is_empty());
+    assert_eq!(replace_placeholders("{{{{a}}", &BTreeMap::from([("a".into(), "б".into())])), "{{б");
+  }
+
+  #[test]
+  fn library_round_trips_and_merges_by_name() {
+    let mut library = TemplateLibrary::default();
+    assert!(!upsert(&mut library, template()));
+    let exported = serde_json::to_string(&library).unwrap();
+
+    let mut other = TemplateLibrary::default();
+    upsert(&mut other, ProjectTemplate { id: "zz".into(), name: "site".into(), ..ProjectTemplate::default() });
+    let result = merge(&mut other, parse_library(&exported).unwrap());
+    assert_eq!((result.added, result.replaced), (0, 1));
+    assert_eq!((other.templates[0].id.as_str(), other.templates[0].name.as_str()), ("zz", "Site"));
+  }
+
+  #[test]
+  fn rejects_foreign_and_newer_libraries() {
+    assert!(parse_library(r#"{"format":"other","version":1}"#).is_err());
+    assert!(parse_library(r#"{"format":"project-notes-studio-templates","version":99}"#).is_err());
+    assert!(parse_library("not json").is_err());
+    let library = parse_library(r#"{"format":"project-notes-studio-templates","version":1}"#).unwrap();
+    assert!(library.templates.is_empty());
+  }
+}
@@ -0,0 +1,29 @@
+use std::fs;
+use std::path::PathBuf;
+
+pub(crate) struct TempDir(pub(crate) PathBuf);
+
+impl TempDir {
+  pub(crate) fn new(prefix: &str, name: &str) -> Self {
+    let path = std::env::temp_dir().join(format!("pns-{prefix}-{name}-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&path);
+    fs::create_dir_all(&path).unwrap();
+    TempDir(path)
+  }
+
+  pub(crate) fn write(&self, relative: &str, content: &str) {
+    let path = self.0.join(relative);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, content).unwrap();
+  }
+
+  pub(crate) fn read(&self, relative: &str) -> Option<String> {
+    fs::read_to_string(self.0.join(relative)).ok()
+  }
+}
+
+impl Drop for TempDir {
+  fn drop(&mut self) {
+    let _ = fs::remove_dir_all(&self.0);
+  }
+}
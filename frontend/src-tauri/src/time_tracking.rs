@@ -0,0 +1,550 @@
+use crate::steps_csv::UTF8_BOM;
+use crate::time_zone::{Zone, resolve_time};
+use crate::{AppState, EntityId, ProjectState, StepState, TimeEntryState, data_dir, file_stem, step_tree, write_export_file};
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::sync::Mutex;
+use tauri::Manager;
+
+pub(crate) const TIMER_FILE: &str = "timer.json";
+const MAX_MANUAL_MINUTES: u32 = 24 * 60;
+const ENTRY_HEADER: [&str; 8] = ["date", "start", "end", "hours", "project", "step", "note", "manual"];
+const TOTAL_HEADER: [&str; 3] = ["period", "project", "hours"];
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RunningTimer {
+  project_id: EntityId,
+  step_id: EntityId,
+  started_at: String,
+}
+
+#[derive(Default)]
+pub(crate) struct TimeTracker(Mutex<Option<RunningTimer>>);
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TimerResult {
+  state: AppState,
+  timer: Option<RunningTimer>,
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum Period {
+  #[default]
+  Day,
+  Week,
+  Month,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct StepTotal {
+  step_id: Option<EntityId>,
+  text: String,
+  seconds: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ProjectTotal {
+  project_id: Option<EntityId>,
+  project: String,
+  seconds: i64,
+  steps: Vec<StepTotal>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PeriodTotal {
+  period: String,
+  seconds: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TimeReport {
+  total_seconds: i64,
+  projects: Vec<ProjectTotal>,
+  periods: Vec<PeriodTotal>,
+}
+
+struct Row<'a> {
+  project_index: usize,
+  project: &'a ProjectState,
+  step: &'a StepState,
+  entry: &'a TimeEntryState,
+  start: DateTime<Utc>,
+  seconds: i64,
+}
+
+impl TimeTracker {
+  pub(crate) fn load(app: &tauri::AppHandle) -> Self {
+    let timer = data_dir(app).ok().and_then(|root| match read_timer(&root) {
+      Ok(timer) => timer,
+      Err(err) => {
+        log::warn!("failed to restore the running timer: {err}");
+        None
+      }
+    });
+    TimeTracker(Mutex::new(timer))
+  }
+
+  pub(crate) fn reload(app: &tauri::AppHandle) -> Result<(), String> {
+    let timer = read_timer(&data_dir(app)?)?;
+    let tracker = app.state::<TimeTracker>();
+    *tracker.0.lock().map_err(|err| err.to_string())? = timer;
+    Ok(())
+  }
+}
+
+fn read_timer(root: &Path) -> Result<Option<RunningTimer>, String> {
+  let path = root.join(TIMER_FILE);
+  if !path.exists() {
+    return Ok(None);
+  }
+  let content = fs::read_to_string(path).map_err(|err| err.to_string())?;
+  serde_json::from_str(&content).map(Some).map_err(|err| err.to_string())
+}
+
+fn write_timer(root: &Path, timer: Option<&RunningTimer>) -> Result<(), String> {
+  let path = root.join(TIMER_FILE);
+  match timer {
+    Some(timer) => {
+      let content = serde_json::to_string_pretty(timer).map_err(|err| err.to_string())?;
+      fs::write(path, content).map_err(|err| err.to_string())
+    }
+    None if path.exists() => fs::remove_file(path).map_err(|err| err.to_string()),
+    None => Ok(()),
+  }
+}
+
+fn step_mut<'a>(state: &'a mut AppState, project_id: &EntityId, step_id: &EntityId) -> Option<&'a mut StepState> {
+  let project = state.projects.iter_mut().find(|project| project.id.as_ref() == Some(project_id))?;
+  let path = step_tree::find_path(&project.steps, step_id)?;
+  Some(step_tree::step_at_mut(&mut project.steps, &path))
+}
+
+fn parse_time(value: &str) -> Option<DateTime<Utc>> {
+  DateTime::parse_from_rfc3339(value).ok().map(|value| value.with_timezone(&Utc))
+}
+
+fn close(state: &mut AppState, timer: &RunningTimer, now: DateTime<Utc>) {
+  if parse_time(&timer.started_at).is_none_or(|started| now <= started) {
+    return;
+  }
+  let Some(step) = step_mut(state, &timer.project_id, &timer.step_id) else {
+    log::warn!("timer for step {} was dropped because the step no longer exists", timer.step_id);
+    return;
+  };
+  step.time_entries.push(TimeEntryState {
+    id: Some(EntityId::generate()),
+    started_at: timer.started_at.clone(),
+    ended_at: now.to_rfc3339(),
+    note: String::new(),
+    manual: false,
+  });
+}
+
+fn start(
+  mut state: AppState,
+  running: Option<RunningTimer>,
+  project_id: EntityId,
+  step_id: EntityId,
+  now: DateTime<Utc>,
+) -> Result<TimerResult, String> {
+  if step_mut(&mut state, &project_id, &step_id).is_none() {
+    return Err("step not found".to_string());
+  }
+  if let Some(timer) = running {
+    if timer.project_id == project_id && timer.step_id == step_id {
+      return Ok(TimerResult { state, timer: Some(timer) });
+    }
+    close(&mut state, &timer, now);
+  }
+  let timer = RunningTimer { project_id, step_id, started_at: now.to_rfc3339() };
+  Ok(TimerResult { state, timer: Some(timer) })
+}
+
+fn add_entry(
+  mut state: AppState,
+  project_id: &EntityId,
+  step_id: &EntityId,
+  started_at: &str,
+  minutes: u32,
+  note: &str,
+) -> Result<AppState, String> {
+  if minutes == 0 || minutes > MAX_MANUAL_MINUTES {
+    return Err(format!("duration must be between 1 and {MAX_MANUAL_MINUTES} minutes"));
+  }
+  let zone = Zone::from_settings(&state.settings.time_zone);
+  let start = resolve_time(started_at, &zone).ok_or_else(|| "invalid start time".to_string())?;
+  let end = start + chrono::Duration::minutes(i64::from(minutes));
+  let step = step_mut(&mut state, project_id, step_id).ok_or_else(|| "step not found".to_string())?;
+  step.time_entries.push(TimeEntryState {
+    id: Some(EntityId::generate()),
+    started_at: start.to_rfc3339(),
+    ended_at: end.to_rfc3339(),
+    note: note.trim().to_string(),
+    manual: true,
+  });
+  step.time_entries.sort_by_key(|entry| parse_time(&entry.started_at));
+  Ok(state)
+}
+
+fn remove_entry(mut state: AppState, project_id: &EntityId, step_id: &EntityId, entry_id: &EntityId) -> Result<AppState, String> {
+  let step = step_mut(&mut state, project_id, step_id).ok_or_else(|| "step not found".to_string())?;
+  let before = step.time_entries.len();
+  step.time_entries.retain(|entry| entry.id.as_ref() != Some(entry_id));
+  if step.time_entries.len() == before {
+    return Err("time entry not found".to_string());
+  }
+  Ok(state)
+}
+
+fn parse_day(value: Option<&str>) -> Result<Option<NaiveDate>, String> {
+  match value.map(str::trim).filter(|value| !value.is_empty()) {
+    Some(value) => NaiveDate::parse_from_str(value, "%Y-%m-%d").map(Some).map_err(|_| format!("invalid date: {value}")),
+    None => Ok(None),
+  }
+}
+
+fn period_label(date: NaiveDate, period: Period) -> String {
+  match period {
+    Period::Day => date.format("%Y-%m-%d").to_string(),
+    Period::Week => {
+      let week = date.iso_week();
+      format!("{}-W{:02}", week.year(), week.week())
+    }
+    Period::Month => date.format("%Y-%m").to_string(),
+  }
+}
+
+fn rows<'a>(
+  state: &'a AppState,
+  zone: &Zone,
+  from: Option<&str>,
+  to: Option<&str>,
+  project_id: Option<&EntityId>,
+) -> Result<Vec<Row<'a>>, String> {
+  let from = parse_day(from)?;
+  let to = parse_day(to)?;
+  let mut rows = Vec::new();
+  for (project_index, project) in state.select_projects(project_id)?.into_iter().enumerate() {
+    for (_, step) in step_tree::flatten(&project.steps) {
+      for entry in &step.time_entries {
+        let (Some(start), Some(end)) = (parse_time(&entry.started_at), parse_time(&entry.ended_at)) else {
+          continue;
+        };
+        let day = zone.local(&start).date();
+        if from.is_some_and(|from| day < from) || to.is_some_and(|to| day > to) {
+          continue;
+        }
+        let seconds = (end - start).num_seconds().max(0);
+        rows.push(Row { project_index, project, step, entry, start, seconds });
+      }
+    }
+  }
+  rows.sort_by_key(|row| row.start);
+  Ok(rows)
+}
+
+fn summarize(rows: &[Row], zone: &Zone, period: Period) -> TimeReport {
+  let mut by_project: BTreeMap<usize, ProjectTotal> = BTreeMap::new();
+  let mut by_period: BTreeMap<String, i64> = BTreeMap::new();
+  for row in rows {
+    let total = by_project.entry(row.project_index).or_insert_with(|| ProjectTotal {
+      project_id: row.project.id.clone(),
+      project: row.project.name.clone(),
+      seconds: 0,
+      steps: Vec::new(),
+    });
+    total.seconds += row.seconds;
+    match total.steps.iter_mut().find(|step| step.step_id == row.step.id && step.text == row.step.text) {
+      Some(step) => step.seconds += row.seconds,
+      None => total.steps.push(StepTotal { step_id: row.step.id.clone(), text: row.step.text.clone(), seconds: row.seconds }),
+    }
+    *by_period.entry(period_label(zone.local(&row.start).date(), period)).or_default() += row.seconds;
+  }
+  TimeReport {
+    total_seconds: rows.iter().map(|row| row.seconds).sum(),
+    projects: by_project.into_values().collect(),
+    periods: by_period.into_iter().map(|(period, seconds)| PeriodTotal { period, seconds }).collect(),
+  }
+}
+
+fn hours(seconds: i64) -> String {
+  format!("{:.2}", seconds as f64 / 3600.0)
+}
+
+fn render_timesheet(rows: &[Row], zone: &Zone, period: Period) -> Result<String, String> {
+  let mut writer = csv::Writer::from_writer(Vec::new());
+  writer.write_record(ENTRY_HEADER).map_err(|err| err.to_string())?;
+  let mut totals: BTreeMap<(String, &str), i64> = BTreeMap::new();
+  for row in rows {
+    let start = zone.local(&row.start);
+    let end = parse_time(&row.entry.ended_at)
+      .map(|end| zone.local(&end))
+      .map(|end| if end.date() == start.date() { end.format("%H:%M") } else { end.format("%Y-%m-%d %H:%M") }.to_string())
+      .unwrap_or_default();
+    writer
+      .write_record([
+        start.format("%Y-%m-%d").to_string(),
+        start.format("%H:%M").to_string(),
+        end,
+        hours(row.seconds),
+        row.project.name.clone(),
+        row.step.text.clone(),
+        row.entry.note.clone(),
+        if row.entry.manual { "yes" } else { "no" }.to_string(),
+      ])
+      .map_err(|err| err.to_string())?;
+    *totals.entry((period_label(start.date(), period), row.project.name.as_str())).or_default() += row.seconds;
+  }
+  let mut bytes = writer.into_inner().map_err(|err| err.to_string())?;
+  bytes.push(b'\n');
+  let mut writer = csv::Writer::from_writer(bytes);
+  writer.write_record(TOTAL_HEADER).map_err(|err| err.to_string())?;
+  for ((label, project), seconds) in &totals {
+    writer.write_record([label.as_str(), project, &hours(*seconds)]).map_err(|err| err.to_string())?;
+  }
+  let total: i64 = rows.iter().map(|row| row.seconds).sum();
+  writer.write_record(["total", "", &hours(total)]).map_err(|err| err.to_string())?;
+  let bytes = writer.into_inner().map_err(|err| err.to_string())?;
+  let body = String::from_utf8(bytes).map_err(|err| err.to_string())?;
+  Ok(format!("{UTF8_BOM}{body}"))
+}
+
+#[tauri::command]
+pub(crate) fn timer_status(app: tauri::AppHandle) -> Result<Option<RunningTimer>, String> {
+  let tracker = app.state::<TimeTracker>();
+  let running = tracker.0.lock().map_err(|err| err.to_string())?;
+  Ok(running.clone())
+}
+
+#[tauri::command]
+pub(crate) fn start_timer(
+  app: tauri::AppHandle,
+  state: AppState,
+  project_id: EntityId,
+  step_id: EntityId,
+) -> Result<TimerResult, String> {
+  let tracker = app.state::<TimeTracker>();
+  let mut running = tracker.0.lock().map_err(|err| err.to_string())?;
+  let result = start(state, running.clone(), project_id, step_id, Utc::now())?;
+  write_timer(&data_dir(&app)?, result.timer.as_ref())?;
+  *running = result.timer.clone();
+  Ok(result)
+}
+
+#[tauri::command]
+pub(crate) fn stop_timer(app: tauri::AppHandle, mut state: AppState) -> Result<TimerResult, String> {
+  let tracker = app.state::<TimeTracker>();
+  let mut running = tracker.0.lock().map_err(|err| err.to_string())?;
+  if let Some(timer) = running.as_ref() {
+    close(&mut state, timer, Utc::now());
+  }
+  write_timer(&data_dir(&app)?, None)?;
+  *running = None;
+  Ok(TimerResult { state, timer: None })
+}
+
+#[tauri::command]
+pub(crate) fn add_time_entry(
+  state: AppState,
+  project_id: EntityId,
+  step_id: EntityId,
+  started_at: String,
+  minutes: u32,
+  note: String,
+) -> Result<AppState, String> {
+  add_entry(state, &project_id, &step_id, &started_at, minutes, &note)
+}
+
+#[tauri::command]
+pub(crate) fn remove_time_entry(
+  state: AppState,
+  project_id: EntityId,
+  step_id: EntityId,
+  entry_id: EntityId,
+) -> Result<AppState, String> {
+  remove_entry(state, &project_id, &step_id, &entry_id)
+}
+
+#[tauri::command]
+pub(crate) fn time_report(
+  state: AppState,
+  from: Option<String>,
+  to: Option<String>,
+  project_id: Option<EntityId>,
+  period: Option<Period>,
+) -> Result<TimeReport, String> {
+  let zone = Zone::from_settings(&state.settings.time_zone);
+  let rows = rows(&state, &zone, from.as_deref(), to.as_deref(), project_id.as_ref())?;
+  Ok(summarize(&rows, &zone, period.unwrap_or_default()))
+}
+
+#[tauri::command]
+pub(crate) fn export_timesheet(
+  app: tauri::AppHandle,
+  state: AppState,
+  from: Option<String>,
+  to: Option<String>,
+  project_id: Option<EntityId>,
+  period: Option<Period>,
+) -> Result<String, String> {
+  let zone = Zone::from_settings(&state.settings.time_zone);
+  let rows = rows(&state, &zone, from.as_deref(), to.as_deref(), project_id.as_ref())?;
+  let content = render_timesheet(&rows, &zone, period.unwrap_or_default())?;
+  let stem = match project_id.as_ref().and_then(|id| state.select_projects(Some(id)).ok()) {
+    Some(projects) => file_stem(&projects[0].name, "project"),
+    None => "workspace".to_string(),
+  };
+  write_export_file(&app, &format!("{stem}.timesheet"), ".csv", content.as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test_support::TempDir;
+  use chrono::Duration;
+
+  fn sample() -> AppState {
+    serde_json::from_str(
+      r#"{"projects":[
+        {"id":1,"name":"Acme","steps":[{"id":"a","text":"Design","children":[{"id":"b","text":"Mockups"}]}]},
+        {"id":2,"name":"Beta","steps":[{"id":"c","text":"Build"}]}
+      ],"settings":{"timeZone":"UTC"}}"#,
+    )
+    .unwrap()
+  }
+
+  fn at(value: &str) -> DateTime<Utc> {
+    parse_time(value).unwrap()
+  }
+
+  fn entry(started_at: &str, ended_at: &str) -> TimeEntryState {
+    TimeEntryState { id: Some(EntityId::generate()), started_at: started_at.into(), ended_at: ended_at.into(), note: String::new(), manual: false }
+  }
+
+  fn tracked() -> AppState {
+    let mut state = sample();
+    state.projects[0].steps[0].children[0].time_entries = vec![
+      entry("2026-10-05T09:00:00+00:00", "2026-10-05T10:30:00+00:00"),
+      entry("2026-10-11T23:30:00+00:00", "2026-10-12T00:30:00+00:00"),
+      entry("broken", "2026-10-05T10:30:00+00:00"),
+    ];
+    state.projects[1].steps[0].time_entries = vec![entry("2026-12-31T12:00:00+00:00", "2026-12-31T12:45:00+00:00")];
+    state
+  }
+
+  #[test]
+  fn starting_another_step_closes_the_running_timer() {
+    let t0 = at("2026-10-05T09:00:00Z");
+    let first = start(sample(), None, EntityId::Num(1), EntityId::parse("b"), t0).unwrap();
+    let timer = first.timer.clone().unwrap();
+    let again = start(first.state, Some(timer.clone()), EntityId::Num(1), EntityId::parse("b"), t0 + Duration::minutes(5)).unwrap();
+    assert_eq!(again.timer.as_ref(), Some(&timer));
+    assert!(again.state.projects[0].steps[0].children[0].time_entries.is_empty());
+
+    let switched = start(again.state, Some(timer), EntityId::Num(2), EntityId::parse("c"), t0 + Duration::minutes(90)).unwrap();
+    let entries = &switched.state.projects[0].steps[0].children[0].time_entries;
+    assert_eq!(entries.len(), 1);
+    assert_eq!((entries[0].started_at.as_str(), entries[0].manual), ("2026-10-05T09:00:00+00:00", false));
+    assert_eq!(entries[0].ended_at, "2026-10-05T10:30:00+00:00");
+    assert_eq!(switched.timer.unwrap().step_id, EntityId::parse("c"));
+    assert!(start(sample(), None, EntityId::Num(2), EntityId::parse("zz"), t0).is_err_and(|err| err == "step not found"));
+  }
+
+  #[test]
+  fn closing_ignores_clock_skew_and_deleted_steps() {
+    let mut state = sample();
+    let timer = RunningTimer { project_id: EntityId::Num(1), step_id: EntityId::parse("a"), started_at: "2026-10-05T09:00:00Z".into() };
+    close(&mut state, &timer, at("2026-10-05T08:00:00Z"));
+    close(&mut state, &RunningTimer { step_id: EntityId::parse("gone"), ..timer.clone() }, at("2026-10-05T10:00:00Z"));
+    close(&mut state, &RunningTimer { started_at: "not a time".into(), ..timer }, at("2026-10-05T10:00:00Z"));
+    assert!(step_tree::flatten(&state.projects[0].steps).iter().all(|(_, step)| step.time_entries.is_empty()));
+  }
+
+  #[test]
+  fn persists_the_running_timer() {
+    let dir = TempDir::new("time", "timer");
+    let timer = RunningTimer { project_id: EntityId::Num(1), step_id: EntityId::parse("a"), started_at: "2026-10-05T09:00:00Z".into() };
+    assert_eq!(read_timer(&dir.0).unwrap(), None);
+    write_timer(&dir.0, Some(&timer)).unwrap();
+    assert_eq!(read_timer(&dir.0).unwrap(), Some(timer));
+    write_timer(&dir.0, None).unwrap();
+    write_timer(&dir.0, None).unwrap();
+    assert!(!dir.0.join(TIMER_FILE).exists());
+    fs::write(dir.0.join(TIMER_FILE), "{").unwrap();
+    assert!(read_timer(&dir.0).is_err());
+  }
+
+  #[test]
+  fn adds_and_removes_manual_entries() {
+    let state = add_entry(sample(), &EntityId::Num(1), &EntityId::parse("a"), "2026-10-20T10:00", 30, " call ").unwrap();
+    let added = &state.projects[0].steps[0].time_entries[0];
+    assert_eq!((added.started_at.as_str(), added.ended_at.as_str()), ("2026-10-20T10:00:00+00:00", "2026-10-20T10:30:00+00:00"));
+    assert_eq!((added.note.as_str(), added.manual), ("call", true));
+
+    for (started_at, minutes) in [("x", 30), ("2026-10-20T10:00", 0), ("2026-10-20T10:00", MAX_MANUAL_MINUTES + 1)] {
+      assert!(add_entry(sample(), &EntityId::Num(1), &EntityId::parse("a"), started_at, minutes, "").is_err());
+    }
+
+    let id = added.id.clone().unwrap();
+    let removed = remove_entry(state, &EntityId::Num(1), &EntityId::parse("a"), &id).unwrap();
+    assert!(removed.projects[0].steps[0].time_entries.is_empty());
+    assert!(remove_entry(removed, &EntityId::Num(1), &EntityId::parse("a"), &id).is_err_and(|err| err == "time entry not found"));
+  }
+
+  #[test]
+  fn summarizes_by_project_step_and_period() {
+    let state = tracked();
+    let zone = Zone::from_settings("UTC");
+    let all = rows(&state, &zone, None, None, None).unwrap();
+    let report = serde_json::to_value(summarize(&all, &zone, Period::Week)).unwrap();
+    assert_eq!(report["totalSeconds"], 195 * 60);
+    assert_eq!(report["projects"][0]["seconds"], 150 * 60);
+    assert_eq!(report["projects"][0]["steps"], serde_json::json!([{ "stepId": "b", "text": "Mockups", "seconds": 150 * 60 }]));
+    assert_eq!(
+      report["periods"],
+      serde_json::json!([
+        { "period": "2026-W41", "seconds": 150 * 60 },
+        { "period": "2026-W53", "seconds": 45 * 60 },
+      ])
+    );
+
+    let berlin = Zone::from_settings("Europe/Berlin");
+    let days = summarize(&rows(&state, &berlin, None, None, Some(&EntityId::Num(1))).unwrap(), &berlin, Period::Day);
+    let days: Vec<String> = days.periods.into_iter().map(|period| period.period).collect();
+    assert_eq!(days, ["2026-10-05", "2026-10-12"]);
+  }
+
+  #[test]
+  fn filters_by_local_day_and_rejects_bad_dates() {
+    let state = tracked();
+    let zone = Zone::from_settings("UTC");
+    assert_eq!(rows(&state, &zone, Some("2026-10-05"), Some("2026-10-11"), None).unwrap().len(), 2);
+    assert_eq!(rows(&state, &zone, Some(" "), Some("2026-10-05"), None).unwrap().len(), 1);
+    assert!(rows(&state, &zone, Some("05.10.2026"), None, None).is_err_and(|err| err.contains("invalid date")));
+    assert!(rows(&state, &zone, None, None, Some(&EntityId::Num(9))).is_err());
+  }
+
+  #[test]
+  fn writes_a_timesheet_with_period_totals() {
+    let state = tracked();
+    let zone = Zone::from_settings("UTC");
+    let sheet = render_timesheet(&rows(&state, &zone, None, None, None).unwrap(), &zone, Period::Month).unwrap();
+    let sheet = sheet.strip_prefix(UTF8_BOM).unwrap();
+    let (entries, totals) = sheet.split_once("\n\n").unwrap();
+    let entries: Vec<&str> = entries.lines().collect();
+    assert_eq!(entries[0], ENTRY_HEADER.join(","));
+    assert_eq!(entries[1], "2026-10-05,09:00,10:30,1.50,Acme,Mockups,,no");
+    assert_eq!(entries[2], "2026-10-11,23:30,2026-10-12 00:30,1.00,Acme,Mockups,,no");
+    assert_eq!(totals.lines().collect::<Vec<_>>(), ["period,project,hours", "2026-10,Acme,2.50", "2026-12,Beta,0.75", "total,,3.25"]);
+  }
+}
@@ -0,0 +1,78 @@
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
+
+const DEFAULT_HOUR: u32 = 9;
+
+pub(crate) enum Zone {
+  Local,
+  Named(Tz),
+}
+
+impl Zone {
+  pub(crate) fn from_settings(value: &str) -> Self {
+    match value.trim().parse::<Tz>() {
+      Ok(tz) => Zone::Named(tz),
+      Err(_) => Zone::Local,
+    }
+  }
+
+  fn to_utc(&self, naive: &NaiveDateTime) -> Option<DateTime<Utc>> {
+    let resolve = |naive: &NaiveDateTime| match self {
+      Zone::Local => Local.from_local_datetime(naive).earliest().map(|value| value.with_timezone(&Utc)),
+      Zone::Named(tz) => tz.from_local_datetime(naive).earliest().map(|value| value.with_timezone(&Utc)),
+    };
+    resolve(naive).or_else(|| resolve(&(*naive + chrono::Duration::hours(1))))
+  }
+
+  pub(crate) fn local(&self, value: &DateTime<Utc>) -> NaiveDateTime {
+    match self {
+      Zone::Local => value.with_timezone(&Local).naive_local(),
+      Zone::Named(tz) => value.with_timezone(tz).naive_local(),
+    }
+  }
+
+  pub(crate) fn format(&self, value: &DateTime<Utc>) -> String {
+    self.local(value).format("%Y-%m-%d %H:%M").to_string()
+  }
+}
+
+pub(crate) fn resolve_time(value: &str, zone: &Zone) -> Option<DateTime<Utc>> {
+  let value = value.trim();
+  if value.is_empty() {
+    return None;
+  }
+  if let Ok(parsed) = DateTime::parse_from_rfc3339(value) {
+    return Some(parsed.with_timezone(&Utc));
+  }
+  let naive = NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M")
+    .or_else(|_| NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S"))
+    .or_else(|_| NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M"))
+    .ok()
+    .or_else(|| {
+      NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .ok()
+        .and_then(|date| date.and_hms_opt(DEFAULT_HOUR, 0, 0))
+    })?;
+  zone.to_utc(&naive)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn at(value: &str) -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339(value).unwrap().with_timezone(&Utc)
+  }
+
+  #[test]
+  fn resolves_dates_times_and_invalid_values() {
+    let zone = Zone::from_settings("Europe/Kyiv");
+    assert_eq!(resolve_time("2026-01-05", &zone), Some(at("2026-01-05T07:00:00Z")));
+    assert_eq!(resolve_time("2026-01-05 10:30", &zone), Some(at("2026-01-05T08:30:00Z")));
+    assert_eq!(resolve_time("2026-01-05T10:30:00Z", &zone), Some(at("2026-01-05T10:30:00Z")));
+    assert_eq!(resolve_time("2026-03-29T03:30", &zone), Some(at("2026-03-29T01:30:00Z")));
+    assert_eq!(resolve_time("tomorrow", &zone), None);
+    assert_eq!(resolve_time("", &zone), None);
+    assert!(matches!(Zone::from_settings("Not/AZone"), Zone::Local));
+  }
+}
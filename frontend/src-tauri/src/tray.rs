@@ -0,0 +1,76 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::labels::{app_labels, doc_labels};
+use crate::read_state_file;
+use tauri::menu::{Menu, MenuItem};
+use tauri::tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent};
+use tauri::{Manager, WindowEvent};
+
+const MAIN_WINDOW: &str = "main";
+const TRAY_ID: &str = "main";
+const OPEN_ITEM: &str = "open";
+const QUIT_ITEM: &str = "quit";
+
+#[derive(Default)]
+pub(crate) struct CloseToTray(AtomicBool);
+
+pub(crate) fn set_close_to_tray(app: &tauri::AppHandle, enabled: bool) {
+  if let Some(close_to_tray) = app.try_state::<CloseToTray>() {
+    close_to_tray.0.store(enabled, Ordering::Relaxed);
+  }
+}
+
+fn show_main_window(app: &tauri::AppHandle) {
+  let Some(window) = app.get_webview_window(MAIN_WINDOW) else {
+    return;
+  };
+  let shown = window.show().and_then(|_| window.unminimize()).and_then(|_| window.set_focus());
+  if let Err(err) = shown {
+    log::warn!("failed to show the main window: {err}");
+  }
+}
+
+pub(crate) fn setup(app: &tauri::App) -> tauri::Result<()> {
+  let settings = read_state_file(app.handle())
+    .ok()
+    .flatten()
+    .map(|state| state.settings)
+    .unwrap_or_default();
+  app.manage(CloseToTray(AtomicBool::new(settings.close_to_tray)));
+  let labels = app_labels(&settings.language);
+  let open = MenuItem::with_id(app, OPEN_ITEM, labels.tray_open, true, None::<&str>)?;
+  let quit = MenuItem::with_id(app, QUIT_ITEM, labels.tray_quit, true, None::<&str>)?;
+  let menu = Menu::with_items(app, &[&open, &quit])?;
+
+  let mut builder = TrayIconBuilder::with_id(TRAY_ID)
+    .tooltip(doc_labels(&settings.language).app_title)
+    .menu(&menu)
+    .show_menu_on_left_click(false)
+    .on_menu_event(|app, event| match event.id().as_ref() {
+      OPEN_ITEM => show_main_window(app),
+      QUIT_ITEM => app.exit(0),
+      _ => {}
+    })
+    .on_tray_icon_event(|tray, event| {
+      if let TrayIconEvent::Click { button: MouseButton::Left, button_state: MouseButtonState::Up, .. } = event {
+        show_main_window(tray.app_handle());
+      }
+    });
+  if let Some(icon) = app.default_window_icon() {
+    builder = builder.icon(icon.clone());
+  }
+  builder.build(app)?;
+  Ok(())
+}
+
+pub(crate) fn hide_on_close(window: &tauri::Window, event: &WindowEvent) {
+  if let WindowEvent::CloseRequested { api, .. } = event
+    && window.label() == MAIN_WINDOW
+    && window.try_state::<CloseToTray>().is_some_and(|close_to_tray| close_to_tray.0.load(Ordering::Relaxed))
+  {
+    api.prevent_close();
+    if let Err(err) = window.hide() {
+      log::warn!("failed to hide the main window: {err}");
+    }
+  }
+}
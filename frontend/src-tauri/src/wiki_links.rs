@@ -0,0 +1,562 @@
+use crate::{AppState, EntityId, NoteState, ProjectState};
+use pulldown_cmark::{Event, Parser, Tag};
+use serde::Serialize;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use std::ops::Range;
+use std::sync::Mutex;
+use tauri::Manager;
+
+type NoteKey = (EntityId, EntityId);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct WikiLink {
+  pub(crate) target: String,
+  pub(crate) label: Option<String>,
+  pub(crate) span: Range<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Resolution {
+  Note(NoteKey),
+  Missing,
+  Ambiguous,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct LinkSource {
+  project_id: EntityId,
+  project: String,
+  note_id: EntityId,
+  note: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct NoteBacklinks {
+  project_id: EntityId,
+  note_id: EntityId,
+  sources: Vec<LinkSource>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum BrokenReason {
+  Missing,
+  Ambiguous,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BrokenLink {
+  source: LinkSource,
+  target: String,
+  reason: BrokenReason,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct LinkReport {
+  backlinks: Vec<NoteBacklinks>,
+  broken: Vec<BrokenLink>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct LinkTarget {
+  project_id: EntityId,
+  note_id: EntityId,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RenameResult {
+  state: AppState,
+  rewritten_links: usize,
+}
+
+struct IndexedNote {
+  project: String,
+  title: String,
+  fingerprint: u64,
+  links: Vec<WikiLink>,
+}
+
+#[derive(Default)]
+struct IndexInner {
+  notes: HashMap<NoteKey, IndexedNote>,
+  by_target: HashMap<String, HashSet<NoteKey>>,
+}
+
+#[derive(Default)]
+pub(crate) struct LinkIndex(Mutex<IndexInner>);
+
+pub(crate) fn parse_links(body: &str) -> Vec<WikiLink> {
+  let mut links = Vec::new();
+  let mut offset = 0;
+  while let Some(start) = body[offset..].find("[[") {
+    let open = offset + start;
+    let inner_start = open + 2;
+    let Some(end) = body[inner_start..].find("]]") else {
+      break;
+    };
+    let inner = &body[inner_start..inner_start + end];
+    offset = inner_start + end + 2;
+    if inner.contains('\n') {
+      offset = inner_start;
+      continue;
+    }
+    let (target, label) = match inner.split_once('|') {
+      Some((target, label)) => (target.trim(), Some(label.trim().to_string()).filter(|label| !label.is_empty())),
+      None => (inner.trim(), None),
+    };
+    if !target.is_empty() {
+      links.push(WikiLink { target: target.to_string(), label, span: open..offset });
+    }
+  }
+  links
+}
+
+pub(crate) fn body_links(body: &str) -> Vec<WikiLink> {
+  let code: Vec<Range<usize>> = Parser::new(body)
+    .into_offset_iter()
+    .filter_map(|(event, range)| matches!(event, Event::Code(_) | Event::Start(Tag::CodeBlock(_))).then_some(range))
+    .collect();
+  parse_links(body)
+    .into_iter()
+    .filter(|link| !code.iter().any(|range| range.start < link.span.end && link.span.start < range.end))
+    .collect()
+}
+
+fn key(value: &str) -> String {
+  value.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase()
+}
+
+fn note_key(project: &ProjectState, note: &NoteState) -> Option<NoteKey> {
+  Some((project.id.clone()?, note.id.clone()?))
+}
+
+fn fingerprint(body: &str) -> u64 {
+  let mut hasher = DefaultHasher::new();
+  body.hash(&mut hasher);
+  hasher.finish()
+}
+
+fn target_keys(link: &WikiLink) -> Vec<String> {
+  let mut keys = vec![key(&link.target)];
+  for (index, _) in link.target.match_indices('/') {
+    let title = key(&link.target[index + 1..]);
+    if !title.is_empty() && !keys.contains(&title) {
+      keys.push(title);
+    }
+  }
+  keys
+}
+
+struct Titles<'a> {
+  by_title: HashMap<String, Vec<&'a NoteKey>>,
+  projects: HashMap<&'a EntityId, String>,
+}
+
+impl<'a> Titles<'a> {
+  fn new(notes: impl Iterator<Item = (&'a NoteKey, &'a str, &'a str)>) -> Self {
+    let mut titles = Titles { by_title: HashMap::new(), projects: HashMap::new() };
+    for (note, project, title) in notes {
+      titles.by_title.entry(key(title)).or_default().push(note);
+      titles.projects.insert(&note.0, key(project));
+    }
+    titles
+  }
+
+  fn resolve(&self, source_project: &EntityId, target: &str) -> Resolution {
+    let candidates = self.by_title.get(&key(target)).map(Vec::as_slice).unwrap_or_default();
+    if let Some(local) = candidates.iter().find(|note| note.0 == *source_project) {
+      return Resolution::Note((*local).clone());
+    }
+    for (index, _) in target.match_indices('/') {
+      let project = key(&target[..index]);
+      let matches: Vec<&&NoteKey> = self
+        .by_title
+        .get(&key(&target[index + 1..]))
+        .map(|notes| notes.iter().filter(|note| self.projects.get(&note.0) == Some(&project)).collect())
+        .unwrap_or_default();
+      if let [single] = matches.as_slice() {
+        return Resolution::Note((**single).clone());
+      }
+    }
+    match candidates {
+      [] => Resolution::Missing,
+      [single] => Resolution::Note((*single).clone()),
+      _ => Resolution::Ambiguous,
+    }
+  }
+}
+
+fn state_notes(state: &AppState) -> Vec<(NoteKey, &str, &str)> {
+  let mut notes = Vec::new();
+  for project in &state.projects {
+    for note in &project.notes {
+      if let Some(note_key) = note_key(project, note) {
+        notes.push((note_key, project.name.as_str(), note.title.as_str()));
+      }
+    }
+  }
+  notes
+}
+
+fn state_titles<'a>(notes: &'a [(NoteKey, &'a str, &'a str)]) -> Titles<'a> {
+  Titles::new(notes.iter().map(|(note, project, title)| (note, *project, *title)))
+}
+
+impl IndexInner {
+  fn forget(&mut self, note: &NoteKey) {
+    if let Some(previous) = self.notes.remove(note) {
+      for link in &previous.links {
+        for target in target_keys(link) {
+          if let Some(sources) = self.by_target.get_mut(&target) {
+            sources.remove(note);
+            if sources.is_empty() {
+              self.by_target.remove(&target);
+            }
+          }
+        }
+      }
+    }
+  }
+
+  fn sync(&mut self, state: &AppState) {
+    let mut seen = HashSet::new();
+    for project in &state.projects {
+      for note in &project.notes {
+        let Some(note_key) = note_key(project, note) else {
+          continue;
+        };
+        seen.insert(note_key.clone());
+        let body_fingerprint = fingerprint(&note.body);
+        if let Some(existing) = self.notes.get_mut(&note_key)
+          && existing.fingerprint == body_fingerprint
+        {
+          existing.project = project.name.clone();
+          existing.title = note.title.clone();
+          continue;
+        }
+        self.forget(&note_key);
+        let links = body_links(&note.body);
+        for link in &links {
+          for target in target_keys(link) {
+            self.by_target.entry(target).or_default().insert(note_key.clone());
+          }
+        }
+        self.notes.insert(
+          note_key,
+          IndexedNote { project: project.name.clone(), title: note.title.clone(), fingerprint: body_fingerprint, links },
+        );
+      }
+    }
+    let removed: Vec<NoteKey> = self.notes.keys().filter(|note| !seen.contains(*note)).cloned().collect();
+    for note in removed {
+      self.forget(&note);
+    }
+  }
+
+  fn titles(&self) -> Titles<'_> {
+    Titles::new(self.notes.iter().map(|(note, indexed)| (note, indexed.project.as_str(), indexed.title.as_str())))
+  }
+
+  fn source(&self, note: &NoteKey) -> LinkSource {
+    let indexed = &self.notes[note];
+    LinkSource {
+      project_id: note.0.clone(),
+      project: indexed.project.trim().to_string(),
+      note_id: note.1.clone(),
+      note: indexed.title.trim().to_string(),
+    }
+  }
+
+  fn backlinks(&self, titles: &Titles<'_>, note: &NoteKey) -> Vec<LinkSource> {
+    let Some(indexed) = self.notes.get(note) else {
+      return Vec::new();
+    };
+    let Some(candidates) = self.by_target.get(&key(&indexed.title)) else {
+      return Vec::new();
+    };
+    let mut sources = candidates
+      .iter()
+      .filter(|source| *source != note)
+      .filter(|source| {
+        self.notes[*source]
+          .links
+          .iter()
+          .any(|link| titles.resolve(&source.0, &link.target) == Resolution::Note(note.clone()))
+      })
+      .map(|source| self.source(source))
+      .collect::<Vec<_>>();
+    sources.sort_by(|a, b| (&a.project, &a.note).cmp(&(&b.project, &b.note)));
+    sources
+  }
+
+  fn report(&self, order: &[NoteKey]) -> LinkReport {
+    let titles = self.titles();
+    let mut backlinks = Vec::new();
+    let mut broken = Vec::new();
+    for note in order {
+      let sources = self.backlinks(&titles, note);
+      if !sources.is_empty() {
+        backlinks.push(NoteBacklinks { project_id: note.0.clone(), note_id: note.1.clone(), sources });
+      }
+      for link in &self.notes[note].links {
+        let reason = match titles.resolve(&note.0, &link.target) {
+          Resolution::Note(_) => continue,
+          Resolution::Missing => BrokenReason::Missing,
+          Resolution::Ambiguous => BrokenReason::Ambiguous,
+        };
+        broken.push(BrokenLink { source: self.source(note), target: link.target.clone(), reason });
+      }
+    }
+    LinkReport { backlinks, broken }
+  }
+}
+
+fn project_prefix<'a>(target: &'a str, old_title: &str) -> &'a str {
+  if key(target) == key(old_title) {
+    return "";
+  }
+  target
+    .match_indices('/')
+    .map(|(index, _)| index)
+    .find(|index| key(&target[index + 1..]) == key(old_title))
+    .map_or("", |index| &target[..=index])
+}
+
+fn rewrite_body(body: &str, links: &[WikiLink], old_title: &str, title: &str) -> String {
+  let mut out = String::with_capacity(body.len());
+  let mut last = 0;
+  for link in links {
+    out.push_str(&body[last..link.span.start]);
+    out.push_str("[[");
+    out.push_str(project_prefix(&link.target, old_title));
+    out.push_str(title);
+    if let Some(label) = &link.label {
+      out.push('|');
+      out.push_str(label);
+    }
+    out.push_str("]]");
+    last = link.span.end;
+  }
+  out.push_str(&body[last..]);
+  out
+}
+
+fn rename(mut state: AppState, project_id: &EntityId, note_id: &EntityId, title: &str, rewrite_links: bool) -> Result<RenameResult, String> {
+  let renamed: NoteKey = (project_id.clone(), note_id.clone());
+  let old_title = state
+    .projects
+    .iter()
+    .flat_map(|project| project.notes.iter().map(move |note| (project, note)))
+    .find(|(project, note)| note_key(project, note).as_ref() == Some(&renamed))
+    .map(|(_, note)| note.title.clone())
+    .ok_or_else(|| "note not found".to_string())?;
+  let title = title.trim();
+  if title.is_empty() {
+    return Err("note title is empty".to_string());
+  }
+
+  let mut rewrites: Vec<(NoteKey, String)> = Vec::new();
+  let mut rewritten_links = 0;
+  if rewrite_links {
+    let notes = state_notes(&state);
+    let titles = state_titles(&notes);
+    for project in &state.projects {
+      for note in &project.notes {
+        let Some(source) = note_key(project, note) else {
+          continue;
+        };
+        let matching: Vec<WikiLink> = body_links(&note.body)
+          .into_iter()
+          .filter(|link| titles.resolve(&source.0, &link.target) == Resolution::Note(renamed.clone()))
+          .collect();
+        if !matching.is_empty() {
+          rewritten_links += matching.len();
+          rewrites.push((source, rewrite_body(&note.body, &matching, &old_title, title)));
+        }
+      }
+    }
+  }
+
+  for project in &mut state.projects {
+    let project_id = project.id.clone();
+    for note in &mut project.notes {
+      let current = project_id.clone().zip(note.id.clone());
+      if current.as_ref() == Some(&renamed) {
+        note.title = title.to_string();
+      }
+      if let Some((_, body)) = rewrites.iter().find(|(source, _)| Some(source) == current.as_ref()) {
+        note.body = body.clone();
+      }
+    }
+  }
+  Ok(RenameResult { state, rewritten_links })
+}
+
+fn with_index<T>(app: &tauri::AppHandle, state: &AppState, f: impl FnOnce(&IndexInner) -> T) -> Result<T, String> {
+  let index = app.state::<LinkIndex>();
+  let mut inner = index.0.lock().map_err(|_| "link index is unavailable".to_string())?;
+  inner.sync(state);
+  Ok(f(&inner))
+}
+
+#[tauri::command]
+pub(crate) fn link_report(app: tauri::AppHandle, state: AppState) -> Result<LinkReport, String> {
+  let order: Vec<NoteKey> = state_notes(&state).into_iter().map(|(note, _, _)| note).collect();
+  with_index(&app, &state, |index| index.report(&order))
+}
+
+#[tauri::command]
+pub(crate) fn resolve_wiki_link(state: AppState, project_id: EntityId, target: String) -> Result<LinkTarget, String> {
+  let notes = state_notes(&state);
+  match state_titles(&notes).resolve(&project_id, &target) {
+    Resolution::Note((project_id, note_id)) => Ok(LinkTarget { project_id, note_id }),
+    Resolution::Missing => Err("note not found".to_string()),
+    Resolution::Ambiguous => Err("several notes match this link".to_string()),
+  }
+}
+
+#[tauri::command]
+pub(crate) fn rename_note(
+  state: AppState,
+  project_id: EntityId,
+  note_id: EntityId,
+  title: String,
+  rewrite_links: bool,
+) -> Result<RenameResult, String> {
+  rename(state, &project_id, &note_id, &title, rewrite_links)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn sample() -> AppState {
+    serde_json::from_value(serde_json::json!({"projects":[
+      {"id":"p1","name":"Alpha","notes":[
+        {"id":"a","title":"Plan","body":"See [[Spec]] and [[Beta/Spec|beta spec]] and [[Missing]] and [[Shared]]\n`[[code]]`"},
+        {"id":"b","title":"Spec","body":"back to [[plan]]"}]},
+      {"id":"p2","name":"Beta","notes":[
+        {"id":"c","title":"Spec","body":"[[Alpha/Plan]] [[Plan]] [[ Spec ]]"},
+        {"id":"d","title":"Shared","body":""}]},
+      {"id":"p3","name":"Gamma","notes":[{"id":"e","title":"Shared","body":"[[Spec]]"}]}
+    ]}))
+    .unwrap()
+  }
+
+  fn id(value: &str) -> EntityId {
+    EntityId::Str(value.to_string())
+  }
+
+  fn order(state: &AppState) -> Vec<NoteKey> {
+    state_notes(state).into_iter().map(|(note, _, _)| note).collect()
+  }
+
+  fn sources(index: &IndexInner, project: &str, note: &str) -> Vec<String> {
+    index
+      .backlinks(&index.titles(), &(id(project), id(note)))
+      .iter()
+      .map(|source| format!("{}/{}", source.project, source.note))
+      .collect()
+  }
+
+  #[test]
+  fn parses_links_and_skips_unterminated_or_multiline_ones() {
+    let body = "ё [[A|b]] [[\n]] [[ Заметка ]] [[|x]] [[C|]] [[open";
+    let links = parse_links(body);
+    let parsed: Vec<_> = links.iter().map(|link| (link.target.as_str(), link.label.as_deref())).collect();
+    assert_eq!(parsed, [("A", Some("b")), ("Заметка", None), ("C", None)]);
+    assert_eq!(&body[links[1].span.clone()], "[[ Заметка ]]");
+    assert!(parse_links("[[").is_empty());
+  }
+
+  #[test]
+  fn ignores_links_inside_code() {
+    let targets: Vec<String> = body_links("[[one]] `[[two]]`\n\n```\n[[three]]\n```\n\n    [[four]]\n\n[[five]]")
+      .into_iter()
+      .map(|link| link.target)
+      .collect();
+    assert_eq!(targets, ["one", "five"]);
+  }
+
+  #[test]
+  fn reports_backlinks_and_broken_links() {
+    let state = sample();
+    let mut index = IndexInner::default();
+    index.sync(&state);
+    assert_eq!(sources(&index, "p1", "b"), ["Alpha/Plan"]);
+    assert_eq!(sources(&index, "p2", "c"), ["Alpha/Plan"]);
+    assert_eq!(sources(&index, "p1", "a"), ["Alpha/Spec", "Beta/Spec"]);
+    let broken: Vec<String> = index
+      .report(&order(&state))
+      .broken
+      .iter()
+      .map(|link| format!("{}:{}:{:?}", link.source.note, link.target, link.reason))
+      .collect();
+    assert_eq!(broken, ["Plan:Missing:Missing", "Plan:Shared:Ambiguous", "Shared:Spec:Ambiguous"]);
+  }
+
+  #[test]
+  fn resyncs_edited_renamed_and_removed_notes() {
+    let mut state = sample();
+    let mut index = IndexInner::default();
+    index.sync(&state);
+
+    state.projects[1].notes[1].title = "Missing".into();
+    state.projects[2].notes.clear();
+    index.sync(&state);
+    assert_eq!(index.report(&order(&state)).broken.len(), 1);
+    assert_eq!(sources(&index, "p2", "d"), ["Alpha/Plan"]);
+    assert!(!index.notes.contains_key(&(id("p3"), id("e"))));
+
+    state.projects[0].notes[0].body = "nothing".into();
+    index.sync(&state);
+    assert!(sources(&index, "p1", "b").is_empty());
+    assert!(index.by_target.values().all(|notes| !notes.contains(&(id("p1"), id("a")))));
+  }
+
+  #[test]
+  fn renames_notes_and_rewrites_links_to_them() {
+    let state = sample();
+    let result = rename(state.clone(), &id("p2"), &id("c"), " Beta spec ", true).unwrap();
+    assert_eq!(result.rewritten_links, 2);
+    assert_eq!(
+      result.state.projects[0].notes[0].body,
+      "See [[Spec]] and [[Beta/Beta spec|beta spec]] and [[Missing]] and [[Shared]]\n`[[code]]`"
+    );
+    assert_eq!(result.state.projects[1].notes[0].body, "[[Alpha/Plan]] [[Plan]] [[Beta spec]]");
+    assert_eq!(result.state.projects[1].notes[0].title, "Beta spec");
+
+    let renamed = rename(state.clone(), &id("p1"), &id("a"), "Roadmap", true).unwrap();
+    assert_eq!(renamed.state.projects[1].notes[0].body, "[[Alpha/Roadmap]] [[Roadmap]] [[ Spec ]]");
+    assert_eq!(renamed.state.projects[0].notes[1].body, "back to [[Roadmap]]");
+
+    let plain = rename(state.clone(), &id("p1"), &id("a"), "Roadmap", false).unwrap();
+    assert_eq!((plain.rewritten_links, plain.state.projects[1].notes[0].body.as_str()), (0, "[[Alpha/Plan]] [[Plan]] [[ Spec ]]"));
+  }
+
+  #[test]
+  fn rejects_unknown_notes_and_blank_titles() {
+    let state = sample();
+    assert!(rename(state.clone(), &id("p1"), &id("zz"), "x", true).is_err_and(|err| err == "note not found"));
+    assert!(rename(state.clone(), &id("p1"), &id("a"), "  ", true).is_err_and(|err| err == "note title is empty"));
+  }
+
+  #[test]
+  fn resolves_local_qualified_and_ambiguous_targets() {
+    let state = sample();
+    let target = resolve_wiki_link(state.clone(), id("p2"), "Shared".into()).unwrap();
+    assert_eq!((target.project_id, target.note_id), (id("p2"), id("d")));
+    let target = resolve_wiki_link(state.clone(), id("p3"), "beta / SPEC".into()).unwrap();
+    assert_eq!(target.note_id, id("c"));
+    assert!(resolve_wiki_link(state.clone(), id("p1"), "Shared".into()).is_err_and(|err| err.contains("several")));
+    assert!(resolve_wiki_link(state, id("p1"), "Nope".into()).is_err_and(|err| err == "note not found"));
+  }
+}
@@ -1,8 +1,50 @@
 import { useCallback, useEffect, useMemo, useRef, useState } from 'react'
 import { invoke } from '@tauri-apps/api/core'
-import { Check, FilePlus2, FolderCog, FolderPlus, Pencil, Plus, Save } from 'lucide-react'
+import { listen } from '@tauri-apps/api/event'
+import {
+  AlarmClock,
+  ArchiveRestore,
+  BookOpen,
+  Check,
+  ChevronLeft,
+  ChevronRight,
+  Download,
+  FileCode2,
+  FileInput,
+  FileOutput,
+  FilePlus2,
+  FileText,
+  FileType,
+  FolderCog,
+  FolderPlus,
+  GitCommitHorizontal,
+  History,
+  LayoutTemplate,
+  Link2,
+  ListChecks,
+  ListTree,
+  Lock,
+  Network,
+  NotebookPen,
+  Pencil,
+  Plus,
+  Presentation,
+  QrCode,
+  Save,
+  ScanLine,
+  ScrollText,
+  Search,
+  Sheet,
+  SlidersHorizontal,
+  Tags,
+  Timer,
+  Trash2,
+  Upload,
+  X,
+} from 'lucide-react'
 import Modal from './components/Modal'
 import ProjectsPage from './components/ProjectsPage'
+import RevisionDiff from './components/RevisionDiff'
 import SettingsPage from './components/SettingsPage'
 import TopBar from './components/TopBar'
 import { DEFAULT_SETTINGS, GITHUB_REPO, SETTINGS_KEY, STORAGE_KEY } from './constants'
@@ -15,13 +57,44 @@ import {
   loadSettingsLocal,
   normalizeProjects,
   normalizeSettings,
+  normalizeTags,
   normalizeVersionTag,
 } from './utils/state'
+import { findStep, findStepLocation, formatDuration, mapSteps, moveStepAmongSiblings, removeStepTree, stepProgress, updateStepTree } from './utils/steps'
 import './App.css'
 
+const DOCUMENT_EXPORTS = [
+  { command: 'export_html_report', label: 'documentHtml', icon: FileCode2, workspace: true },
+  { command: 'export_pdf_report', label: 'documentPdf', icon: FileText, workspace: false },
+  { command: 'export_steps_csv', label: 'documentStepsCsv', icon: Sheet, workspace: true },
+  { command: 'export_opml', label: 'documentOpml', icon: ListTree, workspace: true },
+  { command: 'export_org', label: 'documentOrg', icon: ScrollText, workspace: true },
+  { command: 'export_epub', label: 'documentEpub', icon: BookOpen, workspace: true },
+  { command: 'export_docx', label: 'documentDocx', icon: FileType, workspace: true },
+  { command: 'export_slides', label: 'documentSlides', icon: Presentation, workspace: false },
+]
+
+const IMPORT_SOURCES = [
+  { id: 'steps_csv', label: 'importStepsCsv', icon: Sheet, accept: '.csv,text/csv' },
+  { id: 'todoist', label: 'importTodoist', icon: ListChecks, accept: '.csv,text/csv', task: true },
+  { id: 'trello', label: 'importTrello', icon: ListChecks, accept: '.json,application/json', task: true },
+  { id: 'microsoft_todo', label: 'importMicrosoftTodo', icon: ListChecks, accept: '.json,application/json', task: true },
+  { id: 'google_keep', label: 'importGoogleKeep', icon: NotebookPen, accept: '.json,application/json', notes: true, multiple: true },
+  { id: 'evernote', label: 'importEvernote', icon: NotebookPen, accept: '.enex', notes: true, multiple: true },
+  { id: 'joplin', label: 'importJoplin', icon: NotebookPen, accept: '.md,text/markdown', notes: true, multiple: true },
+  { id: 'opml', label: 'importOpml', icon: ListTree, accept: '.opml,.xml,text/x-opml' },
+  { id: 'org', label: 'importOrg', icon: ScrollText, accept: '.org,text/plain' },
+]
+
 function App() {
   const appVersion = import.meta.env.VITE_APP_VERSION || 'dev'
   const importFileRef = useRef(null)
+  const templateFileRef = useRef(null)
+  const attachmentFileRef = useRef(null)
+  const attachmentNoteIdRef = useRef(null)
+  const importSourceFileRef = useRef(null)
+  const importSourceRef = useRef(null)
+  const backupFileRef = useRef(null)
 
   const [projects, setProjects] = useState([])
   const [settings, setSettings] = useState({ ...DEFAULT_SETTINGS })
@@ -33,12 +106,32 @@ function App() {
   const [editProjectOpen, setEditProjectOpen] = useState(false)
   const [createNoteOpen, setCreateNoteOpen] = useState(false)
   const [editNoteOpen, setEditNoteOpen] = useState(false)
-  const [projectForm, setProjectForm] = useState({ name: '', description: '', status: '' })
-  const [projectEditForm, setProjectEditForm] = useState({ name: '', description: '', status: '' })
-  const [noteCreateForm, setNoteCreateForm] = useState({ title: '', body: '' })
+  const [projectForm, setProjectForm] = useState({ name: '', description: '', status: '', tags: '' })
+  const [projectEditForm, setProjectEditForm] = useState({ name: '', description: '', status: '', tags: '', dueAt: '', remindAt: '' })
+  const [noteCreateForm, setNoteCreateForm] = useState({ title: '', body: '', tags: '' })
   const [noteEditForm, setNoteEditForm] = useState(null)
   const [newProjectStep, setNewProjectStep] = useState('')
   const [showStepCreate, setShowStepCreate] = useState(false)
+  const [passwordPrompt, setPasswordPrompt] = useState(null)
+  const [exportFilterForm, setExportFilterForm] = useState(null)
+  const [documentExport, setDocumentExport] = useState(null)
+  const [importCenterOpen, setImportCenterOpen] = useState(false)
+  const [importReport, setImportReport] = useState(null)
+  const [backupRestore, setBackupRestore] = useState(null)
+  const [projectHistory, setProjectHistory] = useState(null)
+  const [projectMap, setProjectMap] = useState(null)
+  const [qrShare, setQrShare] = useState(null)
+  const [qrImportText, setQrImportText] = useState(null)
+  const [dueReminders, setDueReminders] = useState([])
+  const [tagManager, setTagManager] = useState(null)
+  const [linkReport, setLinkReport] = useState({ backlinks: [], broken: [] })
+  const [focusNoteId, setFocusNoteId] = useState(null)
+  const [linkRewritePrompt, setLinkRewritePrompt] = useState(null)
+  const [noteRevisions, setNoteRevisions] = useState(null)
+  const [runningTimer, setRunningTimer] = useState(null)
+  const [timerNow, setTimerNow] = useState(() => Date.now())
+  const [timesheet, setTimesheet] = useState(null)
+  const [templateLibrary, setTemplateLibrary] = useState(null)
   const [updateInfo, setUpdateInfo] = useState({
     loading: false,
     error: '',
@@ -86,11 +179,21 @@ function App() {
     return [...base, selectedProject.status]
   }, [settings.projectStatuses, selectedProject])
 
+  const runningTimerInfo = useMemo(() => {
+    if (!runningTimer) return null
+    const project = projects.find((entry) => entry.id === runningTimer.projectId)
+    const step = project ? findStep(project.steps, runningTimer.stepId) : null
+    return {
+      projectId: runningTimer.projectId,
+      project: project?.name || '',
+      step: step?.text || '',
+      elapsed: formatDuration(Math.max(0, (timerNow - Date.parse(runningTimer.startedAt)) / 1000)),
+    }
+  }, [runningTimer, projects, timerNow])
+
   const progress = useMemo(() => {
     if (!selectedProject) return { done: 0, total: 0, value: 0 }
-    const steps = selectedProject.steps || []
-    const done = steps.filter((step) => step.done).length
-    const total = steps.length
+    const { done, total } = stepProgress(selectedProject.steps)
     return { done, total, value: total ? done / total : 0 }
   }, [selectedProject])
 
@@ -114,7 +217,7 @@ function App() {
             setSettings(loadedSettings)
             setSettingsDraft(loadedSettings)
             setSelectedProjectId(loadedProjects[0]?.id ?? null)
-            await invoke('apply_window_settings', { payload: { windowMode: loadedSettings.windowMode, alwaysOnTop: loadedSettings.alwaysOnTop } })
+            await invoke('apply_window_settings', { payload: { windowMode: loadedSettings.windowMode, alwaysOnTop: loadedSettings.alwaysOnTop, closeToTray: loadedSettings.closeToTray } })
             setLoaded(true)
             return
           }
@@ -149,6 +252,35 @@ function App() {
     return () => clearTimeout(saveTimer)
   }, [projects, settings, loaded])
 
+  useEffect(() => {
+    if (!loaded || !isTauriRuntime()) return
+    invoke('timer_status')
+      .then(setRunningTimer)
+      .catch(() => {
+        // timers stay stopped if the saved one cannot be read
+      })
+  }, [loaded])
+
+  useEffect(() => {
+    if (!runningTimer) return undefined
+    setTimerNow(Date.now())
+    const tick = setInterval(() => setTimerNow(Date.now()), 30000)
+    return () => clearInterval(tick)
+  }, [runningTimer])
+
+  useEffect(() => {
+    if (!loaded || !isTauriRuntime()) return
+    const linkTimer = setTimeout(() => {
+      invoke('link_report', { state: { projects, settings } })
+        .then(setLinkReport)
+        .catch(() => {
+          // links stay as plain text until the next successful refresh
+        })
+    }, 400)
+
+    return () => clearTimeout(linkTimer)
+  }, [projects, settings, loaded])
+
   useEffect(() => {
     if (!loaded) return
     const next = normalizeSettings(settingsDraft)
@@ -158,7 +290,7 @@ function App() {
       return prevJson === nextJson ? prev : next
     })
     if (isTauriRuntime()) {
-      invoke('apply_window_settings', { payload: { windowMode: next.windowMode, alwaysOnTop: next.alwaysOnTop } }).catch(() => {
+      invoke('apply_window_settings', { payload: { windowMode: next.windowMode, alwaysOnTop: next.alwaysOnTop, closeToTray: next.closeToTray } }).catch(() => {
         // noop
       })
     }
@@ -191,6 +323,27 @@ function App() {
     setShowStepCreate(false)
   }, [selectedProjectId])
 
+  useEffect(() => {
+    if (!isTauriRuntime()) return undefined
+    let active = true
+    let unlisten = null
+    listen('reminders-due', (event) => {
+      const items = Array.isArray(event.payload) ? event.payload : []
+      setDueReminders((prev) => [...prev.filter((item) => !items.some((next) => next.key === item.key)), ...items])
+    })
+      .then((stop) => {
+        if (active) unlisten = stop
+        else stop()
+      })
+      .catch(() => {
+        // reminders stay available as desktop notifications
+      })
+    return () => {
+      active = false
+      if (unlisten) unlisten()
+    }
+  }, [])
+
   const checkForUpdates = useCallback(async (options = {}) => {
     const silent = !!options.silent
     if (!silent) pushToast(t('updateCheckStarted'), 'info')
@@ -254,7 +407,7 @@ function App() {
   }
 
   function openCreateProjectModal() {
-    setProjectForm({ name: '', description: '', status: defaultProjectStatus })
+    setProjectForm({ name: '', description: '', status: defaultProjectStatus, tags: '' })
     setCreateProjectOpen(true)
   }
 
@@ -264,18 +417,21 @@ function App() {
       name: selectedProject.name,
       description: selectedProject.description,
       status: selectedProject.status || defaultProjectStatus,
+      tags: (selectedProject.tags || []).join(', '),
+      dueAt: selectedProject.dueAt || '',
+      remindAt: selectedProject.remindAt || '',
     })
     setEditProjectOpen(true)
   }
 
   function openCreateNoteModal() {
     if (!selectedProject) return
-    setNoteCreateForm({ title: '', body: '' })
+    setNoteCreateForm({ title: '', body: '', tags: '' })
     setCreateNoteOpen(true)
   }
 
   function openEditNoteModal(note) {
-    setNoteEditForm({ id: note.id, title: note.title, body: note.body })
+    setNoteEditForm({ id: note.id, title: note.title, body: note.body, tags: (note.tags || []).join(', ') })
     setEditNoteOpen(true)
   }
 
@@ -284,6 +440,75 @@ function App() {
     setActivePage('settings')
   }
 
+  async function createBackup() {
+    if (!isTauriRuntime()) {
+      pushToast(t('backupDesktopOnly'), 'error')
+      return
+    }
+    try {
+      const path = await invoke('create_backup', { state: { projects, settings } })
+      pushToast(t('documentExported').replace('{path}', path), 'success')
+    } catch {
+      pushToast(t('backupError'), 'error')
+    }
+  }
+
+  async function cleanAttachments() {
+    if (!isTauriRuntime()) {
+      pushToast(t('attachmentCleanupDesktopOnly'), 'error')
+      return
+    }
+    try {
+      const report = await invoke('clean_attachments', { state: { projects, settings } })
+      const freed = (report.freedBytes / (1024 * 1024)).toFixed(1)
+      pushToast(t('attachmentCleanupDone').replace('{count}', report.removedBlobs).replace('{size}', freed), 'success')
+    } catch {
+      pushToast(t('attachmentCleanupError'), 'error')
+    }
+  }
+
+  function askRestoreBackup() {
+    if (!isTauriRuntime()) {
+      pushToast(t('backupDesktopOnly'), 'error')
+      return
+    }
+    backupFileRef.current?.click()
+  }
+
+  async function onBackupFilePicked(event) {
+    const file = event.target.files?.[0]
+    event.target.value = ''
+    if (!file) return
+    try {
+      setBackupRestore({ name: file.name, data: await readFileBase64(file) })
+    } catch {
+      pushToast(t('backupError'), 'error')
+    }
+  }
+
+  async function restoreBackup(mode) {
+    if (!backupRestore) return
+    try {
+      const result = await invoke('restore_backup', { data: backupRestore.data, mode, current: { projects, settings } })
+      const restoredProjects = normalizeProjects(result.state.projects)
+      const restoredSettings = normalizeSettings(result.state.settings)
+      setProjects(restoredProjects)
+      setSettings(restoredSettings)
+      setSettingsDraft(restoredSettings)
+      setSelectedProjectId((prev) => (restoredProjects.some((project) => project.id === prev) ? prev : restoredProjects[0]?.id ?? null))
+      setBackupRestore(null)
+      pushToast(
+        t('backupRestored')
+          .replace('{added}', result.addedProjects)
+          .replace('{skipped}', result.skippedProjects)
+          .replace('{date}', result.createdAt),
+        'success',
+      )
+    } catch (error) {
+      pushToast(`${t('backupError')}: ${error}`, 'error')
+    }
+  }
+
   function saveNewProject() {
     const name = projectForm.name.trim()
     if (!name) return
@@ -295,6 +520,9 @@ function App() {
       pinned: false,
       notes: [],
       steps: [],
+      tags: normalizeTags(projectForm.tags),
+      dueAt: '',
+      remindAt: '',
     }
     setProjects((prev) => [...prev, project])
     setSelectedProjectId(project.id)
@@ -314,6 +542,9 @@ function App() {
               name,
               description: projectEditForm.description.trim(),
               status: statusesEnabled ? String(projectEditForm.status || '').trim() : project.status,
+              tags: normalizeTags(projectEditForm.tags),
+              dueAt: projectEditForm.dueAt,
+              remindAt: projectEditForm.remindAt,
             }),
     )
     setEditProjectOpen(false)
@@ -340,8 +571,7 @@ function App() {
     )
   }
 
-  async function exportSelectedProject() {
-    if (!selectedProject) return
+  function buildProjectExport() {
     const payload = {
       format: 'project-notes-studio-project',
       version: 2,
@@ -362,8 +592,12 @@ function App() {
       .replace(/[^a-z0-9а-яё_-]+/gi, '-')
       .replace(/^-+|-+$/g, '') || 'project'
 
-    const fileName = `${safeName}.pns-project.json`
-    const content = JSON.stringify(payload, null, 2)
+    return { fileName: `${safeName}.pns-project.json`, content: JSON.stringify(payload, null, 2) }
+  }
+
+  async function exportSelectedProject() {
+    if (!selectedProject) return
+    const { fileName, content } = buildProjectExport()
 
     if (isTauriRuntime()) {
       try {
@@ -388,6 +622,173 @@ function App() {
     pushToast(t('projectExported'), 'success')
   }
 
+  function openExportFilterModal() {
+    if (!selectedProject) return
+    if (!isTauriRuntime()) {
+      pushToast(t('filteredExportDesktopOnly'), 'error')
+      return
+    }
+    setExportFilterForm({
+      noteIds: (selectedProject.notes || []).map((note) => note.id),
+      openStepsOnly: false,
+      excludeDescriptions: false,
+      stripIds: false,
+    })
+  }
+
+  function toggleExportFilterNote(noteId) {
+    setExportFilterForm((prev) => {
+      if (!prev) return prev
+      const noteIds = prev.noteIds.includes(noteId) ? prev.noteIds.filter((id) => id !== noteId) : [...prev.noteIds, noteId]
+      return { ...prev, noteIds }
+    })
+  }
+
+  async function exportSelectedProjectFiltered() {
+    if (!selectedProject || !exportFilterForm) return
+    const { fileName, content } = buildProjectExport()
+    try {
+      await invoke('export_project_file', { filename: fileName, content, filter: exportFilterForm })
+      setExportFilterForm(null)
+      pushToast(t('projectExported'), 'success')
+    } catch {
+      pushToast(t('projectExportError'), 'error')
+    }
+  }
+
+  function openDocumentExport() {
+    if (!selectedProject) return
+    if (!isTauriRuntime()) {
+      pushToast(t('documentExportDesktopOnly'), 'error')
+      return
+    }
+    setDocumentExport({ workspace: false })
+  }
+
+  async function exportDocument(format) {
+    if (!selectedProject || !documentExport) return
+    const projectId = documentExport.workspace && format.workspace ? null : selectedProject.id
+    try {
+      const path = await invoke(format.command, { state: { projects, settings }, projectId })
+      setDocumentExport(null)
+      pushToast(t('documentExported').replace('{path}', path), 'success')
+    } catch {
+      pushToast(t('projectExportError'), 'error')
+    }
+  }
+
+  async function openProjectMap() {
+    if (!selectedProject) return
+    if (!isTauriRuntime()) {
+      pushToast(t('projectMapDesktopOnly'), 'error')
+      return
+    }
+    try {
+      const map = await invoke('project_map', { state: { projects, settings }, projectId: selectedProject.id, export: false })
+      setProjectMap({ projectId: selectedProject.id, svg: map.svg })
+    } catch {
+      pushToast(t('projectMapError'), 'error')
+    }
+  }
+
+  async function exportProjectMap() {
+    if (!projectMap) return
+    try {
+      const map = await invoke('project_map', { state: { projects, settings }, projectId: projectMap.projectId, export: true })
+      setProjectMap(null)
+      pushToast(t('documentExported').replace('{path}', map.path), 'success')
+    } catch {
+      pushToast(t('projectMapError'), 'error')
+    }
+  }
+
+  function exportSelectedProjectEncrypted() {
+    if (!selectedProject) return
+    if (!isTauriRuntime()) {
+      pushToast(t('encryptedDesktopOnly'), 'error')
+      return
+    }
+    setPasswordPrompt({ mode: 'export', password: '', confirm: '' })
+  }
+
+  async function confirmPasswordPrompt() {
+    if (!passwordPrompt) return
+    const { mode, password, confirm, content } = passwordPrompt
+    if (!password) {
+      pushToast(t('passwordRequired'), 'error')
+      return
+    }
+
+    if (mode === 'export') {
+      if (password !== confirm) {
+        pushToast(t('passwordMismatch'), 'error')
+        return
+      }
+      const exported = buildProjectExport()
+      try {
+        await invoke('export_encrypted_project', { filename: exported.fileName, content: exported.content, password })
+        setPasswordPrompt(null)
+        pushToast(t('projectExported'), 'success')
+      } catch {
+        pushToast(t('projectExportError'), 'error')
+      }
+      return
+    }
+
+    let decrypted
+    try {
+      decrypted = await invoke('decrypt_project_file', { content, password })
+    } catch {
+      pushToast(t('wrongPassword'), 'error')
+      return
+    }
+    setPasswordPrompt(null)
+    importProjectText(decrypted)
+  }
+
+  async function shareSelectedProjectQr() {
+    if (!selectedProject) return
+    if (!isTauriRuntime()) {
+      pushToast(t('qrDesktopOnly'), 'error')
+      return
+    }
+    const { content } = buildProjectExport()
+    try {
+      const parts = await invoke('encode_project_qr', { content, format: 'svg' })
+      setQrShare({ parts, index: 0 })
+    } catch {
+      pushToast(t('qrTooLarge'), 'error')
+    }
+  }
+
+  function openQrImport() {
+    if (!isTauriRuntime()) {
+      pushToast(t('qrDesktopOnly'), 'error')
+      return
+    }
+    setQrImportText('')
+  }
+
+  async function importProjectFromQr() {
+    const parts = String(qrImportText || '')
+      .split(/\r?\n/)
+      .map((line) => line.trim())
+      .filter(Boolean)
+    let result
+    try {
+      result = await invoke('decode_project_qr', { parts })
+    } catch {
+      pushToast(t('qrImportError'), 'error')
+      return
+    }
+    if (!result.complete) {
+      pushToast(t('qrMissingParts').replace('{parts}', result.missing.join(', ')), 'error')
+      return
+    }
+    setQrImportText(null)
+    importProjectText(result.content)
+  }
+
   function askImportProject() {
     importFileRef.current?.click()
   }
@@ -397,8 +798,27 @@ function App() {
     event.target.value = ''
     if (!file) return
 
+    let text
+    try {
+      text = await file.text()
+      const parsed = JSON.parse(text)
+      if (parsed?.format === 'project-notes-studio-encrypted') {
+        if (!isTauriRuntime()) {
+          pushToast(t('encryptedDesktopOnly'), 'error')
+          return
+        }
+        setPasswordPrompt({ mode: 'import', password: '', confirm: '', content: text })
+        return
+      }
+    } catch {
+      pushToast(t('projectImportError'), 'error')
+      return
+    }
+    importProjectText(text)
+  }
+
+  function importProjectText(text) {
     try {
-      const text = await file.text()
       const parsed = JSON.parse(text)
       const fileVersion = normalizeVersionTag(parsed?.appVersion || '')
       const currentVersion = normalizeVersionTag(appVersion)
@@ -416,6 +836,12 @@ function App() {
         return
       }
 
+      if (parsed?.attachmentFiles && typeof parsed.attachmentFiles === 'object' && isTauriRuntime()) {
+        invoke('import_attachment_files', { files: parsed.attachmentFiles }).catch(() => {
+          pushToast(t('attachmentImportError'), 'error')
+        })
+      }
+
       const sourceProject = parsed?.project ?? parsed
       const normalized = normalizeProjects([sourceProject])[0]
       if (!normalized) throw new Error('invalid payload')
@@ -424,7 +850,7 @@ function App() {
         ...normalized,
         id: createId(),
         notes: (normalized.notes || []).map((note) => ({ ...note, id: createId() })),
-        steps: (normalized.steps || []).map((step) => ({ ...step, id: createId() })),
+        steps: mapSteps(normalized.steps, (step) => ({ ...step, id: createId() })),
       }
 
       const importedStatus = String(importedProject.status || '').trim()
@@ -448,11 +874,94 @@ function App() {
     }
   }
 
+  function addImportedProjects(report) {
+    const imported = normalizeProjects(report.projects).map((project) => ({
+      ...project,
+      id: createId(),
+      notes: (project.notes || []).map((note) => ({ ...note, id: createId() })),
+      steps: mapSteps(project.steps, (step) => ({ ...step, id: createId() })),
+    }))
+    const statuses = [...new Set(imported.map((project) => String(project.status || '').trim()).filter(Boolean))]
+    if (statuses.length) {
+      const addStatuses = (prev) => {
+        const missing = statuses.filter((status) => !(prev.projectStatuses || []).includes(status))
+        return missing.length ? { ...prev, projectStatuses: [...(prev.projectStatuses || []), ...missing] } : prev
+      }
+      setSettings(addStatuses)
+      setSettingsDraft(addStatuses)
+    }
+    setProjects((prev) => [...prev, ...imported])
+    setSelectedProjectId(imported[0]?.id ?? selectedProjectId)
+    setActivePage('projects')
+    setImportReport({
+      summary: t('importedProjects').replace('{count}', imported.length),
+      details: report.unmapped.map((item) => `${item.field}: ${item.count}`),
+    })
+  }
+
+  function openImportCenter() {
+    if (!isTauriRuntime()) {
+      pushToast(t('importCenterDesktopOnly'), 'error')
+      return
+    }
+    setImportCenterOpen(true)
+  }
+
+  function askImportSource(source) {
+    const input = importSourceFileRef.current
+    if (!input) return
+    importSourceRef.current = source
+    input.accept = source.accept
+    input.multiple = !!source.multiple
+    input.click()
+  }
+
+  async function onImportSourcePicked(event) {
+    const files = [...(event.target.files || [])]
+    event.target.value = ''
+    const source = importSourceRef.current
+    if (!files.length || !source) return
+    try {
+      if (source.id === 'steps_csv') {
+        const result = await invoke('import_steps_csv', { state: { projects, settings }, content: await files[0].text() })
+        setProjects(normalizeProjects(result.state.projects))
+        setImportReport({
+          summary: t('stepsCsvSummary').replace('{updated}', result.updated).replace('{appended}', result.appended),
+          details: result.unmatched.map((row) => `${t('csvLine')} ${row.line}: ${row.text} — ${row.reason}`),
+        })
+      } else if (source.task) {
+        const report = await invoke('import_task_export', {
+          source: source.id,
+          fileName: files[0].name,
+          content: await files[0].text(),
+          timeZone: settings.timeZone || '',
+        })
+        addImportedProjects(report)
+      } else if (source.notes) {
+        const contents = await Promise.all(files.map(async (file) => [file.name, await file.text()]))
+        const report = await invoke('import_notes_export', { source: source.id, files: Object.fromEntries(contents) })
+        addImportedProjects(report)
+      } else if (source.id === 'org') {
+        const result = await invoke('import_org', { state: { projects, settings }, content: await files[0].text() })
+        setProjects(normalizeProjects(result.state.projects))
+        setImportReport({
+          summary: t('orgSummary').replace('{updated}', result.updated).replace('{added}', result.added),
+          details: [],
+        })
+      } else if (source.id === 'opml') {
+        addImportedProjects(await invoke('import_opml', { content: await files[0].text() }))
+      }
+      setImportCenterOpen(false)
+    } catch (error) {
+      pushToast(`${t('importSourceError')}: ${error}`, 'error')
+    }
+  }
+
   function saveNewNote() {
     if (!selectedProject) return
     const title = noteCreateForm.title.trim()
     if (!title) return
-    const note = { id: createId(), title, body: noteCreateForm.body.trim() }
+    const note = { id: createId(), title, body: noteCreateForm.body.trim(), tags: normalizeTags(noteCreateForm.tags), attachments: [] }
     setProjects((prev) =>
       prev.map((project) =>
         project.id !== selectedProject.id ? project : { ...project, notes: [...project.notes, note] }),
@@ -460,20 +969,49 @@ function App() {
     setCreateNoteOpen(false)
   }
 
+  function applyNoteEdit(projectId, noteId, fields) {
+    return projects.map((project) => {
+      if (project.id !== projectId) return project
+      return {
+        ...project,
+        notes: project.notes.map((note) => (note.id !== noteId ? note : { ...note, ...fields })),
+      }
+    })
+  }
+
+  async function renameNoteWithLinks(rewriteLinks) {
+    const prompt = linkRewritePrompt
+    if (!prompt) return
+    setLinkRewritePrompt(null)
+    const edited = applyNoteEdit(prompt.projectId, prompt.noteId, { body: prompt.body, tags: prompt.tags })
+    try {
+      const result = await invoke('rename_note', {
+        state: { projects: edited, settings },
+        projectId: prompt.projectId,
+        noteId: prompt.noteId,
+        title: prompt.title,
+        rewriteLinks,
+      })
+      setProjects(normalizeProjects(result.state.projects))
+      if (rewriteLinks) pushToast(t('linksRewritten').replace('{count}', result.rewrittenLinks), 'success')
+    } catch {
+      setProjects(applyNoteEdit(prompt.projectId, prompt.noteId, { title: prompt.title, body: prompt.body, tags: prompt.tags }))
+      pushToast(t('linksRewriteError'), 'error')
+    }
+  }
+
   function saveEditedNote() {
     if (!selectedProject || !noteEditForm) return
     const title = noteEditForm.title.trim()
     if (!title) return
-    setProjects((prev) =>
-      prev.map((project) => {
-        if (project.id !== selectedProject.id) return project
-        return {
-          ...project,
-          notes: project.notes.map((note) =>
-            note.id !== noteEditForm.id ? note : { ...note, title, body: noteEditForm.body.trim() }),
-        }
-      }),
-    )
+    const fields = { title, body: noteEditForm.body.trim(), tags: normalizeTags(noteEditForm.tags) }
+    const original = selectedProject.notes.find((note) => note.id === noteEditForm.id)
+    const backlinks = linkReport.backlinks.find((entry) => entry.projectId === selectedProject.id && entry.noteId === noteEditForm.id)
+    if (original && original.title !== title && backlinks?.sources.length && isTauriRuntime()) {
+      setLinkRewritePrompt({ projectId: selectedProject.id, noteId: noteEditForm.id, ...fields, count: backlinks.sources.length })
+    } else {
+      setProjects(applyNoteEdit(selectedProject.id, noteEditForm.id, fields))
+    }
     setEditNoteOpen(false)
     setNoteEditForm(null)
   }
@@ -488,74 +1026,629 @@ function App() {
     )
   }
 
-  function addProjectStep() {
-    if (!selectedProject) return
-    const text = newProjectStep.trim()
-    if (!text) return
-    const step = { id: createId(), text, done: false }
-    setProjects((prev) =>
-      prev.map((project) =>
-        project.id !== selectedProject.id ? project : { ...project, steps: [...project.steps, step] }),
-    )
-    setNewProjectStep('')
+  async function loadRevisionDiff(view) {
+    const ids = { projectId: view.projectId, noteId: view.noteId }
+    try {
+      const loaded = view.compareTo
+        ? { diff: await invoke('diff_revisions', { ...ids, from: view.compareTo, to: view.selected, mode: view.mode }) }
+        : { revision: await invoke('note_revision', { ...ids, revisionId: view.selected }) }
+      setNoteRevisions((prev) =>
+        prev && prev.selected === view.selected && prev.compareTo === view.compareTo && prev.mode === view.mode ? { ...prev, ...loaded } : prev)
+    } catch {
+      pushToast(t('noteRevisionsError'), 'error')
+    }
   }
 
-  function updateProjectStep(stepId, patch) {
-    if (!selectedProject) return
-    setProjects((prev) =>
-      prev.map((project) => {
-        if (project.id !== selectedProject.id) return project
-        return {
-          ...project,
-          steps: project.steps.map((step) => (step.id === stepId ? { ...step, ...patch } : step)),
-        }
-      }),
-    )
+  function updateRevisionView(changes) {
+    if (!noteRevisions) return
+    const view = { ...noteRevisions, ...changes, diff: null, revision: null }
+    setNoteRevisions(view)
+    loadRevisionDiff(view)
   }
 
-  function removeProjectStep(stepId) {
-    if (!selectedProject) return
-    setProjects((prev) =>
-      prev.map((project) =>
-        project.id !== selectedProject.id
-          ? project
-          : { ...project, steps: project.steps.filter((step) => step.id !== stepId) }),
-    )
+  function selectRevision(revisionId) {
+    const index = noteRevisions.list.findIndex((revision) => revision.id === revisionId)
+    updateRevisionView({ selected: revisionId, compareTo: noteRevisions.list[index + 1]?.id || null })
   }
 
-  function moveProjectStep(from, to) {
+  async function openNoteRevisions(note) {
     if (!selectedProject) return
-    if (to < 0 || to >= selectedProject.steps.length) return
-    setProjects((prev) =>
-      prev.map((project) => {
-        if (project.id !== selectedProject.id) return project
-        const next = [...project.steps]
-        const [moved] = next.splice(from, 1)
-        next.splice(to, 0, moved)
-        return { ...project, steps: next }
-      }),
-    )
+    if (!isTauriRuntime()) {
+      pushToast(t('noteRevisionsDesktopOnly'), 'error')
+      return
+    }
+    try {
+      const list = await invoke('note_revisions', { projectId: selectedProject.id, noteId: note.id })
+      const view = {
+        projectId: selectedProject.id,
+        noteId: note.id,
+        list,
+        selected: list[0]?.id || null,
+        compareTo: list[1]?.id || null,
+        mode: 'line',
+        diff: null,
+        revision: null,
+      }
+      setNoteRevisions(view)
+      if (view.selected) loadRevisionDiff(view)
+    } catch {
+      pushToast(t('noteRevisionsError'), 'error')
+    }
   }
 
-  return (
-    <div className="app-shell" data-anim={settings.animations ? 'on' : 'off'}>
-      <div className="backdrop wave-one" />
-      <div className="backdrop wave-two" />
-      <div className="noise" />
+  async function restoreNoteRevision() {
+    const view = noteRevisions
+    if (!view?.selected) return
+    try {
+      const result = await invoke('restore_revision', {
+        state: { projects, settings },
+        projectId: view.projectId,
+        noteId: view.noteId,
+        revisionId: view.selected,
+      })
+      setProjects(normalizeProjects(result.projects))
+      setNoteRevisions(null)
+      pushToast(t('revisionRestored'), 'success')
+    } catch {
+      pushToast(t('revisionRestoreError'), 'error')
+    }
+  }
 
-      <TopBar
-        t={t}
-        askImportProject={askImportProject}
-        selectedProject={selectedProject}
-        progress={progress}
-        isContextualControls={isContextualControls}
-        updateInfo={updateInfo}
-        openCreateProjectModal={openCreateProjectModal}
-        openEditProjectModal={openEditProjectModal}
-        removeSelectedProject={removeSelectedProject}
-        openCreateNoteModal={openCreateNoteModal}
-        openSettingsPage={openSettingsPage}
-        onOpenProjects={() => {
+  async function loadProjectHistory(projectId, noteId) {
+    const entries = await invoke('history_log', { projectId, noteId, limit: 100 })
+    const view = { projectId, noteId, entries, selected: null, diff: null }
+    setProjectHistory(view)
+    if (entries.length) selectHistoryEntry(view, entries[0].commit)
+  }
+
+  async function openProjectHistory() {
+    if (!selectedProject) return
+    if (!isTauriRuntime()) {
+      pushToast(t('projectHistoryDesktopOnly'), 'error')
+      return
+    }
+    if (!settings.gitHistory) {
+      pushToast(t('projectHistoryDisabled'), 'error')
+      return
+    }
+    try {
+      await loadProjectHistory(selectedProject.id, null)
+    } catch {
+      pushToast(t('projectHistoryError'), 'error')
+    }
+  }
+
+  async function changeProjectHistoryNote(value) {
+    if (!projectHistory) return
+    const note = (selectedProject?.notes || []).find((item) => String(item.id) === value)
+    try {
+      await loadProjectHistory(projectHistory.projectId, note ? note.id : null)
+    } catch {
+      pushToast(t('projectHistoryError'), 'error')
+    }
+  }
+
+  async function selectHistoryEntry(view, commit) {
+    setProjectHistory({ ...view, selected: commit, diff: null })
+    try {
+      const diff = await invoke('history_diff', { from: commit, to: null, projectId: view.projectId, noteId: view.noteId })
+      setProjectHistory((prev) => (prev?.selected === commit ? { ...prev, diff } : prev))
+    } catch {
+      pushToast(t('projectHistoryError'), 'error')
+    }
+  }
+
+  async function restoreProjectHistory() {
+    const view = projectHistory
+    if (!view?.selected) return
+    try {
+      const state = await invoke('history_restore', {
+        commit: view.selected,
+        current: { projects, settings },
+        projectId: view.projectId,
+        noteId: view.noteId,
+      })
+      setProjects(normalizeProjects(state.projects))
+      setProjectHistory(null)
+      pushToast(t('revisionRestored'), 'success')
+    } catch {
+      pushToast(t('revisionRestoreError'), 'error')
+    }
+  }
+
+  function updateNoteAttachments(noteId, update) {
+    if (!selectedProject) return
+    setProjects((prev) =>
+      prev.map((project) =>
+        project.id !== selectedProject.id
+          ? project
+          : {
+              ...project,
+              notes: project.notes.map((note) => (note.id !== noteId ? note : { ...note, attachments: update(note.attachments || []) })),
+            }),
+    )
+  }
+
+  function askAttachToNote(noteId) {
+    if (!isTauriRuntime()) {
+      pushToast(t('attachmentsDesktopOnly'), 'error')
+      return
+    }
+    attachmentNoteIdRef.current = noteId
+    attachmentFileRef.current?.click()
+  }
+
+  function readFileBase64(file) {
+    return new Promise((resolve, reject) => {
+      const reader = new FileReader()
+      reader.onload = () => resolve(String(reader.result).split(',')[1] || '')
+      reader.onerror = () => reject(reader.error)
+      reader.readAsDataURL(file)
+    })
+  }
+
+  async function onAttachmentFilesPicked(event) {
+    const files = [...(event.target.files || [])]
+    event.target.value = ''
+    const noteId = attachmentNoteIdRef.current
+    if (!files.length || !noteId) return
+
+    const added = []
+    for (const file of files) {
+      try {
+        const data = await readFileBase64(file)
+        added.push(await invoke('add_attachment', { name: file.name, data }))
+      } catch {
+        pushToast(`${t('attachmentAddError')}: ${file.name}`, 'error')
+      }
+    }
+    if (!added.length) return
+    updateNoteAttachments(noteId, (attachments) => [
+      ...attachments,
+      ...added.filter((attachment, index) =>
+        !attachments.some((existing) => existing.hash === attachment.hash) &&
+        added.findIndex((other) => other.hash === attachment.hash) === index),
+    ])
+  }
+
+  function removeNoteAttachment(noteId, hash) {
+    updateNoteAttachments(noteId, (attachments) => attachments.filter((attachment) => attachment.hash !== hash))
+  }
+
+  async function openNoteAttachment(attachment) {
+    try {
+      const savedPath = await invoke('open_attachment', { hash: attachment.hash, name: attachment.name })
+      if (savedPath) pushToast(t('attachmentRevealed'), 'success')
+    } catch {
+      pushToast(t('attachmentOpenError'), 'error')
+    }
+  }
+
+  async function saveNoteAttachment(attachment) {
+    try {
+      await invoke('export_attachment', { hash: attachment.hash, name: attachment.name })
+      pushToast(t('attachmentSaved'), 'success')
+    } catch {
+      pushToast(t('attachmentOpenError'), 'error')
+    }
+  }
+
+  const loadAttachmentThumbnail = useCallback((hash) => {
+    if (!isTauriRuntime()) return Promise.resolve(null)
+    return invoke('attachment_thumbnail', { hash }).catch(() => null)
+  }, [])
+
+  function addProjectStep() {
+    if (!selectedProject) return
+    const text = newProjectStep.trim()
+    if (!text) return
+    const step = { id: createId(), text, done: false, dueAt: '', remindAt: '', tags: [], children: [] }
+    setProjects((prev) =>
+      prev.map((project) =>
+        project.id !== selectedProject.id ? project : { ...project, steps: [...project.steps, step] }),
+    )
+    setNewProjectStep('')
+  }
+
+  async function completeRecurringStep(stepId) {
+    try {
+      const result = await invoke('complete_recurring_step', { state: { projects, settings }, projectId: selectedProject.id, stepId })
+      setProjects(normalizeProjects(result.state.projects))
+      if (result.nextDueAt) pushToast(t('recurrenceNext').replace('{date}', result.nextDueAt.replace('T', ' ')), 'success')
+    } catch {
+      pushToast(t('recurrenceError'), 'error')
+    }
+  }
+
+  const previewRecurrence = useCallback(
+    (rule, dueAt) => invoke('preview_recurrence', { rule, dueAt: dueAt || null, timeZone: settings.timeZone || '' }),
+    [settings.timeZone],
+  )
+
+  function updateProjectStep(stepId, patch) {
+    if (!selectedProject) return
+    if (patch.done === true && isTauriRuntime() && findStep(selectedProject.steps, stepId)?.recurrence) {
+      completeRecurringStep(stepId)
+      return
+    }
+    if (Object.keys(patch).length === 1 && 'done' in patch && isTauriRuntime()) {
+      runStepTreeCommand('set_step_done', { stepId, done: patch.done })
+      return
+    }
+    setProjects((prev) =>
+      prev.map((project) => {
+        if (project.id !== selectedProject.id) return project
+        return {
+          ...project,
+          steps: updateStepTree(project.steps, stepId, patch, settings.autoCompleteSteps),
+        }
+      }),
+    )
+  }
+
+  function removeProjectStep(stepId) {
+    if (!selectedProject) return
+    setProjects((prev) =>
+      prev.map((project) =>
+        project.id !== selectedProject.id
+          ? project
+          : { ...project, steps: removeStepTree(project.steps, stepId) }),
+    )
+  }
+
+  function moveProjectStep(stepId, offset) {
+    if (!selectedProject) return
+    const location = findStepLocation(selectedProject.steps, stepId)
+    if (isTauriRuntime() && location) {
+      const index = location.index + offset
+      if (index < 0 || index >= location.count) return
+      runStepTreeCommand('move_step', { stepId, parentId: location.parentId, index })
+      return
+    }
+    setProjects((prev) =>
+      prev.map((project) =>
+        project.id !== selectedProject.id ? project : { ...project, steps: moveStepAmongSiblings(project.steps, stepId, offset) }),
+    )
+  }
+
+  function nestProjectStep(stepId, command) {
+    if (!selectedProject) return
+    if (!isTauriRuntime()) {
+      pushToast(t('stepTreeDesktopOnly'), 'error')
+      return
+    }
+    runStepTreeCommand(command, { stepId })
+  }
+
+  async function runStepTreeCommand(command, args) {
+    try {
+      const updated = await invoke(command, { project: selectedProject, ...args, autoComplete: !!settings.autoCompleteSteps })
+      const [normalized] = normalizeProjects([updated])
+      setProjects((prev) => prev.map((project) => (project.id === selectedProject.id ? { ...project, steps: normalized.steps } : project)))
+    } catch {
+      pushToast(t('stepTreeError'), 'error')
+    }
+  }
+
+  async function runTimerCommand(command, args = {}) {
+    if (!isTauriRuntime()) {
+      pushToast(t('timeTrackingDesktopOnly'), 'error')
+      return
+    }
+    try {
+      const result = await invoke(command, { state: { projects, settings }, ...args })
+      setProjects(normalizeProjects(result.state.projects))
+      setRunningTimer(result.timer)
+    } catch {
+      pushToast(t('timerError'), 'error')
+    }
+  }
+
+  function toggleStepTimer(stepId) {
+    if (!selectedProject) return
+    if (runningTimer?.projectId === selectedProject.id && runningTimer?.stepId === stepId) {
+      runTimerCommand('stop_timer')
+    } else {
+      runTimerCommand('start_timer', { projectId: selectedProject.id, stepId })
+    }
+  }
+
+  async function updateTimeEntries(command, args, errorKey) {
+    if (!selectedProject) return false
+    if (!isTauriRuntime()) {
+      pushToast(t('timeTrackingDesktopOnly'), 'error')
+      return false
+    }
+    try {
+      const result = await invoke(command, { state: { projects, settings }, projectId: selectedProject.id, ...args })
+      setProjects(normalizeProjects(result.projects))
+      return true
+    } catch {
+      pushToast(t(errorKey), 'error')
+      return false
+    }
+  }
+
+  function addManualTime(stepId, entry) {
+    return updateTimeEntries(
+      'add_time_entry',
+      { stepId, startedAt: entry.startedAt, minutes: Number(entry.minutes) || 0, note: entry.note },
+      'timeEntryAddError',
+    )
+  }
+
+  function removeTimeEntry(stepId, entryId) {
+    return updateTimeEntries('remove_time_entry', { stepId, entryId }, 'timeEntryRemoveError')
+  }
+
+  function localDay(date) {
+    return `${date.getFullYear()}-${String(date.getMonth() + 1).padStart(2, '0')}-${String(date.getDate()).padStart(2, '0')}`
+  }
+
+  async function loadTimesheet(view) {
+    try {
+      const report = await invoke('time_report', {
+        state: { projects, settings },
+        from: view.from,
+        to: view.to,
+        projectId: view.scope === 'project' ? view.projectId : null,
+        period: view.period,
+      })
+      setTimesheet((prev) => (prev && prev.from === view.from && prev.to === view.to && prev.scope === view.scope && prev.period === view.period ? { ...prev, report } : prev))
+    } catch {
+      pushToast(t('timesheetError'), 'error')
+    }
+  }
+
+  function updateTimesheet(changes) {
+    if (!timesheet) return
+    const view = { ...timesheet, ...changes, report: null }
+    setTimesheet(view)
+    loadTimesheet(view)
+  }
+
+  function openTimesheet() {
+    if (!isTauriRuntime()) {
+      pushToast(t('timeTrackingDesktopOnly'), 'error')
+      return
+    }
+    const today = new Date()
+    const view = {
+      projectId: selectedProject?.id ?? null,
+      from: localDay(new Date(today.getFullYear(), today.getMonth(), 1)),
+      to: localDay(today),
+      period: 'day',
+      scope: selectedProject ? 'project' : 'all',
+      report: null,
+    }
+    setTimesheet(view)
+    loadTimesheet(view)
+  }
+
+  async function exportTimesheet() {
+    if (!timesheet) return
+    try {
+      await invoke('export_timesheet', {
+        state: { projects, settings },
+        from: timesheet.from,
+        to: timesheet.to,
+        projectId: timesheet.scope === 'project' ? timesheet.projectId : null,
+        period: timesheet.period,
+      })
+      pushToast(t('timesheetExported'), 'success')
+    } catch {
+      pushToast(t('timesheetError'), 'error')
+    }
+  }
+
+  async function openTemplateLibrary() {
+    if (!isTauriRuntime()) {
+      pushToast(t('templatesDesktopOnly'), 'error')
+      return
+    }
+    try {
+      const list = await invoke('project_templates')
+      setTemplateLibrary({ list, saveName: selectedProject?.name || '', filling: null })
+    } catch {
+      pushToast(t('templatesError'), 'error')
+    }
+  }
+
+  async function reloadTemplates(changes = {}) {
+    const list = await invoke('project_templates')
+    setTemplateLibrary((prev) => (prev ? { ...prev, ...changes, list } : prev))
+  }
+
+  async function saveProjectAsTemplate() {
+    const name = templateLibrary?.saveName.trim()
+    if (!selectedProject || !name) return
+    try {
+      await invoke('save_project_template', { project: selectedProject, name })
+      await reloadTemplates()
+      pushToast(t('templateSaved'), 'success')
+    } catch {
+      pushToast(t('templatesError'), 'error')
+    }
+  }
+
+  async function deleteTemplate(templateId) {
+    try {
+      await invoke('delete_project_template', { templateId })
+      await reloadTemplates({ filling: null })
+    } catch {
+      pushToast(t('templatesError'), 'error')
+    }
+  }
+
+  function startProjectFromTemplate(template) {
+    if (!template.placeholders.length) {
+      createProjectFromTemplate(template.id, {})
+      return
+    }
+    const values = Object.fromEntries(template.placeholders.map((name) => [name, '']))
+    setTemplateLibrary((prev) => (prev ? { ...prev, filling: { id: template.id, name: template.name, values } } : prev))
+  }
+
+  async function createProjectFromTemplate(templateId, values) {
+    try {
+      const created = await invoke('create_project_from_template', { templateId, values, timeZone: settings.timeZone || '' })
+      const project = normalizeProjects([created])[0]
+      if (statusesEnabled && !project.status) project.status = defaultProjectStatus
+      setProjects((prev) => [...prev, project])
+      setSelectedProjectId(project.id)
+      setActivePage('projects')
+      setTemplateLibrary(null)
+      pushToast(t('templateProjectCreated'), 'success')
+    } catch {
+      pushToast(t('templatesError'), 'error')
+    }
+  }
+
+  async function exportTemplateLibrary() {
+    try {
+      await invoke('export_template_library')
+      pushToast(t('templatesExported'), 'success')
+    } catch {
+      pushToast(t('templatesError'), 'error')
+    }
+  }
+
+  async function onTemplateFilePicked(event) {
+    const file = event.target.files?.[0]
+    event.target.value = ''
+    if (!file) return
+    try {
+      const result = await invoke('import_template_library', { content: await file.text() })
+      await reloadTemplates()
+      pushToast(t('templatesImported').replace('{added}', result.added).replace('{replaced}', result.replaced), 'success')
+    } catch {
+      pushToast(t('templatesImportError'), 'error')
+    }
+  }
+
+  async function openTagManager() {
+    if (!isTauriRuntime()) {
+      pushToast(t('tagsDesktopOnly'), 'error')
+      return
+    }
+    try {
+      const index = await invoke('tag_index', { state: { projects, settings } })
+      setTagManager({ index, selected: [], mode: 'all', results: null, rename: null })
+    } catch {
+      pushToast(t('tagsError'), 'error')
+    }
+  }
+
+  async function applyTagChange(command, args) {
+    try {
+      const state = await invoke(command, { state: { projects, settings }, ...args })
+      const nextProjects = normalizeProjects(state.projects)
+      const index = await invoke('tag_index', { state: { projects: nextProjects, settings } })
+      setProjects(nextProjects)
+      setTagManager((prev) => (prev ? { ...prev, index, selected: [], results: null, rename: null } : prev))
+    } catch {
+      pushToast(t('tagsError'), 'error')
+    }
+  }
+
+  function toggleTagSelection(tag) {
+    setTagManager((prev) => {
+      if (!prev) return prev
+      const selected = prev.selected.includes(tag) ? prev.selected.filter((item) => item !== tag) : [...prev.selected, tag]
+      return { ...prev, selected, results: null }
+    })
+  }
+
+  function confirmTagRename() {
+    if (!tagManager?.rename) return
+    const { from, to } = tagManager.rename
+    if (!normalizeTags(to).length) return
+    if (from.length > 1) {
+      applyTagChange('merge_tags', { tags: from, into: to })
+    } else {
+      applyTagChange('rename_tag', { from: from[0], to })
+    }
+  }
+
+  async function runTagQuery() {
+    if (!tagManager?.selected.length) return
+    const query = tagManager.mode === 'any' ? { any: tagManager.selected } : { all: tagManager.selected }
+    try {
+      const results = await invoke('query_tags', { state: { projects, settings }, query })
+      setTagManager((prev) => (prev ? { ...prev, results } : prev))
+    } catch {
+      pushToast(t('tagsError'), 'error')
+    }
+  }
+
+  function openTaggedEntity(entity) {
+    setSelectedProjectId(entity.projectId)
+    setActivePage('projects')
+    setTagManager(null)
+  }
+
+  async function openWikiLink(target) {
+    if (!selectedProject) return
+    try {
+      const resolved = await invoke('resolve_wiki_link', { state: { projects, settings }, projectId: selectedProject.id, target })
+      setSelectedProjectId(resolved.projectId)
+      setFocusNoteId(resolved.noteId)
+    } catch {
+      pushToast(t('wikiLinkBroken').replace('{target}', target), 'error')
+    }
+  }
+
+  const clearFocusNote = useCallback(() => setFocusNoteId(null), [])
+
+  function openLinkSource(source) {
+    setSelectedProjectId(source.projectId)
+    setFocusNoteId(source.noteId)
+  }
+
+  function dismissReminder(key) {
+    setDueReminders((prev) => prev.filter((item) => item.key !== key))
+  }
+
+  async function snoozeReminder(key, minutes) {
+    try {
+      await invoke('snooze_reminder', { key, minutes })
+      dismissReminder(key)
+      pushToast(t('reminderSnoozed'), 'success')
+    } catch {
+      pushToast(t('reminderSnoozeError'), 'error')
+    }
+  }
+
+  function completeReminder(reminder) {
+    setProjects((prev) =>
+      prev.map((project) =>
+        project.id !== reminder.projectId
+          ? project
+          : { ...project, steps: updateStepTree(project.steps, reminder.stepId, { done: true }, settings.autoCompleteSteps) }),
+    )
+    dismissReminder(reminder.key)
+  }
+
+  return (
+    <div className="app-shell" data-anim={settings.animations ? 'on' : 'off'}>
+      <div className="backdrop wave-one" />
+      <div className="backdrop wave-two" />
+      <div className="noise" />
+
+      <TopBar
+        t={t}
+        askImportProject={askImportProject}
+        openQrImport={openQrImport}
+        selectedProject={selectedProject}
+        progress={progress}
+        isContextualControls={isContextualControls}
+        updateInfo={updateInfo}
+        openCreateProjectModal={openCreateProjectModal}
+        openEditProjectModal={openEditProjectModal}
+        removeSelectedProject={removeSelectedProject}
+        openCreateNoteModal={openCreateNoteModal}
+        openSettingsPage={openSettingsPage}
+        onOpenProjects={() => {
           setActivePage('projects')
         }}
         toasts={toasts}
@@ -571,6 +1664,9 @@ function App() {
           openUpdateDownload={openUpdateDownload}
           appVersion={appVersion}
           showWindowSettings={!isMobileDevice}
+          createBackup={createBackup}
+          askRestoreBackup={askRestoreBackup}
+          cleanAttachments={cleanAttachments}
         />
       ) : (
         <ProjectsPage
@@ -584,22 +1680,56 @@ function App() {
           selectedProject={selectedProject}
           openCreateProjectModal={openCreateProjectModal}
           askImportProject={askImportProject}
+          openQrImport={openQrImport}
           openEditProjectModal={openEditProjectModal}
           removeSelectedProject={removeSelectedProject}
           toggleSelectedProjectPinned={toggleSelectedProjectPinned}
           updateSelectedProjectStatus={updateSelectedProjectStatus}
           exportSelectedProject={exportSelectedProject}
+          exportSelectedProjectEncrypted={exportSelectedProjectEncrypted}
+          openExportFilterModal={openExportFilterModal}
+          openDocumentExport={openDocumentExport}
+          openProjectHistory={openProjectHistory}
+          openProjectMap={openProjectMap}
+          shareSelectedProjectQr={shareSelectedProjectQr}
           openCreateNoteModal={openCreateNoteModal}
           openEditNoteModal={openEditNoteModal}
           removeNote={removeNote}
+          askAttachToNote={askAttachToNote}
+          openNoteRevisions={openNoteRevisions}
+          removeNoteAttachment={removeNoteAttachment}
+          openNoteAttachment={openNoteAttachment}
+          saveNoteAttachment={saveNoteAttachment}
+          loadAttachmentThumbnail={loadAttachmentThumbnail}
+          onMarkdownLinkError={() => pushToast(t('linkOpenError'), 'error')}
+          openWikiLink={openWikiLink}
+          openLinkSource={openLinkSource}
+          linkReport={linkReport}
+          focusNoteId={focusNoteId}
+          clearFocusNote={clearFocusNote}
           updateProjectStep={updateProjectStep}
           moveProjectStep={moveProjectStep}
+          indentProjectStep={(stepId) => nestProjectStep(stepId, 'indent_step')}
+          outdentProjectStep={(stepId) => nestProjectStep(stepId, 'outdent_step')}
+          runningTimer={runningTimer}
+          runningTimerInfo={runningTimerInfo}
+          timerNow={timerNow}
+          toggleStepTimer={toggleStepTimer}
+          stopRunningTimer={() => runTimerCommand('stop_timer')}
+          addManualTime={addManualTime}
+          removeTimeEntry={removeTimeEntry}
+          openTimesheet={openTimesheet}
+          previewRecurrence={previewRecurrence}
+          language={settings.language}
           removeProjectStep={removeProjectStep}
           showStepCreate={showStepCreate}
           setShowStepCreate={setShowStepCreate}
           newProjectStep={newProjectStep}
           setNewProjectStep={setNewProjectStep}
           addProjectStep={addProjectStep}
+          openTagManager={openTagManager}
+          openTemplateLibrary={openTemplateLibrary}
+          openImportCenter={openImportCenter}
           isMobileDevice={isMobileDevice}
           projectsMenuOpen={projectsMenuOpen}
           setProjectsMenuOpen={setProjectsMenuOpen}
@@ -609,11 +1739,19 @@ function App() {
       <input
         ref={importFileRef}
         type="file"
-        accept="application/json,.json,.pns-project.json"
+        accept="application/json,.json,.pns-project.json,.pns-locked"
         className="hidden-file-input"
         onChange={onProjectFilePicked}
       />
 
+      <input ref={templateFileRef} type="file" accept="application/json,.json" className="hidden-file-input" onChange={onTemplateFilePicked} />
+
+      <input ref={attachmentFileRef} type="file" multiple className="hidden-file-input" onChange={onAttachmentFilesPicked} />
+
+      <input ref={importSourceFileRef} type="file" className="hidden-file-input" onChange={onImportSourcePicked} />
+
+      <input ref={backupFileRef} type="file" accept=".pns-backup,application/zip" className="hidden-file-input" onChange={onBackupFilePicked} />
+
       {createProjectOpen ? (
         <Modal title={t('newProjectModal')} icon={<FolderPlus size={17} />} closeText={t('close')} onClose={() => setCreateProjectOpen(false)}>
           <div className="modal-body">
@@ -625,6 +1763,10 @@ function App() {
               {t('descriptionField')}
               <textarea rows={3} value={projectForm.description} onChange={(event) => setProjectForm((prev) => ({ ...prev, description: event.target.value }))} />
             </label>
+            <label>
+              {t('tagsField')}
+              <input value={projectForm.tags} placeholder={t('tagsPlaceholder')} onChange={(event) => setProjectForm((prev) => ({ ...prev, tags: event.target.value }))} />
+            </label>
             {statusesEnabled && settings.projectStatuses.length > 0 ? (
               <label>
                 {t('projectStatus')}
@@ -656,6 +1798,10 @@ function App() {
               {t('descriptionField')}
               <textarea rows={3} value={projectEditForm.description} onChange={(event) => setProjectEditForm((prev) => ({ ...prev, description: event.target.value }))} />
             </label>
+            <label>
+              {t('tagsField')}
+              <input value={projectEditForm.tags} placeholder={t('tagsPlaceholder')} onChange={(event) => setProjectEditForm((prev) => ({ ...prev, tags: event.target.value }))} />
+            </label>
             {statusesEnabled && selectedProjectStatusOptions.length > 0 ? (
               <label>
                 {t('projectStatus')}
@@ -666,6 +1812,16 @@ function App() {
                 </select>
               </label>
             ) : null}
+            <div className="schedule-fields">
+              <label>
+                {t('dueAt')}
+                <input type="datetime-local" value={projectEditForm.dueAt} onChange={(event) => setProjectEditForm((prev) => ({ ...prev, dueAt: event.target.value }))} />
+              </label>
+              <label>
+                {t('remindAt')}
+                <input type="datetime-local" value={projectEditForm.remindAt} onChange={(event) => setProjectEditForm((prev) => ({ ...prev, remindAt: event.target.value }))} />
+              </label>
+            </div>
           </div>
           <div className="modal-actions">
             <button className="wide-btn" onClick={saveProjectChanges}>
@@ -686,6 +1842,11 @@ function App() {
             <label>
               {t('noteTextField')}
               <textarea rows={4} value={noteCreateForm.body} onChange={(event) => setNoteCreateForm((prev) => ({ ...prev, body: event.target.value }))} />
+              <span className="field-hint">{t('markdownHint')}</span>
+            </label>
+            <label>
+              {t('tagsField')}
+              <input value={noteCreateForm.tags} placeholder={t('tagsPlaceholder')} onChange={(event) => setNoteCreateForm((prev) => ({ ...prev, tags: event.target.value }))} />
             </label>
           </div>
           <div className="modal-actions">
@@ -707,6 +1868,11 @@ function App() {
             <label>
               {t('noteTextField')}
               <textarea rows={4} value={noteEditForm.body} onChange={(event) => setNoteEditForm((prev) => (prev ? { ...prev, body: event.target.value } : prev))} />
+              <span className="field-hint">{t('markdownHint')}</span>
+            </label>
+            <label>
+              {t('tagsField')}
+              <input value={noteEditForm.tags} placeholder={t('tagsPlaceholder')} onChange={(event) => setNoteEditForm((prev) => (prev ? { ...prev, tags: event.target.value } : prev))} />
             </label>
           </div>
           <div className="modal-actions">
@@ -717,6 +1883,668 @@ function App() {
           </div>
         </Modal>
       ) : null}
+
+      {exportFilterForm && selectedProject ? (
+        <Modal title={t('filteredExportModal')} icon={<SlidersHorizontal size={17} />} closeText={t('close')} onClose={() => setExportFilterForm(null)}>
+          <div className="modal-body">
+            {(selectedProject.notes || []).length > 0 ? (
+              <div className="export-filter-notes">
+                <span>{t('filteredExportNotes')}</span>
+                {(selectedProject.notes || []).map((note) => (
+                  <label key={note.id} className="export-filter-option">
+                    <input type="checkbox" checked={exportFilterForm.noteIds.includes(note.id)} onChange={() => toggleExportFilterNote(note.id)} />
+                    <span className="wrap-anywhere">{note.title}</span>
+                  </label>
+                ))}
+              </div>
+            ) : null}
+            <label className="export-filter-option">
+              <input
+                type="checkbox"
+                checked={exportFilterForm.openStepsOnly}
+                onChange={(event) => setExportFilterForm((prev) => (prev ? { ...prev, openStepsOnly: event.target.checked } : prev))}
+              />
+              <span>{t('filteredExportOpenSteps')}</span>
+            </label>
+            <label className="export-filter-option">
+              <input
+                type="checkbox"
+                checked={exportFilterForm.excludeDescriptions}
+                onChange={(event) => setExportFilterForm((prev) => (prev ? { ...prev, excludeDescriptions: event.target.checked } : prev))}
+              />
+              <span>{t('filteredExportNoDescription')}</span>
+            </label>
+            <label className="export-filter-option">
+              <input
+                type="checkbox"
+                checked={exportFilterForm.stripIds}
+                onChange={(event) => setExportFilterForm((prev) => (prev ? { ...prev, stripIds: event.target.checked } : prev))}
+              />
+              <span>{t('filteredExportTemplate')}</span>
+            </label>
+          </div>
+          <div className="modal-actions">
+            <button className="wide-btn" onClick={exportSelectedProjectFiltered}>
+              <SlidersHorizontal size={16} />
+              <span>{t('exportProject')}</span>
+            </button>
+          </div>
+        </Modal>
+      ) : null}
+
+      {documentExport && selectedProject ? (
+        <Modal title={t('documentExportModal')} icon={<FileOutput size={17} />} closeText={t('close')} onClose={() => setDocumentExport(null)}>
+          <div className="modal-body">
+            <label className="export-filter-option">
+              <input
+                type="checkbox"
+                checked={documentExport.workspace}
+                onChange={(event) => setDocumentExport((prev) => (prev ? { ...prev, workspace: event.target.checked } : prev))}
+              />
+              <span>{t('documentExportWorkspace')}</span>
+            </label>
+            <div className="document-export-list">
+              {DOCUMENT_EXPORTS.map((format) => {
+                const FormatIcon = format.icon
+                return (
+                  <button
+                    key={format.command}
+                    className="mode-btn"
+                    onClick={() => exportDocument(format)}
+                    disabled={documentExport.workspace && !format.workspace}
+                  >
+                    <FormatIcon size={15} />
+                    <span>{t(format.label)}</span>
+                  </button>
+                )
+              })}
+            </div>
+            <p className="qr-caption">{t('documentExportHint')}</p>
+          </div>
+        </Modal>
+      ) : null}
+
+      {importCenterOpen ? (
+        <Modal title={t('importCenterModal')} icon={<FileInput size={17} />} closeText={t('close')} onClose={() => setImportCenterOpen(false)}>
+          <div className="modal-body">
+            <div className="document-export-list">
+              {IMPORT_SOURCES.map((source) => {
+                const SourceIcon = source.icon
+                return (
+                  <button key={source.id} className="mode-btn" onClick={() => askImportSource(source)}>
+                    <SourceIcon size={15} />
+                    <span>{t(source.label)}</span>
+                  </button>
+                )
+              })}
+            </div>
+          </div>
+        </Modal>
+      ) : null}
+
+      {importReport ? (
+        <Modal title={t('importReportModal')} icon={<FileInput size={17} />} closeText={t('close')} onClose={() => setImportReport(null)}>
+          <div className="modal-body">
+            <p>{importReport.summary}</p>
+            {importReport.details.length ? (
+              <>
+                <span>{t('importReportDetails')}</span>
+                <ul className="import-report-list">
+                  {importReport.details.map((detail, index) => (
+                    <li key={index} className="wrap-anywhere">{detail}</li>
+                  ))}
+                </ul>
+              </>
+            ) : null}
+          </div>
+          <div className="modal-actions">
+            <button className="wide-btn" onClick={() => setImportReport(null)}>
+              <Check size={16} />
+              <span>{t('close')}</span>
+            </button>
+          </div>
+        </Modal>
+      ) : null}
+
+      {backupRestore ? (
+        <Modal title={t('backupRestoreModal')} icon={<ArchiveRestore size={17} />} closeText={t('close')} onClose={() => setBackupRestore(null)}>
+          <div className="modal-body">
+            <p className="wrap-anywhere">{backupRestore.name}</p>
+            <p className="qr-caption">{t('backupRestoreHint')}</p>
+          </div>
+          <div className="modal-actions template-fill-actions">
+            <button className="mode-btn" onClick={() => restoreBackup('merge')}>
+              <ArchiveRestore size={15} />
+              <span>{t('backupMerge')}</span>
+            </button>
+            <button className="mode-btn danger" onClick={() => restoreBackup('replace')}>
+              <ArchiveRestore size={15} />
+              <span>{t('backupReplace')}</span>
+            </button>
+          </div>
+        </Modal>
+      ) : null}
+
+      {qrShare ? (
+        <Modal title={t('qrShareModal')} icon={<QrCode size={17} />} closeText={t('close')} onClose={() => setQrShare(null)}>
+          <div className="modal-body qr-share">
+            <img
+              className="qr-image"
+              src={`data:${qrShare.parts[qrShare.index].mime};base64,${qrShare.parts[qrShare.index].image}`}
+              alt={`${qrShare.index + 1} / ${qrShare.parts.length}`}
+            />
+            <p className="qr-caption">{t('qrShareHint')}</p>
+          </div>
+          {qrShare.parts.length > 1 ? (
+            <div className="modal-actions qr-pager">
+              <button className="mode-btn" onClick={() => setQrShare((prev) => ({ ...prev, index: prev.index - 1 }))} disabled={qrShare.index === 0}>
+                <ChevronLeft size={16} />
+              </button>
+              <span>
+                {qrShare.index + 1} / {qrShare.parts.length}
+              </span>
+              <button
+                className="mode-btn"
+                onClick={() => setQrShare((prev) => ({ ...prev, index: prev.index + 1 }))}
+                disabled={qrShare.index === qrShare.parts.length - 1}
+              >
+                <ChevronRight size={16} />
+              </button>
+            </div>
+          ) : null}
+        </Modal>
+      ) : null}
+
+      {qrImportText !== null ? (
+        <Modal title={t('qrImportModal')} icon={<ScanLine size={17} />} closeText={t('close')} onClose={() => setQrImportText(null)}>
+          <div className="modal-body">
+            <label>
+              {t('qrImportField')}
+              <textarea rows={6} value={qrImportText} onChange={(event) => setQrImportText(event.target.value)} />
+            </label>
+          </div>
+          <div className="modal-actions">
+            <button className="wide-btn" onClick={importProjectFromQr}>
+              <ScanLine size={16} />
+              <span>{t('importProject')}</span>
+            </button>
+          </div>
+        </Modal>
+      ) : null}
+
+      {dueReminders.length > 0 ? (
+        <Modal title={t('remindersModal')} icon={<AlarmClock size={17} />} closeText={t('close')} onClose={() => setDueReminders([])}>
+          <div className="modal-body reminder-list">
+            {dueReminders.map((reminder) => (
+              <div key={reminder.key} className="reminder-item">
+                <div className="reminder-text">
+                  <strong className="wrap-anywhere">{reminder.stepId ? reminder.text : reminder.project}</strong>
+                  <span className="wrap-anywhere">
+                    {reminder.stepId ? reminder.project : t('projectDeadline')}
+                    {reminder.dueAt ? ` • ${t('dueAt')}: ${reminder.dueAt}` : ''}
+                    {reminder.missed ? ` • ${t('reminderMissed')}` : ''}
+                  </span>
+                </div>
+                <div className="reminder-actions">
+                  <button className="mode-btn" onClick={() => snoozeReminder(reminder.key, 10)}>{t('snooze10m')}</button>
+                  <button className="mode-btn" onClick={() => snoozeReminder(reminder.key, 60)}>{t('snooze1h')}</button>
+                  <button className="mode-btn" onClick={() => snoozeReminder(reminder.key, 24 * 60)}>{t('snoozeTomorrow')}</button>
+                  {reminder.stepId ? (
+                    <button className="mode-btn" onClick={() => completeReminder(reminder)}>
+                      <Check size={15} />
+                      <span>{t('reminderDone')}</span>
+                    </button>
+                  ) : null}
+                  <button className="mini-btn" onClick={() => dismissReminder(reminder.key)} title={t('reminderDismiss')}>
+                    <X size={14} />
+                  </button>
+                </div>
+              </div>
+            ))}
+          </div>
+        </Modal>
+      ) : null}
+
+      {tagManager ? (
+        <Modal title={t('tagsModal')} icon={<Tags size={17} />} closeText={t('close')} onClose={() => setTagManager(null)}>
+          <div className="modal-body tag-manager">
+            {tagManager.index.length === 0 ? <p className="qr-caption">{t('tagsEmpty')}</p> : null}
+            {tagManager.index.map((entry) => (
+              <div key={entry.tag} className="tag-index-row">
+                <label className="export-filter-option">
+                  <input type="checkbox" checked={tagManager.selected.includes(entry.tag)} onChange={() => toggleTagSelection(entry.tag)} />
+                  <span className="tag-chip">#{entry.tag}</span>
+                  <span className="tag-count">
+                    {entry.total} • {t('tagCounts').replace('{projects}', entry.projects).replace('{notes}', entry.notes).replace('{steps}', entry.steps)}
+                  </span>
+                </label>
+                <div className="reminder-actions">
+                  <button className="mini-btn" onClick={() => setTagManager((prev) => ({ ...prev, rename: { from: [entry.tag], to: entry.tag } }))} title={t('renameTag')}>
+                    <Pencil size={14} />
+                  </button>
+                  <button className="mini-btn danger" onClick={() => applyTagChange('delete_tag', { tag: entry.tag })} title={t('deleteTag')}>
+                    <Trash2 size={14} />
+                  </button>
+                </div>
+              </div>
+            ))}
+            {tagManager.rename ? (
+              <label>
+                {tagManager.rename.from.length > 1 ? t('mergeTagsInto') : t('renameTag')}
+                <input
+                  autoFocus
+                  value={tagManager.rename.to}
+                  onChange={(event) => setTagManager((prev) => ({ ...prev, rename: { ...prev.rename, to: event.target.value } }))}
+                  onKeyDown={(event) => {
+                    if (event.key === 'Enter') confirmTagRename()
+                  }}
+                />
+              </label>
+            ) : null}
+            {tagManager.results ? (
+              <div className="tag-results">
+                {tagManager.results.length === 0 ? <p className="qr-caption">{t('tagQueryEmpty')}</p> : null}
+                {tagManager.results.map((entity) => (
+                  <button key={`${entity.kind}:${entity.projectId}:${entity.id}`} className="tag-result" onClick={() => openTaggedEntity(entity)}>
+                    <strong className="wrap-anywhere">{entity.title}</strong>
+                    <span className="wrap-anywhere">
+                      {t(`tagKind_${entity.kind}`)}
+                      {entity.kind !== 'project' ? ` • ${entity.project}` : ''}
+                    </span>
+                  </button>
+                ))}
+              </div>
+            ) : null}
+          </div>
+          <div className="modal-actions tag-actions">
+            {tagManager.rename ? (
+              <button className="wide-btn" onClick={confirmTagRename}>
+                <Check size={16} />
+                <span>{t('apply')}</span>
+              </button>
+            ) : (
+              <>
+                <button className="mode-btn" onClick={() => setTagManager((prev) => ({ ...prev, mode: prev.mode === 'all' ? 'any' : 'all', results: null }))}>
+                  <span>{tagManager.mode === 'all' ? t('tagMatchAll') : t('tagMatchAny')}</span>
+                </button>
+                <button className="mode-btn" onClick={() => setTagManager((prev) => ({ ...prev, rename: { from: prev.selected, to: prev.selected[0] } }))} disabled={tagManager.selected.length < 2}>
+                  <span>{t('mergeTags')}</span>
+                </button>
+                <button className="wide-btn" onClick={runTagQuery} disabled={!tagManager.selected.length}>
+                  <Search size={16} />
+                  <span>{t('findTagged')}</span>
+                </button>
+              </>
+            )}
+          </div>
+        </Modal>
+      ) : null}
+
+      {linkRewritePrompt ? (
+        <Modal title={t('linksRewriteModal')} icon={<Link2 size={17} />} closeText={t('close')} onClose={() => setLinkRewritePrompt(null)}>
+          <div className="modal-body">
+            <p className="qr-caption">{t('linksRewritePrompt').replace('{count}', linkRewritePrompt.count)}</p>
+          </div>
+          <div className="modal-actions tag-actions">
+            <button className="mode-btn" onClick={() => renameNoteWithLinks(false)}>
+              <span>{t('renameOnly')}</span>
+            </button>
+            <button className="wide-btn" onClick={() => renameNoteWithLinks(true)}>
+              <Check size={16} />
+              <span>{t('renameAndRewrite')}</span>
+            </button>
+          </div>
+        </Modal>
+      ) : null}
+
+      {noteRevisions ? (
+        <Modal title={t('noteRevisionsModal')} icon={<History size={17} />} closeText={t('close')} onClose={() => setNoteRevisions(null)}>
+          <div className="modal-body revisions-body">
+            {noteRevisions.list.length === 0 ? (
+              <p className="qr-caption">{t('noteRevisionsEmpty')}</p>
+            ) : (
+              <>
+                <div className="revision-list">
+                  {noteRevisions.list.map((revision) => (
+                    <button
+                      key={revision.id}
+                      className={`revision-item ${noteRevisions.selected === revision.id ? 'active' : ''}`}
+                      onClick={() => selectRevision(revision.id)}
+                    >
+                      <strong>{new Date(revision.updatedAt).toLocaleString(settings.language)}</strong>
+                      <span className="wrap-anywhere">
+                        {revision.title} • {t('revisionChars').replace('{count}', revision.chars)}
+                      </span>
+                    </button>
+                  ))}
+                </div>
+                <div className="revision-view">
+                  <div className="revision-controls">
+                    <label>
+                      {t('revisionCompareWith')}
+                      <select
+                        value={noteRevisions.compareTo || ''}
+                        onChange={(event) => updateRevisionView({ compareTo: Number(event.target.value) || null })}
+                      >
+                        <option value="">{t('revisionCompareNone')}</option>
+                        {noteRevisions.list
+                          .filter((revision) => revision.id !== noteRevisions.selected)
+                          .map((revision) => (
+                            <option key={revision.id} value={revision.id}>
+                              {new Date(revision.updatedAt).toLocaleString(settings.language)}
+                            </option>
+                          ))}
+                      </select>
+                    </label>
+                    <button className={`mode-btn ${noteRevisions.mode === 'line' ? 'active' : ''}`} onClick={() => updateRevisionView({ mode: 'line' })}>
+                      <span>{t('revisionDiffLines')}</span>
+                    </button>
+                    <button className={`mode-btn ${noteRevisions.mode === 'word' ? 'active' : ''}`} onClick={() => updateRevisionView({ mode: 'word' })}>
+                      <span>{t('revisionDiffWords')}</span>
+                    </button>
+                  </div>
+                  <RevisionDiff t={t} view={noteRevisions} />
+                </div>
+              </>
+            )}
+          </div>
+          <div className="modal-actions">
+            <button className="wide-btn" onClick={restoreNoteRevision} disabled={!noteRevisions.selected}>
+              <History size={16} />
+              <span>{t('revisionRestore')}</span>
+            </button>
+          </div>
+        </Modal>
+      ) : null}
+
+      {projectMap ? (
+        <Modal title={t('projectMapModal')} icon={<Network size={17} />} closeText={t('close')} onClose={() => setProjectMap(null)}>
+          <div className="modal-body project-map">
+            <img className="project-map-image" src={`data:image/svg+xml;charset=utf-8,${encodeURIComponent(projectMap.svg)}`} alt={t('projectMapModal')} />
+          </div>
+          <div className="modal-actions">
+            <button className="wide-btn" onClick={exportProjectMap}>
+              <Download size={16} />
+              <span>{t('projectMapSave')}</span>
+            </button>
+          </div>
+        </Modal>
+      ) : null}
+
+      {projectHistory && selectedProject ? (
+        <Modal title={t('projectHistoryModal')} icon={<GitCommitHorizontal size={17} />} closeText={t('close')} onClose={() => setProjectHistory(null)}>
+          <div className="modal-body">
+            <label>
+              {t('projectHistoryScope')}
+              <select value={projectHistory.noteId === null ? '' : String(projectHistory.noteId)} onChange={(event) => changeProjectHistoryNote(event.target.value)}>
+                <option value="">{t('projectHistoryWholeProject')}</option>
+                {(selectedProject.notes || []).map((note) => (
+                  <option key={note.id} value={String(note.id)}>{note.title}</option>
+                ))}
+              </select>
+            </label>
+            {projectHistory.entries.length === 0 ? (
+              <p className="qr-caption">{t('projectHistoryEmpty')}</p>
+            ) : (
+              <div className="revisions-body">
+                <div className="revision-list">
+                  {projectHistory.entries.map((entry) => (
+                    <button
+                      key={entry.commit}
+                      className={`revision-item ${projectHistory.selected === entry.commit ? 'active' : ''}`}
+                      onClick={() => selectHistoryEntry(projectHistory, entry.commit)}
+                      title={entry.message}
+                    >
+                      <strong>{new Date(entry.time).toLocaleString(settings.language)}</strong>
+                      <span className="wrap-anywhere">{entry.shortId} • {entry.summary}</span>
+                    </button>
+                  ))}
+                </div>
+                <div className="revision-view">
+                  {projectHistory.diff ? (
+                    <>
+                      <p className="revision-stats">{t('projectHistoryCompare')}</p>
+                      {projectHistory.diff.files.map((file) => (
+                        <p key={file.path} className="revision-stats wrap-anywhere">
+                          {file.path}: +{file.additions} −{file.deletions}
+                        </p>
+                      ))}
+                      <pre className="revision-text history-patch">
+                        {projectHistory.diff.patch.split('\n').map((line, index) => (
+                          <span
+                            key={index}
+                            className={/^\+(?!\+\+)/.test(line) ? 'diff-insert' : /^-(?!--)/.test(line) ? 'diff-delete' : ''}
+                          >
+                            {line}
+                          </span>
+                        ))}
+                      </pre>
+                    </>
+                  ) : null}
+                </div>
+              </div>
+            )}
+          </div>
+          <div className="modal-actions">
+            <button className="wide-btn" onClick={restoreProjectHistory} disabled={!projectHistory.selected}>
+              <History size={16} />
+              <span>{t('revisionRestore')}</span>
+            </button>
+          </div>
+        </Modal>
+      ) : null}
+
+      {timesheet ? (
+        <Modal title={t('timesheetModal')} icon={<Timer size={17} />} closeText={t('close')} onClose={() => setTimesheet(null)}>
+          <div className="modal-body timesheet-body">
+            <div className="timesheet-range">
+              <label>
+                {t('timesheetFrom')}
+                <input type="date" value={timesheet.from} onChange={(event) => updateTimesheet({ from: event.target.value })} />
+              </label>
+              <label>
+                {t('timesheetTo')}
+                <input type="date" value={timesheet.to} onChange={(event) => updateTimesheet({ to: event.target.value })} />
+              </label>
+            </div>
+            <div className="timesheet-controls">
+              {timesheet.projectId ? (
+                <>
+                  <button className={`mode-btn ${timesheet.scope === 'project' ? 'active' : ''}`} onClick={() => updateTimesheet({ scope: 'project' })}>
+                    <span>{t('timesheetScopeProject')}</span>
+                  </button>
+                  <button className={`mode-btn ${timesheet.scope === 'all' ? 'active' : ''}`} onClick={() => updateTimesheet({ scope: 'all' })}>
+                    <span>{t('timesheetScopeAll')}</span>
+                  </button>
+                </>
+              ) : null}
+              {['day', 'week', 'month'].map((period) => (
+                <button key={period} className={`mode-btn ${timesheet.period === period ? 'active' : ''}`} onClick={() => updateTimesheet({ period })}>
+                  <span>{t(`timesheetPeriod_${period}`)}</span>
+                </button>
+              ))}
+            </div>
+            {timesheet.report ? (
+              timesheet.report.totalSeconds === 0 ? (
+                <p className="qr-caption">{t('timesheetEmpty')}</p>
+              ) : (
+                <div className="timesheet-report">
+                  <p className="timesheet-total">
+                    {t('timesheetTotal')}: <strong>{formatDuration(timesheet.report.totalSeconds)}</strong>
+                  </p>
+                  <div className="timesheet-section">
+                    {timesheet.report.projects.map((project) => (
+                      <div key={project.projectId ?? project.project} className="timesheet-group">
+                        <div className="timesheet-line">
+                          <strong className="wrap-anywhere">{project.project}</strong>
+                          <span>{formatDuration(project.seconds)}</span>
+                        </div>
+                        {project.steps.map((step) => (
+                          <div key={step.stepId ?? step.text} className="timesheet-line nested">
+                            <span className="wrap-anywhere">{step.text}</span>
+                            <span>{formatDuration(step.seconds)}</span>
+                          </div>
+                        ))}
+                      </div>
+                    ))}
+                  </div>
+                  <div className="timesheet-section">
+                    {timesheet.report.periods.map((entry) => (
+                      <div key={entry.period} className="timesheet-line">
+                        <span>{entry.period}</span>
+                        <span>{formatDuration(entry.seconds)}</span>
+                      </div>
+                    ))}
+                  </div>
+                </div>
+              )
+            ) : null}
+          </div>
+          <div className="modal-actions">
+            <button className="wide-btn" onClick={exportTimesheet} disabled={!timesheet.report?.totalSeconds}>
+              <Download size={16} />
+              <span>{t('exportTimesheet')}</span>
+            </button>
+          </div>
+        </Modal>
+      ) : null}
+
+      {templateLibrary ? (
+        <Modal title={t('templatesModal')} icon={<LayoutTemplate size={17} />} closeText={t('close')} onClose={() => setTemplateLibrary(null)}>
+          <div className="modal-body templates-body">
+            {templateLibrary.filling ? (
+              <div className="template-fill">
+                <p className="template-fill-title">{templateLibrary.filling.name}</p>
+                {Object.entries(templateLibrary.filling.values).map(([name, value]) => (
+                  <label key={name}>
+                    {`{{${name}}}`}
+                    <input
+                      value={value}
+                      onChange={(event) =>
+                        setTemplateLibrary((prev) =>
+                          prev?.filling
+                            ? { ...prev, filling: { ...prev.filling, values: { ...prev.filling.values, [name]: event.target.value } } }
+                            : prev,
+                        )
+                      }
+                    />
+                  </label>
+                ))}
+                <p className="qr-caption">{t('templateBuiltIns')}</p>
+                <div className="template-fill-actions">
+                  <button className="mode-btn" onClick={() => setTemplateLibrary((prev) => (prev ? { ...prev, filling: null } : prev))}>
+                    <ChevronLeft size={15} />
+                    <span>{t('templateBack')}</span>
+                  </button>
+                  <button className="mode-btn active" onClick={() => createProjectFromTemplate(templateLibrary.filling.id, templateLibrary.filling.values)}>
+                    <FolderPlus size={15} />
+                    <span>{t('templateCreateProject')}</span>
+                  </button>
+                </div>
+              </div>
+            ) : (
+              <>
+                {selectedProject ? (
+                  <div className="template-save">
+                    <label>
+                      {t('templateName')}
+                      <input
+                        value={templateLibrary.saveName}
+                        onChange={(event) => setTemplateLibrary((prev) => (prev ? { ...prev, saveName: event.target.value } : prev))}
+                        onKeyDown={(event) => {
+                          if (event.key === 'Enter') saveProjectAsTemplate()
+                        }}
+                      />
+                    </label>
+                    <button className="mode-btn" onClick={saveProjectAsTemplate} disabled={!templateLibrary.saveName.trim()}>
+                      <Save size={15} />
+                      <span>{t('templateSaveProject')}</span>
+                    </button>
+                  </div>
+                ) : null}
+                <p className="qr-caption">{t('templatePlaceholderHint')}</p>
+                {templateLibrary.list.length ? (
+                  <div className="template-list">
+                    {templateLibrary.list.map((template) => (
+                      <div key={template.id} className="template-item">
+                        <div className="template-info">
+                          <strong className="wrap-anywhere">{template.name}</strong>
+                          <span className="template-meta">
+                            {t('templateCounts').replace('{notes}', template.notes).replace('{steps}', template.steps)}
+                            {template.placeholders.length ? ` · ${template.placeholders.map((name) => `{{${name}}}`).join(', ')}` : ''}
+                          </span>
+                        </div>
+                        <button className="mini-btn" onClick={() => startProjectFromTemplate(template)}>
+                          <FolderPlus size={14} />
+                          <span>{t('templateUse')}</span>
+                        </button>
+                        <button className="mini-btn danger" onClick={() => deleteTemplate(template.id)} title={t('deleteTemplate')}>
+                          <Trash2 size={14} />
+                        </button>
+                      </div>
+                    ))}
+                  </div>
+                ) : (
+                  <p className="qr-caption">{t('templatesEmpty')}</p>
+                )}
+              </>
+            )}
+          </div>
+          <div className="modal-actions">
+            <button className="wide-btn" onClick={() => templateFileRef.current?.click()}>
+              <Upload size={16} />
+              <span>{t('importTemplates')}</span>
+            </button>
+            <button className="wide-btn" onClick={exportTemplateLibrary} disabled={!templateLibrary.list.length}>
+              <Download size={16} />
+              <span>{t('exportTemplates')}</span>
+            </button>
+          </div>
+        </Modal>
+      ) : null}
+
+      {passwordPrompt ? (
+        <Modal
+          title={passwordPrompt.mode === 'export' ? t('encryptedExportModal') : t('encryptedImportModal')}
+          icon={<Lock size={17} />}
+          closeText={t('close')}
+          onClose={() => setPasswordPrompt(null)}
+        >
+          <div className="modal-body">
+            <label>
+              {t('passwordField')}
+              <input
+                type="password"
+                autoFocus
+                value={passwordPrompt.password}
+                onChange={(event) => setPasswordPrompt((prev) => (prev ? { ...prev, password: event.target.value } : prev))}
+                onKeyDown={(event) => {
+                  if (event.key === 'Enter') confirmPasswordPrompt()
+                }}
+              />
+            </label>
+            {passwordPrompt.mode === 'export' ? (
+              <label>
+                {t('passwordConfirmField')}
+                <input
+                  type="password"
+                  value={passwordPrompt.confirm}
+                  onChange={(event) => setPasswordPrompt((prev) => (prev ? { ...prev, confirm: event.target.value } : prev))}
+                  onKeyDown={(event) => {
+                    if (event.key === 'Enter') confirmPasswordPrompt()
+                  }}
+                />
+              </label>
+            ) : null}
+          </div>
+          <div className="modal-actions">
+            <button className="wide-btn" onClick={confirmPasswordPrompt}>
+              <Lock size={16} />
+              <span>{passwordPrompt.mode === 'export' ? t('encryptAndExport') : t('decryptAndImport')}</span>
+            </button>
+          </div>
+        </Modal>
+      ) : null}
     </div>
   )
 }
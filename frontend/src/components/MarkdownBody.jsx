@@ -0,0 +1,61 @@
+import { useEffect, useState } from 'react'
+import { invoke } from '@tauri-apps/api/core'
+import { isTauriRuntime } from '../utils/state'
+
+let highlightStyleRequested = false
+
+function ensureHighlightStyle() {
+  if (highlightStyleRequested) return
+  highlightStyleRequested = true
+  invoke('markdown_css')
+    .then((css) => {
+      const style = document.createElement('style')
+      style.dataset.markdownHighlight = 'true'
+      style.textContent = css
+      document.head.appendChild(style)
+    })
+    .catch(() => {
+      highlightStyleRequested = false
+    })
+}
+
+function MarkdownBody({ body, onLinkError, onWikiLink }) {
+  const [rendered, setRendered] = useState({ body: null, html: '' })
+  const desktop = isTauriRuntime()
+
+  useEffect(() => {
+    if (!desktop || !body) return undefined
+    ensureHighlightStyle()
+    let active = true
+    invoke('render_markdown', { body })
+      .then((html) => {
+        if (active) setRendered({ body, html })
+      })
+      .catch(() => {
+        if (active) setRendered({ body: null, html: '' })
+      })
+    return () => {
+      active = false
+    }
+  }, [body, desktop])
+
+  function handleClick(event) {
+    const link = event.target.closest('a')
+    if (!link) return
+    event.preventDefault()
+    const url = link.getAttribute('href')
+    if (!url) return
+    if (url.startsWith('#note:')) {
+      onWikiLink?.(decodeURIComponent(url.slice('#note:'.length)))
+      return
+    }
+    invoke('open_link', { url }).catch(() => onLinkError?.())
+  }
+
+  if (!body) return null
+  if (!desktop || rendered.body !== body) return <p className="note-body wrap-anywhere">{body}</p>
+
+  return <div className="note-body markdown-body wrap-anywhere" onClick={handleClick} dangerouslySetInnerHTML={{ __html: rendered.html }} />
+}
+
+export default MarkdownBody
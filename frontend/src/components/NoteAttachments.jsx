@@ -0,0 +1,63 @@
+import { useEffect, useState } from 'react'
+import { Download, ExternalLink, FileText, Trash2 } from 'lucide-react'
+
+function formatSize(size) {
+  if (size >= 1024 * 1024) return `${(size / (1024 * 1024)).toFixed(1)} MB`
+  if (size >= 1024) return `${Math.round(size / 1024)} KB`
+  return `${size} B`
+}
+
+function AttachmentPreview({ attachment, loadThumbnail }) {
+  const [thumbnail, setThumbnail] = useState(null)
+  const isImage = attachment.mime.startsWith('image/')
+
+  useEffect(() => {
+    if (!isImage) return undefined
+    let active = true
+    loadThumbnail(attachment.hash).then((value) => {
+      if (active) setThumbnail(value)
+    })
+    return () => {
+      active = false
+    }
+  }, [attachment.hash, isImage, loadThumbnail])
+
+  if (thumbnail) return <img className="attachment-thumb" src={thumbnail} alt={attachment.name} />
+  return (
+    <span className="attachment-thumb attachment-icon">
+      <FileText size={18} />
+    </span>
+  )
+}
+
+function NoteAttachments({ t, note, removeAttachment, openAttachment, saveAttachment, loadThumbnail }) {
+  const attachments = note.attachments || []
+  if (!attachments.length) return null
+
+  return (
+    <div className="attachment-list">
+      {attachments.map((attachment) => (
+        <div key={attachment.hash} className="attachment-item">
+          <button className="attachment-open" onClick={() => openAttachment(attachment)} title={t('openAttachment')}>
+            <AttachmentPreview attachment={attachment} loadThumbnail={loadThumbnail} />
+            <span className="attachment-name wrap-anywhere">{attachment.name}</span>
+            <span className="attachment-size">{formatSize(attachment.size)}</span>
+          </button>
+          <div className="attachment-actions">
+            <button className="mini-btn" onClick={() => openAttachment(attachment)} title={t('openAttachment')}>
+              <ExternalLink size={14} />
+            </button>
+            <button className="mini-btn" onClick={() => saveAttachment(attachment)} title={t('saveAttachment')}>
+              <Download size={14} />
+            </button>
+            <button className="mini-btn danger" onClick={() => removeAttachment(note.id, attachment.hash)} title={t('removeAttachment')}>
+              <Trash2 size={14} />
+            </button>
+          </div>
+        </div>
+      ))}
+    </div>
+  )
+}
+
+export default NoteAttachments
@@ -1,22 +1,56 @@
+import { useEffect, useState } from 'react'
 import {
+  AlarmClock,
   ChevronLeft,
   Download,
+  FileInput,
+  FileOutput,
   FilePenLine,
   FilePlus2,
   FolderCog,
   FolderOpen,
   FolderPlus,
+  GitCommitHorizontal,
+  History,
+  IndentDecrease,
+  IndentIncrease,
+  LayoutTemplate,
   ListTodo,
+  Link2,
+  Lock,
   Menu,
+  Network,
   NotebookText,
+  Paperclip,
   Pencil,
   Pin,
   PinOff,
+  Play,
   Plus,
+  Repeat,
+  QrCode,
+  ScanLine,
+  SlidersHorizontal,
+  Square,
+  Tags,
+  Timer,
   Trash2,
   Upload,
 } from 'lucide-react'
 import IconButton from './IconButton'
+import MarkdownBody from './MarkdownBody'
+import NoteAttachments from './NoteAttachments'
+import {
+  WEEKDAY_CODES,
+  countSteps,
+  findStep,
+  formatDuration,
+  recurrencePreset,
+  recurrenceRule,
+  stepProgress,
+  trackedSeconds,
+} from '../utils/steps'
+import { normalizeTags } from '../utils/state'
 
 function ProjectsPage({
   t,
@@ -33,27 +67,318 @@ function ProjectsPage({
   toggleSelectedProjectPinned,
   updateSelectedProjectStatus,
   exportSelectedProject,
+  exportSelectedProjectEncrypted,
+  openExportFilterModal,
+  openDocumentExport,
+  openProjectHistory,
+  openProjectMap,
+  shareSelectedProjectQr,
   askImportProject,
+  openQrImport,
   openCreateNoteModal,
   openEditNoteModal,
   removeNote,
+  askAttachToNote,
+  openNoteRevisions,
+  removeNoteAttachment,
+  openNoteAttachment,
+  saveNoteAttachment,
+  loadAttachmentThumbnail,
+  onMarkdownLinkError,
+  openWikiLink,
+  openLinkSource,
+  linkReport,
+  focusNoteId,
+  clearFocusNote,
   updateProjectStep,
   moveProjectStep,
+  indentProjectStep,
+  outdentProjectStep,
   removeProjectStep,
   showStepCreate,
   setShowStepCreate,
   newProjectStep,
   setNewProjectStep,
   addProjectStep,
+  openTagManager,
+  openTemplateLibrary,
+  openImportCenter,
+  runningTimer,
+  runningTimerInfo,
+  timerNow,
+  toggleStepTimer,
+  stopRunningTimer,
+  addManualTime,
+  removeTimeEntry,
+  openTimesheet,
+  previewRecurrence,
+  language,
   isMobileDevice,
   projectsMenuOpen,
   setProjectsMenuOpen,
 }) {
+  const [scheduleStepId, setScheduleStepId] = useState(null)
+  const [manualTime, setManualTime] = useState({ startedAt: '', minutes: '', note: '' })
+  const [customRecurrence, setCustomRecurrence] = useState(false)
+  const [recurrencePreview, setRecurrencePreview] = useState({ dates: [], error: false })
+  const scheduledStep = selectedProject && scheduleStepId ? findStep(selectedProject.steps, scheduleStepId) : null
+  const scheduledRule = scheduledStep?.recurrence || ''
+  const scheduledDue = scheduledStep?.dueAt || ''
+
+  useEffect(() => {
+    if (!scheduledRule.trim()) {
+      setRecurrencePreview({ dates: [], error: false })
+      return undefined
+    }
+    let active = true
+    previewRecurrence(scheduledRule, scheduledDue)
+      .then((dates) => {
+        if (active) setRecurrencePreview({ dates, error: false })
+      })
+      .catch(() => {
+        if (active) setRecurrencePreview({ dates: [], error: true })
+      })
+    return () => {
+      active = false
+    }
+  }, [scheduledRule, scheduledDue, previewRecurrence])
+
+  useEffect(() => {
+    if (!focusNoteId || !selectedProject?.notes.some((note) => note.id === focusNoteId)) return undefined
+    document.getElementById(`note-${focusNoteId}`)?.scrollIntoView({ behavior: 'smooth', block: 'center' })
+    const focusTimer = setTimeout(clearFocusNote, 1600)
+    return () => clearTimeout(focusTimer)
+  }, [focusNoteId, selectedProject, clearFocusNote])
+
+  function noteLinks(note) {
+    const backlinks = linkReport.backlinks.find((entry) => entry.projectId === selectedProject.id && entry.noteId === note.id)
+    const broken = linkReport.broken.filter((entry) => entry.source.projectId === selectedProject.id && entry.source.noteId === note.id)
+    if (!backlinks && !broken.length) return null
+    return (
+      <div className="note-links">
+        {backlinks ? (
+          <div className="note-links-row">
+            <Link2 size={13} />
+            <span>{t('backlinks')}:</span>
+            {backlinks.sources.map((source) => (
+              <button key={`${source.projectId}:${source.noteId}`} className="link-chip wrap-anywhere" onClick={() => openLinkSource(source)}>
+                {source.projectId === selectedProject.id ? source.note : `${source.project}/${source.note}`}
+              </button>
+            ))}
+          </div>
+        ) : null}
+        {broken.length ? (
+          <div className="note-links-row broken">
+            <span>{t('brokenLinks')}:</span>
+            {broken.map((entry, index) => (
+              <span key={`${entry.target}:${index}`} className="link-chip broken wrap-anywhere" title={t(`brokenLink_${entry.reason}`)}>
+                [[{entry.target}]]
+              </span>
+            ))}
+          </div>
+        ) : null}
+      </div>
+    )
+  }
+
   function handleSelectProject(projectId) {
     setSelectedProjectId(projectId)
     if (isMobileDevice) setProjectsMenuOpen(false)
   }
 
+  function renderTags(tags) {
+    if (!tags?.length) return null
+    return (
+      <div className="tag-list">
+        {tags.map((tag) => (
+          <span key={tag} className="tag-chip wrap-anywhere">#{tag}</span>
+        ))}
+      </div>
+    )
+  }
+
+  async function submitManualTime(stepId) {
+    if (!manualTime.startedAt || !(Number(manualTime.minutes) > 0)) return
+    if (await addManualTime(stepId, manualTime)) setManualTime({ startedAt: '', minutes: '', note: '' })
+  }
+
+  function renderRecurrence(step) {
+    const preset = recurrencePreset(step.recurrence)
+    const kind = customRecurrence ? 'custom' : preset.kind
+    const completions = step.completions || []
+    return (
+      <div className="recurrence-fields">
+        <label>
+          {t('recurrence')}
+          <select
+            value={kind}
+            onChange={(event) => {
+              const next = event.target.value
+              setCustomRecurrence(next === 'custom')
+              if (next !== 'custom') updateProjectStep(step.id, { recurrence: recurrenceRule(next, preset.days) })
+            }}
+          >
+            {['none', 'daily', 'weekly', 'monthly', 'custom'].map((option) => (
+              <option key={option} value={option}>{t(`recurrence_${option}`)}</option>
+            ))}
+          </select>
+        </label>
+        {kind === 'weekly' ? (
+          <div className="weekday-picker">
+            {WEEKDAY_CODES.map((day) => (
+              <button
+                key={day}
+                className={`mini-btn ${preset.days.includes(day) ? 'active' : ''}`}
+                onClick={() =>
+                  updateProjectStep(step.id, {
+                    recurrence: recurrenceRule('weekly', preset.days.includes(day) ? preset.days.filter((entry) => entry !== day) : [...preset.days, day]),
+                  })}
+              >
+                {t(`weekday_${day}`)}
+              </button>
+            ))}
+          </div>
+        ) : null}
+        {kind === 'custom' ? (
+          <label>
+            {t('recurrenceRule')}
+            <input
+              key={step.recurrence}
+              defaultValue={step.recurrence}
+              placeholder="FREQ=MONTHLY;BYDAY=-1FR"
+              onBlur={(event) => updateProjectStep(step.id, { recurrence: event.target.value.trim() })}
+            />
+          </label>
+        ) : null}
+        {step.recurrence ? (
+          <p className={`field-hint ${recurrencePreview.error ? 'invalid' : ''}`}>
+            {recurrencePreview.error
+              ? t('recurrenceInvalid')
+              : `${t('recurrenceUpcoming')}: ${recurrencePreview.dates.map((date) => date.replace('T', ' ')).join(', ')}`}
+          </p>
+        ) : null}
+        {completions.length ? (
+          <div className="completion-log">
+            <span>{t('recurrenceCompleted')}:</span>
+            {completions.slice(-5).reverse().map((completion) => (
+              <span key={completion.completedAt} className="tag-chip">
+                {new Date(completion.completedAt).toLocaleDateString(language)}
+              </span>
+            ))}
+          </div>
+        ) : null}
+      </div>
+    )
+  }
+
+  function renderTimeEntries(step) {
+    const entries = step.timeEntries || []
+    return (
+      <div className="time-entries">
+        <div className="time-entry-create">
+          <label>
+            {t('timeEntryStart')}
+            <input type="datetime-local" value={manualTime.startedAt} onChange={(event) => setManualTime((prev) => ({ ...prev, startedAt: event.target.value }))} />
+          </label>
+          <label>
+            {t('timeEntryMinutes')}
+            <input type="number" min="1" value={manualTime.minutes} onChange={(event) => setManualTime((prev) => ({ ...prev, minutes: event.target.value }))} />
+          </label>
+          <label>
+            {t('timeEntryNote')}
+            <input value={manualTime.note} onChange={(event) => setManualTime((prev) => ({ ...prev, note: event.target.value }))} />
+          </label>
+          <button className="mode-btn" onClick={() => submitManualTime(step.id)}>
+            <Plus size={15} />
+            <span>{t('addTimeEntry')}</span>
+          </button>
+        </div>
+        {entries.map((entry) => (
+          <div key={entry.id} className="time-entry">
+            <span>{new Date(entry.startedAt).toLocaleString(language)}</span>
+            <strong>{formatDuration(Math.max(0, (Date.parse(entry.endedAt) - Date.parse(entry.startedAt)) / 1000))}</strong>
+            <span className="wrap-anywhere">{entry.note || (entry.manual ? t('timeEntryManual') : t('timeEntryTimer'))}</span>
+            <button className="mini-btn danger" onClick={() => removeTimeEntry(step.id, entry.id)} title={t('removeTimeEntry')}>
+              <Trash2 size={14} />
+            </button>
+          </div>
+        ))}
+      </div>
+    )
+  }
+
+  function renderSteps(steps, depth) {
+    return steps.map((step, index) => {
+      const children = step.children || []
+      const nested = children.length ? stepProgress(children) : null
+      const timing = runningTimer?.projectId === selectedProject.id && runningTimer?.stepId === step.id
+      const tracked = trackedSeconds(step, timing ? runningTimer : null, timerNow)
+      return (
+        <div className="step-item" key={step.id}>
+          <div className="step-row">
+            <input type="checkbox" checked={step.done} onChange={(event) => updateProjectStep(step.id, { done: event.target.checked })} />
+            <input className={step.done ? 'done' : ''} value={step.text} onChange={(event) => updateProjectStep(step.id, { text: event.target.value })} />
+            <span className="step-progress">{nested ? `${step.done ? nested.total : nested.done}/${nested.total}` : ''}</span>
+            <span className="step-time">
+              {step.recurrence ? <Repeat size={12} /> : null}
+              {tracked ? formatDuration(tracked) : ''}
+            </span>
+            <button className={`mini-btn ${timing ? 'active' : ''}`} onClick={() => toggleStepTimer(step.id)} title={timing ? t('stopTimer') : t('startTimer')}>
+              {timing ? <Square size={14} /> : <Play size={14} />}
+            </button>
+            <button
+              className={`mini-btn ${step.dueAt || step.remindAt || step.recurrence ? 'active' : ''}`}
+              onClick={() => {
+                setManualTime({ startedAt: '', minutes: '', note: '' })
+                setCustomRecurrence(false)
+                setScheduleStepId((prev) => (prev === step.id ? null : step.id))
+              }}
+              title={step.dueAt ? `${t('dueAt')}: ${step.dueAt.replace('T', ' ')}` : t('schedule')}
+            >
+              <AlarmClock size={14} />
+            </button>
+            <button className="mini-btn" onClick={() => outdentProjectStep(step.id)} disabled={depth === 0} title={t('outdentStep')}>
+              <IndentDecrease size={14} />
+            </button>
+            <button className="mini-btn" onClick={() => indentProjectStep(step.id)} disabled={index === 0} title={t('indentStep')}>
+              <IndentIncrease size={14} />
+            </button>
+            <button className="mini-btn" onClick={() => moveProjectStep(step.id, -1)} disabled={index === 0} title={t('up')}>↑</button>
+            <button className="mini-btn" onClick={() => moveProjectStep(step.id, 1)} disabled={index === steps.length - 1} title={t('down')}>↓</button>
+            <button className="mini-btn danger" onClick={() => removeProjectStep(step.id)} title={t('deleteStep')}>
+              <Trash2 size={14} />
+            </button>
+          </div>
+          {scheduleStepId === step.id ? (
+            <div className="schedule-fields step-schedule">
+              <label>
+                {t('dueAt')}
+                <input type="datetime-local" value={step.dueAt || ''} onChange={(event) => updateProjectStep(step.id, { dueAt: event.target.value })} />
+              </label>
+              <label>
+                {t('remindAt')}
+                <input type="datetime-local" value={step.remindAt || ''} onChange={(event) => updateProjectStep(step.id, { remindAt: event.target.value })} />
+              </label>
+              <label>
+                {t('tagsField')}
+                <input
+                  key={(step.tags || []).join(',')}
+                  defaultValue={(step.tags || []).join(', ')}
+                  placeholder={t('tagsPlaceholder')}
+                  onBlur={(event) => updateProjectStep(step.id, { tags: normalizeTags(event.target.value) })}
+                />
+              </label>
+              {renderRecurrence(step)}
+              {renderTimeEntries(step)}
+            </div>
+          ) : null}
+          {scheduleStepId !== step.id ? renderTags(step.tags) : null}
+          {children.length ? <div className="step-children">{renderSteps(children, depth + 1)}</div> : null}
+        </div>
+      )
+    })
+  }
+
   return (
     <div className="layout">
       {isMobileDevice && projectsMenuOpen ? (
@@ -81,8 +406,12 @@ function ProjectsPage({
             <>
               <IconButton title={t('createProject')} icon={<FolderPlus size={18} />} onClick={openCreateProjectModal} />
               <IconButton title={t('importProject')} icon={<Upload size={18} />} onClick={askImportProject} />
+              <IconButton title={t('qrImport')} icon={<ScanLine size={18} />} onClick={openQrImport} />
             </>
           ) : null}
+          <IconButton title={t('tagManager')} icon={<Tags size={18} />} onClick={openTagManager} />
+          <IconButton title={t('templates')} icon={<LayoutTemplate size={18} />} onClick={openTemplateLibrary} />
+          <IconButton title={t('importCenter')} icon={<FileInput size={18} />} onClick={openImportCenter} />
         </div>
         <div className="project-list">
           {projects.map((project, index) => (
@@ -99,8 +428,9 @@ function ProjectsPage({
                 ) : null}
                 <span className="project-count">
                   <NotebookText size={12} />
-                  <span>{project.notes.length} {t('noteCount')} • {(project.steps || []).length} {t('stepCount')}</span>
+                  <span>{project.notes.length} {t('noteCount')} • {countSteps(project.steps)} {t('stepCount')}</span>
                 </span>
+                {renderTags(project.tags)}
               </div>
             </button>
           ))}
@@ -137,6 +467,12 @@ function ProjectsPage({
                     onClick={toggleSelectedProjectPinned}
                   />
                   <IconButton title={t('exportProject')} icon={<Download size={18} />} onClick={exportSelectedProject} />
+                  <IconButton title={t('filteredExport')} icon={<SlidersHorizontal size={18} />} onClick={openExportFilterModal} />
+                  <IconButton title={t('documentExport')} icon={<FileOutput size={18} />} onClick={openDocumentExport} />
+                  <IconButton title={t('projectHistory')} icon={<GitCommitHorizontal size={18} />} onClick={openProjectHistory} />
+                  <IconButton title={t('projectMap')} icon={<Network size={18} />} onClick={openProjectMap} />
+                  <IconButton title={t('exportProjectEncrypted')} icon={<Lock size={18} />} onClick={exportSelectedProjectEncrypted} />
+                  <IconButton title={t('qrShare')} icon={<QrCode size={18} />} onClick={shareSelectedProjectQr} />
                   {isContextualControls ? (
                     <>
                       <IconButton title={t('editProject')} icon={<FolderCog size={18} />} onClick={openEditProjectModal} />
@@ -165,6 +501,15 @@ function ProjectsPage({
                 <p className="empty-notes">{t('noStatuses')}</p>
               ) : null}
 
+              {selectedProject.dueAt ? (
+                <p className="project-due">
+                  <AlarmClock size={14} />
+                  <span>{t('dueAt')}: {selectedProject.dueAt.replace('T', ' ')}</span>
+                </p>
+              ) : null}
+
+              {renderTags(selectedProject.tags)}
+
               {selectedProject.description ? <p className="project-description wrap-anywhere">{selectedProject.description}</p> : null}
             </div>
             <div className="content-separator" />
@@ -184,19 +529,31 @@ function ProjectsPage({
             ) : (
               <div className="notes-grid">
                 {selectedProject.notes.map((note, index) => (
-                  <article key={note.id} className="note-card" style={{ animationDelay: `${index * 60}ms` }}>
+                  <article key={note.id} id={`note-${note.id}`} className={`note-card ${focusNoteId === note.id ? 'focused' : ''}`} style={{ animationDelay: `${index * 60}ms` }}>
                     <div className="note-header">
                       <h3>
                         <FilePenLine size={15} />
                         <span className="wrap-anywhere">{note.title}</span>
                       </h3>
                       <div className="note-actions">
+                        <IconButton title={t('attachFile')} icon={<Paperclip size={16} />} onClick={() => askAttachToNote(note.id)} />
+                        <IconButton title={t('noteRevisions')} icon={<History size={16} />} onClick={() => openNoteRevisions(note)} />
                         <IconButton title={t('editNote')} icon={<Pencil size={16} />} onClick={() => openEditNoteModal(note)} />
                         <IconButton title={t('deleteNote')} icon={<Trash2 size={16} />} onClick={() => removeNote(note.id)} danger />
                       </div>
                     </div>
 
-                    {note.body ? <p className="note-body wrap-anywhere">{note.body}</p> : null}
+                    <MarkdownBody body={note.body} onLinkError={onMarkdownLinkError} onWikiLink={openWikiLink} />
+                    <NoteAttachments
+                      t={t}
+                      note={note}
+                      removeAttachment={removeNoteAttachment}
+                      openAttachment={openNoteAttachment}
+                      saveAttachment={saveNoteAttachment}
+                      loadThumbnail={loadAttachmentThumbnail}
+                    />
+                    {renderTags(note.tags)}
+                    {noteLinks(note)}
                   </article>
                 ))}
               </div>
@@ -208,25 +565,33 @@ function ProjectsPage({
                 <ListTodo size={18} />
                 <span>{t('projectSteps')}</span>
               </h3>
-              <button className="mode-btn" onClick={() => setShowStepCreate((prev) => !prev)}>
-                <Plus size={15} />
-                <span>{showStepCreate ? t('close') : t('addStep')}</span>
-              </button>
+              <div className="section-actions">
+                {runningTimerInfo ? (
+                  <span className="timer-chip">
+                    <button className="timer-chip-label wrap-anywhere" onClick={() => handleSelectProject(runningTimerInfo.projectId)} title={runningTimerInfo.project}>
+                      <Timer size={14} />
+                      <span>{runningTimerInfo.step || runningTimerInfo.project}</span>
+                      <strong>{runningTimerInfo.elapsed}</strong>
+                    </button>
+                    <button className="mini-btn" onClick={stopRunningTimer} title={t('stopTimer')}>
+                      <Square size={13} />
+                    </button>
+                  </span>
+                ) : null}
+                <button className="mode-btn" onClick={openTimesheet}>
+                  <Timer size={15} />
+                  <span>{t('timesheet')}</span>
+                </button>
+                <button className="mode-btn" onClick={() => setShowStepCreate((prev) => !prev)}>
+                  <Plus size={15} />
+                  <span>{showStepCreate ? t('close') : t('addStep')}</span>
+                </button>
+              </div>
             </div>
 
             {(selectedProject.steps || []).length > 0 || showStepCreate ? (
               <div className="project-steps">
-                {(selectedProject.steps || []).map((step, index) => (
-                  <div className="step-row" key={step.id}>
-                    <input type="checkbox" checked={step.done} onChange={(event) => updateProjectStep(step.id, { done: event.target.checked })} />
-                    <input className={step.done ? 'done' : ''} value={step.text} onChange={(event) => updateProjectStep(step.id, { text: event.target.value })} />
-                    <button className="mini-btn" onClick={() => moveProjectStep(index, index - 1)} disabled={index === 0} title={t('up')}>↑</button>
-                    <button className="mini-btn" onClick={() => moveProjectStep(index, index + 1)} disabled={index === selectedProject.steps.length - 1} title={t('down')}>↓</button>
-                    <button className="mini-btn danger" onClick={() => removeProjectStep(step.id)} title={t('deleteStep')}>
-                      <Trash2 size={14} />
-                    </button>
-                  </div>
-                ))}
+                {renderSteps(selectedProject.steps || [], 0)}
 
                 {showStepCreate ? (
                   <div className="step-create-row">
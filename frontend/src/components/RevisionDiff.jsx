@@ -0,0 +1,36 @@
+function DiffChunks({ chunks }) {
+  return chunks.map((chunk, index) => (
+    <span key={index} className={`diff-chunk diff-${chunk.tag}`}>
+      {chunk.text}
+    </span>
+  ))
+}
+
+function RevisionDiff({ t, view }) {
+  if (view.diff) {
+    return (
+      <div className="revision-diff">
+        <p className="revision-stats">
+          {t('revisionDiffStats').replace('{insertions}', view.diff.insertions).replace('{deletions}', view.diff.deletions)}
+        </p>
+        <h4 className="wrap-anywhere">
+          <DiffChunks chunks={view.diff.title} />
+        </h4>
+        <pre className="revision-text wrap-anywhere">
+          <DiffChunks chunks={view.diff.body} />
+        </pre>
+      </div>
+    )
+  }
+  if (view.revision) {
+    return (
+      <div className="revision-diff">
+        <h4 className="wrap-anywhere">{view.revision.title}</h4>
+        <pre className="revision-text wrap-anywhere">{view.revision.body}</pre>
+      </div>
+    )
+  }
+  return null
+}
+
+export default RevisionDiff
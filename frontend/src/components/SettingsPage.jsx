@@ -1,12 +1,18 @@
-import { useState } from 'react'
+import { useMemo, useState } from 'react'
 import {
+  AlarmClock,
+  Archive,
+  ArchiveRestore,
   Check,
   Download,
   ExternalLink,
   FolderOpen,
+  History,
   Languages,
   LayoutGrid,
+  ListTodo,
   MoonStar,
+  Paperclip,
   RefreshCw,
   Square,
   SquareStack,
@@ -23,8 +29,16 @@ function SettingsPage({
   openUpdateDownload,
   appVersion,
   showWindowSettings,
+  createBackup,
+  askRestoreBackup,
+  cleanAttachments,
 }) {
   const [newStatus, setNewStatus] = useState('')
+  const timeZones = useMemo(() => {
+    const zones = typeof Intl.supportedValuesOf === 'function' ? Intl.supportedValuesOf('timeZone') : []
+    const current = settingsDraft.timeZone || ''
+    return current && !zones.includes(current) ? [current, ...zones] : zones
+  }, [settingsDraft.timeZone])
 
   function addStatus() {
     const value = newStatus.trim()
@@ -151,6 +165,10 @@ function SettingsPage({
               <Check size={15} />
               <span>{settingsDraft.alwaysOnTop ? t('alwaysOnTopYes') : t('alwaysOnTopNo')}</span>
             </button>
+            <button className={`mode-btn ${settingsDraft.closeToTray ? 'active' : ''}`} onClick={() => setSettingsDraft((prev) => ({ ...prev, closeToTray: !prev.closeToTray }))}>
+              <Check size={15} />
+              <span>{settingsDraft.closeToTray ? t('closeToTrayYes') : t('closeToTrayNo')}</span>
+            </button>
           </div>
         </section>
       ) : null}
@@ -167,6 +185,71 @@ function SettingsPage({
         </div>
       </section>
 
+      <section className="setting-card">
+        <h3>
+          <History size={17} />
+          <span>{t('history')}</span>
+        </h3>
+        <div className="setting-actions">
+          <button className={`mode-btn ${settingsDraft.gitHistory ? 'active' : ''}`} onClick={() => setSettingsDraft((prev) => ({ ...prev, gitHistory: !prev.gitHistory }))}>
+            <Check size={15} />
+            <span>{settingsDraft.gitHistory ? t('gitHistoryOn') : t('gitHistoryOff')}</span>
+          </button>
+        </div>
+      </section>
+
+      <section className="setting-card">
+        <h3>
+          <Archive size={17} />
+          <span>{t('backup')}</span>
+        </h3>
+        <div className="setting-actions">
+          <button className="mode-btn" onClick={createBackup}>
+            <Archive size={15} />
+            <span>{t('backupCreate')}</span>
+          </button>
+          <button className="mode-btn" onClick={askRestoreBackup}>
+            <ArchiveRestore size={15} />
+            <span>{t('backupRestore')}</span>
+          </button>
+          <button className="mode-btn" onClick={cleanAttachments}>
+            <Paperclip size={15} />
+            <span>{t('attachmentCleanup')}</span>
+          </button>
+        </div>
+      </section>
+
+      <section className="setting-card">
+        <h3>
+          <AlarmClock size={17} />
+          <span>{t('reminders')}</span>
+        </h3>
+        <div className="setting-actions">
+          <label className="setting-field">
+            <span>{t('timeZone')}</span>
+            <select value={settingsDraft.timeZone || ''} onChange={(event) => setSettingsDraft((prev) => ({ ...prev, timeZone: event.target.value }))}>
+              <option value="">{t('timeZoneSystem')}</option>
+              {timeZones.map((zone) => (
+                <option key={zone} value={zone}>{zone}</option>
+              ))}
+            </select>
+          </label>
+        </div>
+      </section>
+
+      <section className="setting-card">
+        <h3>
+          <ListTodo size={17} />
+          <span>{t('projectSteps')}</span>
+        </h3>
+        <div className="setting-actions">
+          <button className={`mode-btn ${settingsDraft.autoCompleteSteps ? 'active' : ''}`} onClick={() => setSettingsDraft((prev) => ({ ...prev, autoCompleteSteps: !prev.autoCompleteSteps }))}>
+            <Check size={15} />
+            <span>{settingsDraft.autoCompleteSteps ? t('autoCompleteStepsOn') : t('autoCompleteStepsOff')}</span>
+          </button>
+        </div>
+      </section>
+
       <section className="setting-card">
         <h3>
           <Download size={17} />
@@ -8,6 +8,7 @@ import {
   ListTodo,
   NotebookText,
   RefreshCw,
+  ScanLine,
   Settings,
   Trash2,
   Upload,
@@ -17,6 +18,7 @@ import IconButton from './IconButton'
 function TopBar({
   t,
   askImportProject,
+  openQrImport,
   selectedProject,
   progress,
   isContextualControls,
@@ -44,6 +46,7 @@ function TopBar({
             <>
               <IconButton title={t('createProject')} icon={<FolderPlus size={18} />} onClick={openCreateProjectModal} />
               <IconButton title={t('importProject')} icon={<Upload size={18} />} onClick={askImportProject} />
+              <IconButton title={t('qrImport')} icon={<ScanLine size={18} />} onClick={openQrImport} />
               <IconButton title={t('editProject')} icon={<FolderCog size={18} />} onClick={openEditProjectModal} disabled={!selectedProject} />
               <IconButton title={t('deleteProject')} icon={<Trash2 size={18} />} onClick={removeSelectedProject} danger disabled={!selectedProject} />
               <IconButton title={t('createNote')} icon={<FilePlus2 size={18} />} onClick={openCreateNoteModal} disabled={!selectedProject} />
@@ -11,4 +11,8 @@ export const DEFAULT_SETTINGS = {
   windowMode: 'fullscreen_framed',
   alwaysOnTop: false,
   language: 'ru',
+  gitHistory: false,
+  timeZone: '',
+  autoCompleteSteps: false,
+  closeToTray: false,
 }
@@ -41,6 +41,86 @@ export const I18N = {
     unpinProject: 'Открепить проект',
     exportProject: 'Экспорт проекта',
     importProject: 'Импорт проекта',
+    exportProjectEncrypted: 'Экспорт с паролем',
+    encryptedExportModal: 'Зашифрованный экспорт',
+    encryptedImportModal: 'Файл защищен паролем',
+    passwordField: 'Пароль',
+    passwordConfirmField: 'Повторите пароль',
+    passwordRequired: 'Введите пароль',
+    passwordMismatch: 'Пароли не совпадают',
+    wrongPassword: 'Неверный пароль или поврежденный файл',
+    encryptAndExport: 'Зашифровать и экспортировать',
+    decryptAndImport: 'Расшифровать и импортировать',
+    encryptedDesktopOnly: 'Шифрование доступно только в приложении',
+    filteredExport: 'Экспорт с фильтром',
+    filteredExportModal: 'Выборочный экспорт',
+    filteredExportNotes: 'Заметки для экспорта',
+    filteredExportOpenSteps: 'Только незавершенные шаги',
+    filteredExportNoDescription: 'Без описания проекта',
+    filteredExportTemplate: 'Убрать идентификаторы (шаблон)',
+    filteredExportDesktopOnly: 'Выборочный экспорт доступен только в приложении',
+    documentExport: 'Экспорт в документ',
+    documentExportModal: 'Экспорт в документ',
+    documentExportWorkspace: 'Все проекты',
+    documentExportHint: 'Файл сохраняется в папку «Загрузки»',
+    documentExportDesktopOnly: 'Экспорт в документ доступен только в приложении',
+    documentExported: 'Файл сохранен: {path}',
+    documentHtml: 'HTML-отчет',
+    documentPdf: 'PDF-отчет',
+    documentEpub: 'Электронная книга (EPUB)',
+    documentDocx: 'Документ Word (DOCX)',
+    documentSlides: 'Презентация (HTML)',
+    projectMap: 'Карта проекта',
+    projectMapModal: 'Карта проекта',
+    projectMapSave: 'Сохранить SVG',
+    projectMapDesktopOnly: 'Карта проекта доступна только в приложении',
+    projectMapError: 'Не удалось построить карту проекта',
+    documentStepsCsv: 'Шаги в CSV',
+    importCenter: 'Импорт из других программ',
+    importCenterModal: 'Импорт',
+    importCenterDesktopOnly: 'Импорт доступен только в приложении',
+    importSourceError: 'Не удалось импортировать файл',
+    importReportModal: 'Результат импорта',
+    importReportDetails: 'Не перенесено:',
+    importStepsCsv: 'Шаги из CSV',
+    stepsCsvSummary: 'Обновлено шагов: {updated}, добавлено: {appended}',
+    importTodoist: 'Todoist (CSV)',
+    importTrello: 'Trello (JSON)',
+    importMicrosoftTodo: 'Microsoft To Do (JSON)',
+    importGoogleKeep: 'Google Keep (Takeout)',
+    importEvernote: 'Evernote (ENEX)',
+    importJoplin: 'Joplin (RAW)',
+    documentOpml: 'Структура (OPML)',
+    importOpml: 'Структура (OPML)',
+    documentOrg: 'Org-mode',
+    importOrg: 'Org-mode (обновить проекты)',
+    orgSummary: 'Обновлено элементов: {updated}, добавлено: {added}',
+    importedProjects: 'Импортировано проектов: {count}',
+    csvLine: 'Строка',
+    backup: 'Резервная копия',
+    backupCreate: 'Создать копию',
+    backupRestore: 'Восстановить из копии',
+    backupRestoreModal: 'Восстановление из копии',
+    backupRestoreHint: 'Объединение добавит проекты, которых еще нет. Замена удалит текущие проекты, вложения и историю.',
+    backupMerge: 'Объединить',
+    backupReplace: 'Заменить все',
+    backupRestored: 'Копия от {date} восстановлена: добавлено проектов {added}, пропущено {skipped}',
+    backupError: 'Не удалось обработать резервную копию',
+    backupDesktopOnly: 'Резервные копии доступны только в приложении',
+    attachmentCleanup: 'Удалить неиспользуемые вложения',
+    attachmentCleanupDone: 'Удалено файлов: {count}, освобождено {size} МБ',
+    attachmentCleanupError: 'Не удалось очистить вложения',
+    attachmentCleanupDesktopOnly: 'Очистка вложений доступна только в приложении',
+    qrShare: 'Передать через QR-код',
+    qrShareModal: 'QR-коды проекта',
+    qrShareHint: 'Отсканируйте коды по порядку на другом устройстве',
+    qrImport: 'Импорт из QR-кодов',
+    qrImportModal: 'Импорт из QR-кодов',
+    qrImportField: 'Отсканированные коды, по одному на строку',
+    qrImportError: 'Не удалось прочитать QR-коды',
+    qrMissingParts: 'Не хватает кодов: {parts}',
+    qrTooLarge: 'Проект слишком большой для передачи через QR-коды',
+    qrDesktopOnly: 'QR-передача доступна только в приложении',
     projectExported: 'Проект экспортирован',
     projectExportError: 'Не удалось экспортировать проект',
     projectImported: 'Проект импортирован',
@@ -70,6 +150,165 @@ export const I18N = {
     updateCheckStarted: 'Проверяем обновления...',
     alwaysOnTopYes: 'Поверх окон: Да',
     alwaysOnTopNo: 'Поверх окон: Нет',
+    closeToTrayYes: 'Сворачивать в трей при закрытии: Да',
+    closeToTrayNo: 'Сворачивать в трей при закрытии: Нет',
+    history: 'История изменений',
+    gitHistoryOn: 'История в git: Вкл',
+    gitHistoryOff: 'История в git: Выкл',
+    projectHistory: 'История изменений в git',
+    projectHistoryModal: 'История изменений',
+    projectHistoryScope: 'Что показать',
+    projectHistoryWholeProject: 'Весь проект',
+    projectHistoryEmpty: 'Сохраненных версий пока нет',
+    projectHistoryCompare: 'Отличия выбранной версии от текущей:',
+    projectHistoryDesktopOnly: 'История изменений доступна только в приложении',
+    projectHistoryDisabled: 'Включите историю в git в настройках',
+    projectHistoryError: 'Не удалось загрузить историю',
+    reminders: 'Напоминания',
+    remindersModal: 'Напоминания',
+    timeZone: 'Часовой пояс',
+    timeZoneSystem: 'Как в системе',
+    schedule: 'Срок и напоминание',
+    dueAt: 'Срок',
+    remindAt: 'Напомнить',
+    projectDeadline: 'Срок проекта',
+    snooze10m: 'Через 10 мин',
+    snooze1h: 'Через час',
+    snoozeTomorrow: 'Завтра',
+    reminderDone: 'Выполнено',
+    reminderDismiss: 'Скрыть',
+    reminderMissed: 'пропущено',
+    reminderSnoozed: 'Напоминание отложено',
+    reminderSnoozeError: 'Не удалось отложить напоминание',
+    backlinks: 'Ссылаются',
+    brokenLinks: 'Битые ссылки',
+    brokenLink_missing: 'Заметка не найдена',
+    brokenLink_ambiguous: 'Подходит несколько заметок — укажите «Проект/Заметка»',
+    wikiLinkBroken: 'Не удалось найти заметку «{target}»',
+    linksRewriteModal: 'Переименование заметки',
+    linksRewritePrompt: 'На эту заметку ссылаются: {count}. Обновить ссылки на новое название?',
+    renameOnly: 'Только переименовать',
+    renameAndRewrite: 'Обновить ссылки',
+    linksRewritten: 'Обновлено ссылок: {count}',
+    linksRewriteError: 'Не удалось обновить ссылки',
+    noteRevisions: 'История заметки',
+    noteRevisionsModal: 'Версии заметки',
+    noteRevisionsEmpty: 'Сохранённых версий пока нет',
+    noteRevisionsDesktopOnly: 'История версий доступна только в десктопной версии',
+    noteRevisionsError: 'Не удалось загрузить версии заметки',
+    revisionCompareWith: 'Сравнить с',
+    revisionCompareNone: 'Без сравнения',
+    revisionChars: '{count} симв.',
+    revisionDiffLines: 'По строкам',
+    revisionDiffWords: 'По словам',
+    revisionDiffStats: '+{insertions} / −{deletions}',
+    revisionRestore: 'Восстановить эту версию',
+    revisionRestored: 'Версия заметки восстановлена',
+    revisionRestoreError: 'Не удалось восстановить версию',
+    startTimer: 'Запустить таймер',
+    stopTimer: 'Остановить таймер',
+    timerError: 'Не удалось переключить таймер',
+    timeTrackingDesktopOnly: 'Учёт времени доступен только в десктопной версии',
+    timeEntryStart: 'Начало',
+    timeEntryMinutes: 'Минут',
+    timeEntryNote: 'Комментарий',
+    addTimeEntry: 'Добавить время',
+    removeTimeEntry: 'Удалить запись времени',
+    timeEntryManual: 'Вручную',
+    timeEntryTimer: 'Таймер',
+    timeEntryAddError: 'Не удалось добавить запись времени',
+    timeEntryRemoveError: 'Не удалось удалить запись времени',
+    timesheet: 'Табель',
+    timesheetModal: 'Табель учёта времени',
+    timesheetFrom: 'С',
+    timesheetTo: 'По',
+    timesheetScopeProject: 'Этот проект',
+    timesheetScopeAll: 'Все проекты',
+    timesheetPeriod_day: 'По дням',
+    timesheetPeriod_week: 'По неделям',
+    timesheetPeriod_month: 'По месяцам',
+    timesheetTotal: 'Итого',
+    timesheetEmpty: 'За выбранный период времени не записано',
+    exportTimesheet: 'Экспорт табеля в CSV',
+    timesheetExported: 'Табель сохранён в загрузки',
+    timesheetError: 'Не удалось построить табель',
+    recurrence: 'Повтор',
+    recurrence_none: 'Не повторять',
+    recurrence_daily: 'Каждый день',
+    recurrence_weekly: 'Каждую неделю',
+    recurrence_monthly: 'Каждый месяц',
+    recurrence_custom: 'Правило RRULE',
+    recurrenceRule: 'Правило (RFC 5545)',
+    recurrenceUpcoming: 'Ближайшие',
+    recurrenceInvalid: 'Правило повтора не распознано',
+    recurrenceCompleted: 'Выполнено',
+    recurrenceNext: 'Следующее повторение: {date}',
+    recurrenceError: 'Не удалось создать следующее повторение',
+    weekday_MO: 'Пн',
+    weekday_TU: 'Вт',
+    weekday_WE: 'Ср',
+    weekday_TH: 'Чт',
+    weekday_FR: 'Пт',
+    weekday_SA: 'Сб',
+    weekday_SU: 'Вс',
+    templates: 'Шаблоны проектов',
+    templatesModal: 'Шаблоны проектов',
+    templatesDesktopOnly: 'Шаблоны доступны только в настольном приложении',
+    templatesError: 'Не удалось выполнить действие с шаблонами',
+    templatesEmpty: 'Шаблонов пока нет — сохраните проект как шаблон',
+    templateName: 'Название шаблона',
+    templateSaveProject: 'Сохранить текущий проект',
+    templateSaved: 'Шаблон сохранён',
+    templatePlaceholderHint: 'Отметки выполнения, даты, учёт времени и вложения не сохраняются. Используйте {{client}} и подобные подстановки в текстах.',
+    templateCounts: 'Заметок: {notes}, шагов: {steps}',
+    templateUse: 'Создать',
+    deleteTemplate: 'Удалить шаблон',
+    templateBack: 'Назад',
+    templateCreateProject: 'Создать проект',
+    templateBuiltIns: '{{date}} и {{year}} заполняются автоматически; пустые поля останутся как есть.',
+    templateProjectCreated: 'Проект создан из шаблона',
+    importTemplates: 'Импорт',
+    exportTemplates: 'Экспорт',
+    templatesExported: 'Библиотека шаблонов экспортирована',
+    templatesImported: 'Шаблоны импортированы: новых {added}, обновлено {replaced}',
+    templatesImportError: 'Не удалось импортировать библиотеку шаблонов',
+    linkOpenError: 'Не удалось открыть ссылку',
+    markdownHint: 'Поддерживается Markdown: таблицы, списки задач, блоки кода, ссылки [[Заметка]]',
+    attachFile: 'Прикрепить файл',
+    openAttachment: 'Открыть вложение',
+    saveAttachment: 'Сохранить в загрузки',
+    removeAttachment: 'Открепить',
+    attachmentsDesktopOnly: 'Вложения доступны только в настольной версии',
+    attachmentAddError: 'Не удалось прикрепить файл',
+    attachmentOpenError: 'Файл вложения не найден',
+    attachmentSaved: 'Вложение сохранено в загрузки',
+    attachmentRevealed: 'Этот тип файла не открывается напрямую — вложение сохранено в загрузки',
+    attachmentImportError: 'Не удалось восстановить вложения проекта',
+    tagsField: 'Теги',
+    tagsPlaceholder: 'через запятую, например: срочно, клиент',
+    tagManager: 'Теги',
+    tagsModal: 'Управление тегами',
+    tagsDesktopOnly: 'Управление тегами доступно только в настольной версии',
+    tagsError: 'Не удалось обработать теги',
+    tagsEmpty: 'Тегов пока нет',
+    tagCounts: 'проекты: {projects}, заметки: {notes}, шаги: {steps}',
+    renameTag: 'Переименовать тег',
+    deleteTag: 'Удалить тег',
+    mergeTags: 'Объединить',
+    mergeTagsInto: 'Объединить выбранные теги в',
+    tagMatchAll: 'Все выбранные',
+    tagMatchAny: 'Любой из выбранных',
+    findTagged: 'Найти',
+    tagQueryEmpty: 'Ничего не найдено',
+    tagKind_project: 'Проект',
+    tagKind_note: 'Заметка',
+    tagKind_step: 'Шаг',
+    autoCompleteStepsOn: 'Автозавершение родительских шагов: Вкл',
+    autoCompleteStepsOff: 'Автозавершение родительских шагов: Выкл',
+    indentStep: 'Сделать подшагом',
+    outdentStep: 'Поднять уровень',
+    stepTreeDesktopOnly: 'Изменение вложенности шагов доступно в настольном приложении',
+    stepTreeError: 'Не удалось изменить вложенность шага',
     animationsOn: 'Анимации: Вкл',
     animationsOff: 'Анимации: Выкл',
     saveSettings: 'Сохранить настройки',
@@ -131,6 +370,86 @@ export const I18N = {
     unpinProject: 'Unpin project',
     exportProject: 'Export project',
     importProject: 'Import project',
+    exportProjectEncrypted: 'Export with password',
+    encryptedExportModal: 'Encrypted export',
+    encryptedImportModal: 'Password-protected file',
+    passwordField: 'Password',
+    passwordConfirmField: 'Repeat password',
+    passwordRequired: 'Enter a password',
+    passwordMismatch: 'Passwords do not match',
+    wrongPassword: 'Wrong password or damaged file',
+    encryptAndExport: 'Encrypt and export',
+    decryptAndImport: 'Decrypt and import',
+    encryptedDesktopOnly: 'Encryption is only available in the desktop app',
+    filteredExport: 'Export with filter',
+    filteredExportModal: 'Selective export',
+    filteredExportNotes: 'Notes to export',
+    filteredExportOpenSteps: 'Only open steps',
+    filteredExportNoDescription: 'Without project description',
+    filteredExportTemplate: 'Strip ids (template)',
+    filteredExportDesktopOnly: 'Selective export is only available in the desktop app',
+    documentExport: 'Export to document',
+    documentExportModal: 'Export to document',
+    documentExportWorkspace: 'All projects',
+    documentExportHint: 'The file is saved to the Downloads folder',
+    documentExportDesktopOnly: 'Document export is only available in the desktop app',
+    documentExported: 'File saved: {path}',
+    documentHtml: 'HTML report',
+    documentPdf: 'PDF report',
+    documentEpub: 'E-book (EPUB)',
+    documentDocx: 'Word document (DOCX)',
+    documentSlides: 'Slide deck (HTML)',
+    projectMap: 'Project map',
+    projectMapModal: 'Project map',
+    projectMapSave: 'Save SVG',
+    projectMapDesktopOnly: 'The project map is only available in the desktop app',
+    projectMapError: 'Failed to build the project map',
+    documentStepsCsv: 'Steps as CSV',
+    importCenter: 'Import from other apps',
+    importCenterModal: 'Import',
+    importCenterDesktopOnly: 'Import is only available in the desktop app',
+    importSourceError: 'Failed to import the file',
+    importReportModal: 'Import result',
+    importReportDetails: 'Not imported:',
+    importStepsCsv: 'Steps from CSV',
+    stepsCsvSummary: 'Steps updated: {updated}, added: {appended}',
+    importTodoist: 'Todoist (CSV)',
+    importTrello: 'Trello (JSON)',
+    importMicrosoftTodo: 'Microsoft To Do (JSON)',
+    importGoogleKeep: 'Google Keep (Takeout)',
+    importEvernote: 'Evernote (ENEX)',
+    importJoplin: 'Joplin (RAW)',
+    documentOpml: 'Outline (OPML)',
+    importOpml: 'Outline (OPML)',
+    documentOrg: 'Org-mode',
+    importOrg: 'Org-mode (update projects)',
+    orgSummary: 'Items updated: {updated}, added: {added}',
+    importedProjects: 'Projects imported: {count}',
+    csvLine: 'Line',
+    backup: 'Backup',
+    backupCreate: 'Create backup',
+    backupRestore: 'Restore from backup',
+    backupRestoreModal: 'Restore from backup',
+    backupRestoreHint: 'Merge adds projects that are not here yet. Replace deletes the current projects, attachments and history.',
+    backupMerge: 'Merge',
+    backupReplace: 'Replace everything',
+    backupRestored: 'Backup from {date} restored: {added} projects added, {skipped} skipped',
+    backupError: 'Failed to process the backup',
+    backupDesktopOnly: 'Backups are only available in the desktop app',
+    attachmentCleanup: 'Remove unused attachments',
+    attachmentCleanupDone: 'Files removed: {count}, {size} MB freed',
+    attachmentCleanupError: 'Failed to clean up attachments',
+    attachmentCleanupDesktopOnly: 'Attachment cleanup is only available in the desktop app',
+    qrShare: 'Share via QR code',
+    qrShareModal: 'Project QR codes',
+    qrShareHint: 'Scan the codes in order on the other device',
+    qrImport: 'Import from QR codes',
+    qrImportModal: 'Import from QR codes',
+    qrImportField: 'Scanned codes, one per line',
+    qrImportError: 'Failed to read the QR codes',
+    qrMissingParts: 'Missing codes: {parts}',
+    qrTooLarge: 'The project is too large to transfer with QR codes',
+    qrDesktopOnly: 'QR transfer is only available in the app',
     projectExported: 'Project exported',
     projectExportError: 'Failed to export project',
     projectImported: 'Project imported',
@@ -160,6 +479,165 @@ export const I18N = {
     updateCheckStarted: 'Checking for updates...',
     alwaysOnTopYes: 'Always on top: Yes',
     alwaysOnTopNo: 'Always on top: No',
+    closeToTrayYes: 'Close to tray: Yes',
+    closeToTrayNo: 'Close to tray: No',
+    history: 'Change history',
+    gitHistoryOn: 'Git history: On',
+    gitHistoryOff: 'Git history: Off',
+    projectHistory: 'Git change history',
+    projectHistoryModal: 'Change history',
+    projectHistoryScope: 'Show',
+    projectHistoryWholeProject: 'Whole project',
+    projectHistoryEmpty: 'No saved versions yet',
+    projectHistoryCompare: 'Changes from the selected version to the current one:',
+    projectHistoryDesktopOnly: 'Change history is only available in the desktop app',
+    projectHistoryDisabled: 'Turn on git history in settings',
+    projectHistoryError: 'Failed to load the history',
+    reminders: 'Reminders',
+    remindersModal: 'Reminders',
+    timeZone: 'Time zone',
+    timeZoneSystem: 'System default',
+    schedule: 'Due date and reminder',
+    dueAt: 'Due',
+    remindAt: 'Remind at',
+    projectDeadline: 'Project deadline',
+    snooze10m: 'In 10 min',
+    snooze1h: 'In 1 hour',
+    snoozeTomorrow: 'Tomorrow',
+    reminderDone: 'Done',
+    reminderDismiss: 'Dismiss',
+    reminderMissed: 'missed',
+    reminderSnoozed: 'Reminder snoozed',
+    reminderSnoozeError: 'Failed to snooze the reminder',
+    backlinks: 'Linked from',
+    brokenLinks: 'Broken links',
+    brokenLink_missing: 'Note not found',
+    brokenLink_ambiguous: 'Several notes match — use “Project/Note”',
+    wikiLinkBroken: 'Could not find the note “{target}”',
+    linksRewriteModal: 'Rename note',
+    linksRewritePrompt: '{count} link(s) point to this note. Update them to the new title?',
+    renameOnly: 'Rename only',
+    renameAndRewrite: 'Update links',
+    linksRewritten: 'Links updated: {count}',
+    linksRewriteError: 'Failed to update links',
+    noteRevisions: 'Note history',
+    noteRevisionsModal: 'Note revisions',
+    noteRevisionsEmpty: 'No saved revisions yet',
+    noteRevisionsDesktopOnly: 'Revision history is available in the desktop app only',
+    noteRevisionsError: 'Failed to load note revisions',
+    revisionCompareWith: 'Compare with',
+    revisionCompareNone: 'No comparison',
+    revisionChars: '{count} chars',
+    revisionDiffLines: 'Lines',
+    revisionDiffWords: 'Words',
+    revisionDiffStats: '+{insertions} / −{deletions}',
+    revisionRestore: 'Restore this revision',
+    revisionRestored: 'Note revision restored',
+    revisionRestoreError: 'Failed to restore the revision',
+    startTimer: 'Start timer',
+    stopTimer: 'Stop timer',
+    timerError: 'Failed to switch the timer',
+    timeTrackingDesktopOnly: 'Time tracking is available in the desktop app only',
+    timeEntryStart: 'Start',
+    timeEntryMinutes: 'Minutes',
+    timeEntryNote: 'Note',
+    addTimeEntry: 'Log time',
+    removeTimeEntry: 'Remove time entry',
+    timeEntryManual: 'Manual',
+    timeEntryTimer: 'Timer',
+    timeEntryAddError: 'Failed to add the time entry',
+    timeEntryRemoveError: 'Failed to remove the time entry',
+    timesheet: 'Timesheet',
+    timesheetModal: 'Timesheet',
+    timesheetFrom: 'From',
+    timesheetTo: 'To',
+    timesheetScopeProject: 'This project',
+    timesheetScopeAll: 'All projects',
+    timesheetPeriod_day: 'By day',
+    timesheetPeriod_week: 'By week',
+    timesheetPeriod_month: 'By month',
+    timesheetTotal: 'Total',
+    timesheetEmpty: 'No time logged in this period',
+    exportTimesheet: 'Export timesheet CSV',
+    timesheetExported: 'Timesheet saved to downloads',
+    timesheetError: 'Failed to build the timesheet',
+    recurrence: 'Repeat',
+    recurrence_none: 'Does not repeat',
+    recurrence_daily: 'Daily',
+    recurrence_weekly: 'Weekly',
+    recurrence_monthly: 'Monthly',
+    recurrence_custom: 'RRULE rule',
+    recurrenceRule: 'Rule (RFC 5545)',
+    recurrenceUpcoming: 'Upcoming',
+    recurrenceInvalid: 'The repeat rule is not recognized',
+    recurrenceCompleted: 'Completed',
+    recurrenceNext: 'Next occurrence: {date}',
+    recurrenceError: 'Failed to create the next occurrence',
+    weekday_MO: 'Mo',
+    weekday_TU: 'Tu',
+    weekday_WE: 'We',
+    weekday_TH: 'Th',
+    weekday_FR: 'Fr',
+    weekday_SA: 'Sa',
+    weekday_SU: 'Su',
+    templates: 'Project templates',
+    templatesModal: 'Project templates',
+    templatesDesktopOnly: 'Templates are available only in the desktop app',
+    templatesError: 'Template action failed',
+    templatesEmpty: 'No templates yet — save a project as a template',
+    templateName: 'Template name',
+    templateSaveProject: 'Save current project',
+    templateSaved: 'Template saved',
+    templatePlaceholderHint: 'Done flags, dates, tracked time and attachments are not kept. Use placeholders like {{client}} in any text.',
+    templateCounts: '{notes} notes, {steps} steps',
+    templateUse: 'Use',
+    deleteTemplate: 'Delete template',
+    templateBack: 'Back',
+    templateCreateProject: 'Create project',
+    templateBuiltIns: '{{date}} and {{year}} are filled in automatically; empty fields are left as-is.',
+    templateProjectCreated: 'Project created from template',
+    importTemplates: 'Import',
+    exportTemplates: 'Export',
+    templatesExported: 'Template library exported',
+    templatesImported: 'Templates imported: {added} new, {replaced} updated',
+    templatesImportError: 'Failed to import the template library',
+    linkOpenError: 'Failed to open the link',
+    markdownHint: 'Markdown is supported: tables, task lists, code blocks, [[Note]] links',
+    attachFile: 'Attach file',
+    openAttachment: 'Open attachment',
+    saveAttachment: 'Save to downloads',
+    removeAttachment: 'Detach',
+    attachmentsDesktopOnly: 'Attachments are available in the desktop app only',
+    attachmentAddError: 'Failed to attach file',
+    attachmentOpenError: 'Attachment file not found',
+    attachmentSaved: 'Attachment saved to downloads',
+    attachmentRevealed: 'This file type is not opened directly — the attachment was saved to downloads',
+    attachmentImportError: 'Failed to restore project attachments',
+    tagsField: 'Tags',
+    tagsPlaceholder: 'comma separated, e.g. urgent, client',
+    tagManager: 'Tags',
+    tagsModal: 'Manage tags',
+    tagsDesktopOnly: 'Tag management is available in the desktop app only',
+    tagsError: 'Failed to process tags',
+    tagsEmpty: 'No tags yet',
+    tagCounts: 'projects: {projects}, notes: {notes}, steps: {steps}',
+    renameTag: 'Rename tag',
+    deleteTag: 'Delete tag',
+    mergeTags: 'Merge',
+    mergeTagsInto: 'Merge selected tags into',
+    tagMatchAll: 'All selected',
+    tagMatchAny: 'Any selected',
+    findTagged: 'Find',
+    tagQueryEmpty: 'Nothing found',
+    tagKind_project: 'Project',
+    tagKind_note: 'Note',
+    tagKind_step: 'Step',
+    autoCompleteStepsOn: 'Auto-complete parent steps: On',
+    autoCompleteStepsOff: 'Auto-complete parent steps: Off',
+    indentStep: 'Make sub-step',
+    outdentStep: 'Move up a level',
+    stepTreeDesktopOnly: 'Nesting steps is available in the desktop app',
+    stepTreeError: 'Could not change step nesting',
     animationsOn: 'Animations: On',
     animationsOff: 'Animations: Off',
     saveSettings: 'Save settings',
@@ -221,6 +699,86 @@ export const I18N = {
     unpinProject: 'Відкріпити проєкт',
     exportProject: 'Експорт проєкту',
     importProject: 'Імпорт проєкту',
+    exportProjectEncrypted: 'Експорт з паролем',
+    encryptedExportModal: 'Зашифрований експорт',
+    encryptedImportModal: 'Файл захищено паролем',
+    passwordField: 'Пароль',
+    passwordConfirmField: 'Повторіть пароль',
+    passwordRequired: 'Введіть пароль',
+    passwordMismatch: 'Паролі не збігаються',
+    wrongPassword: 'Невірний пароль або пошкоджений файл',
+    encryptAndExport: 'Зашифрувати та експортувати',
+    decryptAndImport: 'Розшифрувати та імпортувати',
+    encryptedDesktopOnly: 'Шифрування доступне лише в застосунку',
+    filteredExport: 'Експорт з фільтром',
+    filteredExportModal: 'Вибірковий експорт',
+    filteredExportNotes: 'Нотатки для експорту',
+    filteredExportOpenSteps: 'Лише незавершені кроки',
+    filteredExportNoDescription: 'Без опису проєкту',
+    filteredExportTemplate: 'Прибрати ідентифікатори (шаблон)',
+    filteredExportDesktopOnly: 'Вибірковий експорт доступний лише в застосунку',
+    documentExport: 'Експорт у документ',
+    documentExportModal: 'Експорт у документ',
+    documentExportWorkspace: 'Усі проєкти',
+    documentExportHint: 'Файл зберігається в теку «Завантаження»',
+    documentExportDesktopOnly: 'Експорт у документ доступний лише в застосунку',
+    documentExported: 'Файл збережено: {path}',
+    documentHtml: 'HTML-звіт',
+    documentPdf: 'PDF-звіт',
+    documentEpub: 'Електронна книга (EPUB)',
+    documentDocx: 'Документ Word (DOCX)',
+    documentSlides: 'Презентація (HTML)',
+    projectMap: 'Мапа проєкту',
+    projectMapModal: 'Мапа проєкту',
+    projectMapSave: 'Зберегти SVG',
+    projectMapDesktopOnly: 'Мапа проєкту доступна лише в застосунку',
+    projectMapError: 'Не вдалося побудувати мапу проєкту',
+    documentStepsCsv: 'Кроки в CSV',
+    importCenter: 'Імпорт з інших програм',
+    importCenterModal: 'Імпорт',
+    importCenterDesktopOnly: 'Імпорт доступний лише в застосунку',
+    importSourceError: 'Не вдалося імпортувати файл',
+    importReportModal: 'Результат імпорту',
+    importReportDetails: 'Не перенесено:',
+    importStepsCsv: 'Кроки з CSV',
+    stepsCsvSummary: 'Оновлено кроків: {updated}, додано: {appended}',
+    importTodoist: 'Todoist (CSV)',
+    importTrello: 'Trello (JSON)',
+    importMicrosoftTodo: 'Microsoft To Do (JSON)',
+    importGoogleKeep: 'Google Keep (Takeout)',
+    importEvernote: 'Evernote (ENEX)',
+    importJoplin: 'Joplin (RAW)',
+    documentOpml: 'Структура (OPML)',
+    importOpml: 'Структура (OPML)',
+    documentOrg: 'Org-mode',
+    importOrg: 'Org-mode (оновити проєкти)',
+    orgSummary: 'Оновлено елементів: {updated}, додано: {added}',
+    importedProjects: 'Імпортовано проєктів: {count}',
+    csvLine: 'Рядок',
+    backup: 'Резервна копія',
+    backupCreate: 'Створити копію',
+    backupRestore: 'Відновити з копії',
+    backupRestoreModal: 'Відновлення з копії',
+    backupRestoreHint: 'Об’єднання додасть проєкти, яких ще немає. Заміна видалить поточні проєкти, вкладення та історію.',
+    backupMerge: 'Об’єднати',
+    backupReplace: 'Замінити все',
+    backupRestored: 'Копію від {date} відновлено: додано проєктів {added}, пропущено {skipped}',
+    backupError: 'Не вдалося обробити резервну копію',
+    backupDesktopOnly: 'Резервні копії доступні лише в застосунку',
+    attachmentCleanup: 'Видалити невикористані вкладення',
+    attachmentCleanupDone: 'Видалено файлів: {count}, звільнено {size} МБ',
+    attachmentCleanupError: 'Не вдалося очистити вкладення',
+    attachmentCleanupDesktopOnly: 'Очищення вкладень доступне лише в застосунку',
+    qrShare: 'Передати через QR-код',
+    qrShareModal: 'QR-коди проєкту',
+    qrShareHint: 'Відскануйте коди по черзі на іншому пристрої',
+    qrImport: 'Імпорт з QR-кодів',
+    qrImportModal: 'Імпорт з QR-кодів',
+    qrImportField: 'Відскановані коди, по одному на рядок',
+    qrImportError: 'Не вдалося прочитати QR-коди',
+    qrMissingParts: 'Бракує кодів: {parts}',
+    qrTooLarge: 'Проєкт завеликий для передачі через QR-коди',
+    qrDesktopOnly: 'QR-передача доступна лише в застосунку',
     projectExported: 'Проєкт експортовано',
     projectExportError: 'Не вдалося експортувати проєкт',
     projectImported: 'Проєкт імпортовано',
@@ -250,6 +808,165 @@ export const I18N = {
     updateCheckStarted: 'Перевіряємо оновлення...',
     alwaysOnTopYes: 'Поверх вікон: Так',
     alwaysOnTopNo: 'Поверх вікон: Ні',
+    closeToTrayYes: 'Згортати в трей при закритті: Так',
+    closeToTrayNo: 'Згортати в трей при закритті: Ні',
+    history: 'Історія змін',
+    gitHistoryOn: 'Історія в git: Увімк',
+    gitHistoryOff: 'Історія в git: Вимк',
+    projectHistory: 'Історія змін у git',
+    projectHistoryModal: 'Історія змін',
+    projectHistoryScope: 'Що показати',
+    projectHistoryWholeProject: 'Увесь проєкт',
+    projectHistoryEmpty: 'Збережених версій поки немає',
+    projectHistoryCompare: 'Відмінності вибраної версії від поточної:',
+    projectHistoryDesktopOnly: 'Історія змін доступна лише в застосунку',
+    projectHistoryDisabled: 'Увімкніть історію в git у налаштуваннях',
+    projectHistoryError: 'Не вдалося завантажити історію',
+    reminders: 'Нагадування',
+    remindersModal: 'Нагадування',
+    timeZone: 'Часовий пояс',
+    timeZoneSystem: 'Як у системі',
+    schedule: 'Термін і нагадування',
+    dueAt: 'Термін',
+    remindAt: 'Нагадати',
+    projectDeadline: 'Термін проєкту',
+    snooze10m: 'Через 10 хв',
+    snooze1h: 'Через годину',
+    snoozeTomorrow: 'Завтра',
+    reminderDone: 'Виконано',
+    reminderDismiss: 'Приховати',
+    reminderMissed: 'пропущено',
+    reminderSnoozed: 'Нагадування відкладено',
+    reminderSnoozeError: 'Не вдалося відкласти нагадування',
+    backlinks: 'Посилаються',
+    brokenLinks: 'Биті посилання',
+    brokenLink_missing: 'Нотатку не знайдено',
+    brokenLink_ambiguous: 'Підходить кілька нотаток — вкажіть «Проєкт/Нотатка»',
+    wikiLinkBroken: 'Не вдалося знайти нотатку «{target}»',
+    linksRewriteModal: 'Перейменування нотатки',
+    linksRewritePrompt: 'На цю нотатку посилаються: {count}. Оновити посилання на нову назву?',
+    renameOnly: 'Лише перейменувати',
+    renameAndRewrite: 'Оновити посилання',
+    linksRewritten: 'Оновлено посилань: {count}',
+    linksRewriteError: 'Не вдалося оновити посилання',
+    noteRevisions: 'Історія нотатки',
+    noteRevisionsModal: 'Версії нотатки',
+    noteRevisionsEmpty: 'Збережених версій поки немає',
+    noteRevisionsDesktopOnly: 'Історія версій доступна лише в десктопній версії',
+    noteRevisionsError: 'Не вдалося завантажити версії нотатки',
+    revisionCompareWith: 'Порівняти з',
+    revisionCompareNone: 'Без порівняння',
+    revisionChars: '{count} симв.',
+    revisionDiffLines: 'За рядками',
+    revisionDiffWords: 'За словами',
+    revisionDiffStats: '+{insertions} / −{deletions}',
+    revisionRestore: 'Відновити цю версію',
+    revisionRestored: 'Версію нотатки відновлено',
+    revisionRestoreError: 'Не вдалося відновити версію',
+    startTimer: 'Запустити таймер',
+    stopTimer: 'Зупинити таймер',
+    timerError: 'Не вдалося перемкнути таймер',
+    timeTrackingDesktopOnly: 'Облік часу доступний лише в десктопній версії',
+    timeEntryStart: 'Початок',
+    timeEntryMinutes: 'Хвилин',
+    timeEntryNote: 'Коментар',
+    addTimeEntry: 'Додати час',
+    removeTimeEntry: 'Видалити запис часу',
+    timeEntryManual: 'Вручну',
+    timeEntryTimer: 'Таймер',
+    timeEntryAddError: 'Не вдалося додати запис часу',
+    timeEntryRemoveError: 'Не вдалося видалити запис часу',
+    timesheet: 'Табель',
+    timesheetModal: 'Табель обліку часу',
+    timesheetFrom: 'З',
+    timesheetTo: 'По',
+    timesheetScopeProject: 'Цей проєкт',
+    timesheetScopeAll: 'Усі проєкти',
+    timesheetPeriod_day: 'По днях',
+    timesheetPeriod_week: 'По тижнях',
+    timesheetPeriod_month: 'По місяцях',
+    timesheetTotal: 'Разом',
+    timesheetEmpty: 'За обраний період час не записано',
+    exportTimesheet: 'Експорт табеля в CSV',
+    timesheetExported: 'Табель збережено в завантаження',
+    timesheetError: 'Не вдалося побудувати табель',
+    recurrence: 'Повтор',
+    recurrence_none: 'Не повторювати',
+    recurrence_daily: 'Щодня',
+    recurrence_weekly: 'Щотижня',
+    recurrence_monthly: 'Щомісяця',
+    recurrence_custom: 'Правило RRULE',
+    recurrenceRule: 'Правило (RFC 5545)',
+    recurrenceUpcoming: 'Найближчі',
+    recurrenceInvalid: 'Правило повтору не розпізнано',
+    recurrenceCompleted: 'Виконано',
+    recurrenceNext: 'Наступне повторення: {date}',
+    recurrenceError: 'Не вдалося створити наступне повторення',
+    weekday_MO: 'Пн',
+    weekday_TU: 'Вт',
+    weekday_WE: 'Ср',
+    weekday_TH: 'Чт',
+    weekday_FR: 'Пт',
+    weekday_SA: 'Сб',
+    weekday_SU: 'Нд',
+    templates: 'Шаблони проєктів',
+    templatesModal: 'Шаблони проєктів',
+    templatesDesktopOnly: 'Шаблони доступні лише в настільному застосунку',
+    templatesError: 'Не вдалося виконати дію з шаблонами',
+    templatesEmpty: 'Шаблонів поки немає — збережіть проєкт як шаблон',
+    templateName: 'Назва шаблону',
+    templateSaveProject: 'Зберегти поточний проєкт',
+    templateSaved: 'Шаблон збережено',
+    templatePlaceholderHint: 'Позначки виконання, дати, облік часу та вкладення не зберігаються. Використовуйте {{client}} та подібні підстановки в текстах.',
+    templateCounts: 'Нотаток: {notes}, кроків: {steps}',
+    templateUse: 'Створити',
+    deleteTemplate: 'Видалити шаблон',
+    templateBack: 'Назад',
+    templateCreateProject: 'Створити проєкт',
+    templateBuiltIns: '{{date}} і {{year}} заповнюються автоматично; порожні поля залишаться як є.',
+    templateProjectCreated: 'Проєкт створено з шаблону',
+    importTemplates: 'Імпорт',
+    exportTemplates: 'Експорт',
+    templatesExported: 'Бібліотеку шаблонів експортовано',
+    templatesImported: 'Шаблони імпортовано: нових {added}, оновлено {replaced}',
+    templatesImportError: 'Не вдалося імпортувати бібліотеку шаблонів',
+    linkOpenError: 'Не вдалося відкрити посилання',
+    markdownHint: 'Підтримується Markdown: таблиці, списки завдань, блоки коду, посилання [[Нотатка]]',
+    attachFile: 'Прикріпити файл',
+    openAttachment: 'Відкрити вкладення',
+    saveAttachment: 'Зберегти в завантаження',
+    removeAttachment: 'Відкріпити',
+    attachmentsDesktopOnly: 'Вкладення доступні лише в настільній версії',
+    attachmentAddError: 'Не вдалося прикріпити файл',
+    attachmentOpenError: 'Файл вкладення не знайдено',
+    attachmentSaved: 'Вкладення збережено в завантаження',
+    attachmentRevealed: 'Цей тип файлу не відкривається напряму — вкладення збережено в завантаження',
+    attachmentImportError: 'Не вдалося відновити вкладення проєкту',
+    tagsField: 'Теги',
+    tagsPlaceholder: 'через кому, наприклад: терміново, клієнт',
+    tagManager: 'Теги',
+    tagsModal: 'Керування тегами',
+    tagsDesktopOnly: 'Керування тегами доступне лише в настільній версії',
+    tagsError: 'Не вдалося обробити теги',
+    tagsEmpty: 'Тегів поки немає',
+    tagCounts: 'проєкти: {projects}, нотатки: {notes}, кроки: {steps}',
+    renameTag: 'Перейменувати тег',
+    deleteTag: 'Видалити тег',
+    mergeTags: 'Об’єднати',
+    mergeTagsInto: 'Об’єднати вибрані теги в',
+    tagMatchAll: 'Усі вибрані',
+    tagMatchAny: 'Будь-який з вибраних',
+    findTagged: 'Знайти',
+    tagQueryEmpty: 'Нічого не знайдено',
+    tagKind_project: 'Проєкт',
+    tagKind_note: 'Нотатка',
+    tagKind_step: 'Крок',
+    autoCompleteStepsOn: 'Автозавершення батьківських кроків: Увімк',
+    autoCompleteStepsOff: 'Автозавершення батьківських кроків: Вимк',
+    indentStep: 'Зробити підкроком',
+    outdentStep: 'Підняти рівень',
+    stepTreeDesktopOnly: 'Зміна вкладеності кроків доступна в настільному застосунку',
+    stepTreeError: 'Не вдалося змінити вкладеність кроку',
     animationsOn: 'Анімації: Увімк',
     animationsOff: 'Анімації: Вимк',
     saveSettings: 'Зберегти налаштування',
@@ -5,6 +5,62 @@ export function createId() {
   return crypto.randomUUID()
 }
 
+function normalizeSchedule(source) {
+  return {
+    dueAt: typeof source?.dueAt === 'string' ? source.dueAt : '',
+    remindAt: typeof source?.remindAt === 'string' ? source.remindAt : '',
+  }
+}
+
+export function normalizeTags(source) {
+  const values = Array.isArray(source) ? source : typeof source === 'string' ? source.split(',') : []
+  const tags = []
+  for (const value of values) {
+    const tag = String(value || '').trim().replace(/^#+/, '').split(/\s+/).filter(Boolean).join(' ')
+    if (tag && !tags.some((existing) => existing.toLowerCase() === tag.toLowerCase())) tags.push(tag)
+  }
+  return tags
+}
+
+function normalizeAttachment(attachment) {
+  return {
+    hash: typeof attachment?.hash === 'string' ? attachment.hash : '',
+    name: attachment?.name || 'attachment',
+    mime: attachment?.mime || 'application/octet-stream',
+    size: Number(attachment?.size) || 0,
+  }
+}
+
+function normalizeTimeEntry(entry) {
+  return {
+    id: entry.id || createId(),
+    startedAt: entry.startedAt || '',
+    endedAt: entry.endedAt || '',
+    note: entry.note || '',
+    manual: !!entry.manual,
+  }
+}
+
+function normalizeStep(step) {
+  return {
+    id: step.id || createId(),
+    text: step.text || '',
+    done: !!step.done,
+    ...normalizeSchedule(step),
+    tags: normalizeTags(step.tags),
+    recurrence: typeof step.recurrence === 'string' ? step.recurrence : '',
+    completions: Array.isArray(step.completions)
+      ? step.completions
+          .map((completion) => ({ dueAt: completion.dueAt || '', completedAt: completion.completedAt || '' }))
+          .filter((completion) => completion.completedAt)
+      : [],
+    timeEntries: Array.isArray(step.timeEntries)
+      ? step.timeEntries.map(normalizeTimeEntry).filter((entry) => entry.startedAt && entry.endedAt)
+      : [],
+    children: Array.isArray(step.children) ? step.children.map(normalizeStep) : [],
+  }
+}
+
 export function normalizeProjects(source) {
   if (!Array.isArray(source)) return []
 
@@ -14,26 +70,20 @@ export function normalizeProjects(source) {
           id: note.id || createId(),
           title: note.title || 'No title',
           body: note.body || '',
+          tags: normalizeTags(note.tags),
+          attachments: Array.isArray(note.attachments)
+            ? note.attachments.map(normalizeAttachment).filter((attachment) => attachment.hash)
+            : [],
         }))
       : []
 
-    let steps = Array.isArray(project.steps)
-      ? project.steps.map((step) => ({
-          id: step.id || createId(),
-          text: step.text || '',
-          done: !!step.done,
-        }))
-      : []
+    let steps = Array.isArray(project.steps) ? project.steps.map(normalizeStep) : []
 
     if (!steps.length && Array.isArray(project.notes)) {
       steps = project.notes
         .flatMap((note) => {
           if (!Array.isArray(note.steps)) return []
-          return note.steps.map((step) => ({
-            id: step.id || createId(),
-            text: step.text || '',
-            done: !!step.done,
-          }))
+          return note.steps.map(normalizeStep)
         })
         .filter((step) => step.text.trim().length > 0)
     }
@@ -46,6 +96,8 @@ export function normalizeProjects(source) {
       pinned: !!project.pinned,
       notes,
       steps,
+      tags: normalizeTags(project.tags),
+      ...normalizeSchedule(project),
     }
   })
 }
@@ -77,6 +129,10 @@ export function normalizeSettings(source) {
     windowMode: normalizedWindowMode,
     alwaysOnTop: !!source?.alwaysOnTop,
     language: source?.language === 'en' || source?.language === 'uk' ? source.language : 'ru',
+    gitHistory: !!source?.gitHistory,
+    timeZone: typeof source?.timeZone === 'string' ? source.timeZone.trim() : '',
+    autoCompleteSteps: !!source?.autoCompleteSteps,
+    closeToTray: !!source?.closeToTray,
   }
 }
 
@@ -0,0 +1,121 @@
+export function mapSteps(steps, fn) {
+  return (steps || []).map((step) => fn({ ...step, children: mapSteps(step.children, fn) }))
+}
+
+export function countSteps(steps) {
+  return (steps || []).reduce((sum, step) => sum + 1 + countSteps(step.children), 0)
+}
+
+export function stepProgress(steps) {
+  return (steps || []).reduce(
+    (acc, step) => {
+      if (!step.children?.length) return { done: acc.done + (step.done ? 1 : 0), total: acc.total + 1 }
+      const nested = stepProgress(step.children)
+      return { done: acc.done + (step.done ? nested.total : nested.done), total: acc.total + nested.total }
+    },
+    { done: 0, total: 0 },
+  )
+}
+
+function syncParents(steps) {
+  return steps.map((step) => {
+    if (!step.children?.length) return step
+    const children = syncParents(step.children)
+    return { ...step, children, done: children.every((child) => child.done) }
+  })
+}
+
+function setAll(steps, done) {
+  return (steps || []).map((step) => ({ ...step, done, children: setAll(step.children, done) }))
+}
+
+export function updateStepTree(steps, stepId, patch, autoComplete = false) {
+  const next = (steps || []).map((step) => {
+    if (step.id === stepId) {
+      const updated = { ...step, ...patch }
+      if (autoComplete && 'done' in patch) updated.children = setAll(step.children, patch.done)
+      return updated
+    }
+    if (!step.children?.length) return step
+    return { ...step, children: updateStepTree(step.children, stepId, patch, autoComplete) }
+  })
+  return autoComplete && 'done' in patch ? syncParents(next) : next
+}
+
+export function removeStepTree(steps, stepId) {
+  return (steps || [])
+    .filter((step) => step.id !== stepId)
+    .map((step) => (step.children?.length ? { ...step, children: removeStepTree(step.children, stepId) } : step))
+}
+
+export function moveStepAmongSiblings(steps, stepId, offset) {
+  const index = (steps || []).findIndex((step) => step.id === stepId)
+  if (index === -1) {
+    return (steps || []).map((step) =>
+      step.children?.length ? { ...step, children: moveStepAmongSiblings(step.children, stepId, offset) } : step)
+  }
+  const target = index + offset
+  if (target < 0 || target >= steps.length) return steps
+  const next = [...steps]
+  const [moved] = next.splice(index, 1)
+  next.splice(target, 0, moved)
+  return next
+}
+
+export function findStep(steps, stepId) {
+  for (const step of steps || []) {
+    if (step.id === stepId) return step
+    const nested = findStep(step.children, stepId)
+    if (nested) return nested
+  }
+  return null
+}
+
+export function findStepLocation(steps, stepId, parentId = null) {
+  const list = steps || []
+  const index = list.findIndex((step) => step.id === stepId)
+  if (index !== -1) return { parentId, index, count: list.length }
+  for (const step of list) {
+    const nested = findStepLocation(step.children, stepId, step.id)
+    if (nested) return nested
+  }
+  return null
+}
+
+export function trackedSeconds(step, runningTimer, now) {
+  const logged = (step.timeEntries || []).reduce(
+    (sum, entry) => sum + Math.max(0, (Date.parse(entry.endedAt) - Date.parse(entry.startedAt)) / 1000 || 0),
+    0,
+  )
+  const running = runningTimer?.stepId === step.id ? Math.max(0, (now - Date.parse(runningTimer.startedAt)) / 1000 || 0) : 0
+  return Math.round(logged + running)
+}
+
+export function formatDuration(seconds) {
+  const minutes = Math.floor(seconds / 60)
+  return `${Math.floor(minutes / 60)}:${String(minutes % 60).padStart(2, '0')}`
+}
+
+export const WEEKDAY_CODES = ['MO', 'TU', 'WE', 'TH', 'FR', 'SA', 'SU']
+
+export function recurrencePreset(rule) {
+  const value = String(rule || '').trim().toUpperCase()
+  if (!value) return { kind: 'none', days: [] }
+  if (value === 'FREQ=DAILY') return { kind: 'daily', days: [] }
+  if (value === 'FREQ=MONTHLY') return { kind: 'monthly', days: [] }
+  const weekly = value.match(/^FREQ=WEEKLY(?:;BYDAY=([A-Z,]+))?$/)
+  if (weekly && (weekly[1] || '').split(',').every((day) => !day || WEEKDAY_CODES.includes(day))) {
+    return { kind: 'weekly', days: weekly[1] ? weekly[1].split(',') : [] }
+  }
+  return { kind: 'custom', days: [] }
+}
+
+export function recurrenceRule(kind, days = []) {
+  if (kind === 'daily') return 'FREQ=DAILY'
+  if (kind === 'monthly') return 'FREQ=MONTHLY'
+  if (kind === 'weekly') {
+    const ordered = WEEKDAY_CODES.filter((day) => days.includes(day))
+    return ordered.length ? `FREQ=WEEKLY;BYDAY=${ordered.join(',')}` : 'FREQ=WEEKLY'
+  }
+  return ''
+}